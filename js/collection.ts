@@ -2,9 +2,10 @@ import * as anchor from '@project-serum/anchor'
 import { Marketplace as MarketplaceDefinition, IDL } from './types/marketplace'
 import { MARKETPLACE_PROGRAM_ID } from './constant'
 import { Keypair, PublicKey, TransactionInstruction } from '@solana/web3.js'
-import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
-import { getAssociatedTokenAddress, getNftVaultPDA, getSellOrderPDA } from './getPDAs'
+import { ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { getAssociatedTokenAddress, getAuctionPDA, getAuctionVaultPDA, getEscrowPDA, getLegacyEscrowPDA, getLegacyNftVaultPDA, getPaymentMintPDA, getReferrerStatsPDA, getRoyaltyEscrowPDA, getRoyaltyVaultPDA, getSellOrderPDA, getSellOrderVaultPDA, getTraderStatsPDA, getTraitOfferPDA } from './getPDAs'
 import { getMetadata } from './metaplex'
+import { getMerkleProof, getMerkleRoot } from './merkle'
 import { programs } from '@metaplex/js'
 import * as idl from './types/marketplace.json'
 import { IdlAccounts, web3 } from "@project-serum/anchor";
@@ -35,20 +36,35 @@ export class Collection {
         nftMint: PublicKey,
         sellerNftAccount: PublicKey,
         sellerDestination: PublicKey,
+        nonce: anchor.BN,
         price: anchor.BN,
         amount: anchor.BN,
         seller: PublicKey,
+        expiry?: anchor.BN,
+        dutchAuction?: { startPrice: anchor.BN, startTime: anchor.BN, endTime: anchor.BN },
+        paymentMint?: PublicKey,
     ): Promise<TransactionInstruction> {
-        let programNftVaultPDA = await getNftVaultPDA(nftMint)
-        let sellOrderPDA = await getSellOrderPDA(sellerNftAccount, price)
+        let sellOrderPDA = await getSellOrderPDA(sellerNftAccount, nonce)
+        if (!paymentMint) {
+            let marketplaceAccount = await this.program.account.marketplace.fetch(this.marketplacePDA)
+            paymentMint = marketplaceAccount.mint
+        }
+        let programNftVaultPDA = await getSellOrderVaultPDA(this.marketplacePDA, sellOrderPDA)
+        if (!expiry) {
+            expiry = null
+        }
+        if (!dutchAuction) {
+            dutchAuction = null
+        }
 
         let metadataPDA = await Metadata.getPDA(nftMint)
-        return await this.program.methods.createSellOrder(price, amount, sellerDestination).accounts(
+        return await this.program.methods.createSellOrder(nonce, price, amount, sellerDestination, expiry, dutchAuction).accounts(
             {
                 payer: seller,
                 sellerNftTokenAccount: sellerNftAccount,
                 marketplace: this.marketplacePDA,
                 collection: this.collectionPDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, paymentMint),
                 mint: nftMint,
                 metadata: metadataPDA,
                 vault: programNftVaultPDA,
@@ -64,17 +80,41 @@ export class Collection {
         nftMint: PublicKey,
         sellerNftAccount: PublicKey,
         sellerDestination: PublicKey,
+        nonce: anchor.BN,
         price: anchor.BN,
         amount: anchor.BN,
-        seller: Keypair
+        seller: Keypair,
+        expiry?: anchor.BN,
+        dutchAuction?: { startPrice: anchor.BN, startTime: anchor.BN, endTime: anchor.BN },
+        paymentMint?: PublicKey,
     ): Promise<string> {
         let ix = await this.sellAssetInstruction(
             nftMint, sellerNftAccount, sellerDestination,
-            price, amount, seller.publicKey,
+            nonce, price, amount, seller.publicKey, expiry, dutchAuction, paymentMint,
         )
         return this._sendInstruction(ix, [seller])
     }
 
+    async updateSellOrderPriceInstruction(
+        sellOrderPDA: PublicKey,
+        price: anchor.BN,
+        seller: PublicKey,
+    ): Promise<TransactionInstruction> {
+        return await this.program.methods.updateSellOrderPrice(price).accounts({
+            authority: seller,
+            sellOrder: sellOrderPDA,
+        }).instruction()
+    }
+
+    async updateSellOrderPrice(
+        sellOrderPDA: PublicKey,
+        price: anchor.BN,
+        seller: Keypair,
+    ): Promise<string> {
+        let ix = await this.updateSellOrderPriceInstruction(sellOrderPDA, price, seller.publicKey)
+        return this._sendInstruction(ix, [seller])
+    }
+
     async removeSellOrderInstruction(
         nftMint: PublicKey,
         sellerNftAccount: PublicKey,
@@ -82,12 +122,14 @@ export class Collection {
         amount: anchor.BN,
         seller: PublicKey,
     ): Promise<TransactionInstruction> {
-        let programNftVaultPDA = await getNftVaultPDA(nftMint)
+        let programNftVaultPDA = await getSellOrderVaultPDA(this.marketplacePDA, sellOrderPDA)
+        let sellOrder = await this.program.account.sellOrder.fetch(sellOrderPDA)
         return await this.program.methods.removeSellOrder(amount).accounts({
             authority: seller,
             sellerNftTokenAccount: sellerNftAccount,
             vault: programNftVaultPDA,
             sellOrder: sellOrderPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, sellOrder.paymentMint),
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -111,6 +153,151 @@ export class Collection {
         return this._sendInstruction(ix, [seller])
     }
 
+    async expireSellOrderInstruction(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellOrderPDA: PublicKey,
+        seller: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let programNftVaultPDA = await getSellOrderVaultPDA(this.marketplacePDA, sellOrderPDA)
+        let sellOrder = await this.program.account.sellOrder.fetch(sellOrderPDA)
+        return await this.program.methods.expireSellOrder().accounts({
+            authority: seller,
+            sellerNftTokenAccount: sellerNftAccount,
+            sellOrder: sellOrderPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, sellOrder.paymentMint),
+            vault: programNftVaultPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).instruction()
+    }
+
+    async expireSellOrder(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellOrderPDA: PublicKey,
+        seller: PublicKey,
+    ): Promise<string> {
+        let ix = await this.expireSellOrderInstruction(
+            nftMint,
+            sellerNftAccount,
+            sellOrderPDA,
+            seller,
+        )
+        return this._sendInstruction(ix, [])
+    }
+
+    async migrateLegacySellOrderInstruction(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        legacySellOrderPDA: PublicKey,
+        seller: PublicKey,
+    ): Promise<TransactionInstruction> {
+        return await this.program.methods.migrateLegacySellOrder().accounts({
+            authority: seller,
+            sellerNftTokenAccount: sellerNftAccount,
+            legacySellOrder: legacySellOrderPDA,
+            legacyVault: await getLegacyNftVaultPDA(nftMint),
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).instruction()
+    }
+
+    async migrateLegacySellOrder(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        legacySellOrderPDA: PublicKey,
+        seller: Keypair,
+    ): Promise<string> {
+        let ix = await this.migrateLegacySellOrderInstruction(
+            nftMint,
+            sellerNftAccount,
+            legacySellOrderPDA,
+            seller.publicKey,
+        )
+        return this._sendInstruction(ix, [seller])
+    }
+
+    async executeLegacyBuyOfferInstruction(
+        legacyBuyOfferPDA: PublicKey,
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerFundsDestAccount: PublicKey,
+        seller: PublicKey,
+    ): Promise<TransactionInstruction> {
+        // the legacy escrow is a token account in the mint the offer was escrowed in, the mint is its first field
+        let legacyEscrowPDA = await getLegacyEscrowPDA(legacyBuyOfferPDA)
+        let legacyEscrowData = (await anchor.getProvider().connection.getAccountInfo(legacyEscrowPDA)).data
+        let paymentMintAccount = await this.program.account.paymentMint.fetch(
+            await getPaymentMintPDA(this.marketplacePDA, new PublicKey(legacyEscrowData.slice(0, 32)))
+        )
+
+        // legacy offers don't fit the BuyOffer layout: marketplace, mint, price, buyer then destination
+        let legacyBuyOfferData = (await anchor.getProvider().connection.getAccountInfo(legacyBuyOfferPDA)).data
+        let buyer = new PublicKey(legacyBuyOfferData.slice(80, 112))
+        let destination = new PublicKey(legacyBuyOfferData.slice(112, 144))
+
+        let metadata = await getMetadata(anchor.getProvider().connection, nftMint)
+        let collection = await this.getCollection()
+        let creatorsAccounts = []
+        if (!collection.ignoreCreatorFee) {
+            for (let creator of metadata.data.creators) {
+                let creatorAccount = await getRoyaltyVaultPDA(this.marketplacePDA, paymentMintAccount.mint, new PublicKey(creator.address))
+                creatorsAccounts.push({ pubkey: creatorAccount, isWritable: true, isSigner: false })
+            }
+        }
+
+        // the first fee split is paid to marketplaceDestAccount
+        let feeSplitsAccounts = paymentMintAccount.feeSplits.slice(1).map((feeSplit) => (
+            { pubkey: feeSplit.destination, isWritable: true, isSigner: false }
+        ))
+
+        let traderStatsAccounts = []
+        let traderStatsPDA = await getTraderStatsPDA(this.marketplacePDA, paymentMintAccount.mint, seller)
+        if (await anchor.getProvider().connection.getAccountInfo(traderStatsPDA)) {
+            traderStatsAccounts.push({ pubkey: traderStatsPDA, isWritable: true, isSigner: false })
+        }
+
+        return await this.program.methods.executeLegacyBuyOffer().accounts({
+            seller: seller,
+            buyer: buyer,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, paymentMintAccount.mint),
+            marketplaceDestAccount: paymentMintAccount.feesDestination,
+            legacyEscrow: legacyEscrowPDA,
+            royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, paymentMintAccount.mint),
+            sellerFundsDestAccount: sellerFundsDestAccount,
+            buyerPayingAccount: await getAssociatedTokenAddress(buyer, paymentMintAccount.mint),
+            destination: destination,
+            sellerNftAccount: sellerNftAccount,
+            metadata: await Metadata.getPDA(nftMint),
+            legacyBuyOffer: legacyBuyOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).remainingAccounts([
+            ...creatorsAccounts,
+            ...feeSplitsAccounts,
+            ...traderStatsAccounts,
+        ]).instruction()
+    }
+
+    async executeLegacyBuyOffer(
+        legacyBuyOfferPDA: PublicKey,
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerFundsDestAccount: PublicKey,
+        seller: Keypair,
+    ): Promise<string> {
+        let ix = await this.executeLegacyBuyOfferInstruction(
+            legacyBuyOfferPDA,
+            nftMint,
+            sellerNftAccount,
+            sellerFundsDestAccount,
+            seller.publicKey,
+        )
+        return this._sendInstruction(ix, [seller])
+    }
+
     async addToSellOrderInstruction(
         nftMint: PublicKey,
         sellerNftAccount: PublicKey,
@@ -118,12 +305,17 @@ export class Collection {
         amount: anchor.BN,
         seller: PublicKey,
     ): Promise<TransactionInstruction> {
-        let programNftVaultPDA = await getNftVaultPDA(nftMint)
+        let programNftVaultPDA = await getSellOrderVaultPDA(this.marketplacePDA, sellOrderPDA)
+        let sellOrder = await this.program.account.sellOrder.fetch(sellOrderPDA)
         return await this.program.methods.addQuantityToSellOrder(amount).accounts({
             authority: seller,
             sellerNftTokenAccount: sellerNftAccount,
             vault: programNftVaultPDA,
             sellOrder: sellOrderPDA,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, sellOrder.paymentMint),
+            metadata: await Metadata.getPDA(nftMint),
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -153,10 +345,17 @@ export class Collection {
         buyerNftAccount: PublicKey,
         buyerPayingAccount: PublicKey,
         wanted_quantity: anchor.BN,
+        max_total_price: anchor.BN,
         buyer: PublicKey,
+        max_unit_price?: anchor.BN,
+        referrer?: PublicKey,
+        feePassMint?: PublicKey,
+        royaltyTip?: number,
     ): Promise<TransactionInstruction> {
-        let programNftVaultPDA = await getNftVaultPDA(nftMint)
-        let marketplaceAccount = await this.program.account.marketplace.fetch(this.marketplacePDA)
+        // every sell order filled in one buy has to be priced in the same mint
+        let firstSellOrder = await this.program.account.sellOrder.fetch(sellOrdersPDA[0])
+        let paymentMintPDA = await getPaymentMintPDA(this.marketplacePDA, firstSellOrder.paymentMint)
+        let paymentMintAccount = await this.program.account.paymentMint.fetch(paymentMintPDA)
 
         let metadata = await getMetadata(
             anchor.getProvider().connection,
@@ -171,36 +370,85 @@ export class Collection {
 
         if (!collection.ignoreCreatorFee) {
             for (let creator of metadata.data.creators) {
-                let creatorAddress = new PublicKey(creator.address)
-                let creatorATA = await getAssociatedTokenAddress(creatorAddress, marketplaceAccount.mint)
+                // royalties accrue in the creator vault until claimed with claimRoyalties
+                let creatorAccount = await getRoyaltyVaultPDA(this.marketplacePDA, paymentMintAccount.mint, new PublicKey(creator.address))
 
                 creatorsAccounts.push(
-                    { pubkey: creatorATA, isWritable: true, isSigner: false },
+                    { pubkey: creatorAccount, isWritable: true, isSigner: false },
                 )
             }
         }
 
+        // the first fee split is paid to marketplaceDestAccount
+        let feeSplitsAccounts = paymentMintAccount.feeSplits.slice(1).map((feeSplit) => (
+            { pubkey: feeSplit.destination, isWritable: true, isSigner: false }
+        ))
+
+        // the referrer has to be registered with createReferrer for this payment mint
+        let referralAccounts = []
+        if (referrer) {
+            let referrerStatsPDA = await getReferrerStatsPDA(this.marketplacePDA, paymentMintAccount.mint, referrer)
+            let referrerStats = await this.program.account.referrerStats.fetch(referrerStatsPDA)
+            referralAccounts.push({ pubkey: referrerStatsPDA, isWritable: true, isSigner: false })
+            referralAccounts.push({ pubkey: referrerStats.destination, isWritable: true, isSigner: false })
+        }
+
+        // the buyer volume is only tracked once it created its trader stats
+        let traderStatsAccounts = []
+        let traderStatsPDA = await getTraderStatsPDA(this.marketplacePDA, paymentMintAccount.mint, buyer)
+        if (await anchor.getProvider().connection.getAccountInfo(traderStatsPDA)) {
+            traderStatsAccounts.push({ pubkey: traderStatsPDA, isWritable: true, isSigner: false })
+        }
+
+        // the pass is held in the buyer associated token account, collection passes also need its metadata
+        let feePassAccounts = []
+        if (feePassMint) {
+            let marketplaceAccount = await this.program.account.marketplace.fetch(this.marketplacePDA)
+            feePassAccounts.push({ pubkey: await getAssociatedTokenAddress(buyer, feePassMint), isWritable: false, isSigner: false })
+            // @ts-ignore
+            let passCollection = marketplaceAccount.feeDiscount?.pass.collection
+            if (passCollection) {
+                feePassAccounts.push({ pubkey: await Metadata.getPDA(feePassMint), isWritable: false, isSigner: false })
+                feePassAccounts.push({ pubkey: passCollection.collection, isWritable: false, isSigner: false })
+            }
+        }
+
+        // each sell order is passed as a (sell order, vault, seller destination) triple
         let sellOrders = []
         for (let sellOrderPDA of sellOrdersPDA) {
             let so = await this.program.account.sellOrder.fetch(sellOrderPDA)
             sellOrders.push({ pubkey: sellOrderPDA, isWritable: true, isSigner: false })
+            sellOrders.push({ pubkey: await getSellOrderVaultPDA(this.marketplacePDA, sellOrderPDA), isWritable: true, isSigner: false })
             sellOrders.push({ pubkey: so.destination, isWritable: true, isSigner: false })
         }
 
-        return await this.program.methods.buy(wanted_quantity).accounts({
+        if (!max_unit_price) {
+            max_unit_price = null
+        }
+        // basis points of the royalties added on top of the price when the collection accepts tips
+        if (!royaltyTip) {
+            royaltyTip = 0
+        }
+
+        return await this.program.methods.buy(wanted_quantity, max_total_price, max_unit_price, royaltyTip).accounts({
             buyer: buyer,
             buyerNftTokenAccount: buyerNftAccount,
             buyerPayingTokenAccount: buyerPayingAccount,
             marketplace: this.marketplacePDA,
-            marketplaceDestAccount: marketplaceAccount.feesDestination,
+            paymentMint: paymentMintPDA,
+            marketplaceDestAccount: paymentMintAccount.feesDestination,
+            royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, paymentMintAccount.mint),
             collection: this.collectionPDA,
             // metadata: await Metadata.getPDA(metadata.mint),
             metadata: await Metadata.getPDA(nftMint),
-            vault: programNftVaultPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
         }).remainingAccounts([
             ...creatorsAccounts,
+            ...feeSplitsAccounts,
+            ...referralAccounts,
+            ...traderStatsAccounts,
+            ...feePassAccounts,
             ...sellOrders,
         ]).instruction()
     }
@@ -211,7 +459,12 @@ export class Collection {
         buyerNftAccount: PublicKey,
         buyerPayingAccount: PublicKey,
         wanted_quantity: anchor.BN,
+        max_total_price: anchor.BN,
         buyer: Keypair,
+        max_unit_price?: anchor.BN,
+        referrer?: PublicKey,
+        feePassMint?: PublicKey,
+        royaltyTip?: number,
     ): Promise<string> {
         let ix = await this.buyInstruction(
             nftMint,
@@ -219,12 +472,363 @@ export class Collection {
             buyerNftAccount,
             buyerPayingAccount,
             wanted_quantity,
+            max_total_price,
             buyer.publicKey,
+            max_unit_price,
+            referrer,
+            feePassMint,
+            royaltyTip,
         )
 
         return this._sendInstruction(ix, [buyer])
     }
 
+    async createTraitOfferInstruction(
+        nftMints: PublicKey[],
+        price: anchor.BN,
+        quantity: anchor.BN,
+        buyerPayingAccount: PublicKey,
+        buyer: PublicKey,
+        paymentMint?: PublicKey,
+    ): Promise<TransactionInstruction> {
+        if (!paymentMint) {
+            let marketplaceAccount = await this.program.account.marketplace.fetch(this.marketplacePDA)
+            paymentMint = marketplaceAccount.mint
+        }
+        let merkleRoot = getMerkleRoot(nftMints)
+        return await this.program.methods.createTraitOffer([...merkleRoot], price, quantity).accounts({
+            payer: buyer,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, paymentMint),
+            escrow: await getEscrowPDA(this.marketplacePDA, paymentMint),
+            buyerPayingAccount: buyerPayingAccount,
+            traitOffer: await getTraitOfferPDA(this.marketplacePDA, buyer, merkleRoot, price),
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).instruction()
+    }
+
+    async createTraitOffer(
+        nftMints: PublicKey[],
+        price: anchor.BN,
+        quantity: anchor.BN,
+        buyerPayingAccount: PublicKey,
+        buyer: Keypair,
+        paymentMint?: PublicKey,
+    ): Promise<string> {
+        let ix = await this.createTraitOfferInstruction(nftMints, price, quantity, buyerPayingAccount, buyer.publicKey, paymentMint)
+        return this._sendInstruction(ix, [buyer])
+    }
+
+    async removeTraitOfferInstruction(
+        traitOfferPDA: PublicKey,
+        buyerPayingAccount: PublicKey,
+        buyer: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let traitOffer = await this.program.account.traitOffer.fetch(traitOfferPDA)
+        return await this.program.methods.removeTraitOffer().accounts({
+            buyer: buyer,
+            buyerPayingAccount: buyerPayingAccount,
+            marketplace: this.marketplacePDA,
+            escrow: await getEscrowPDA(this.marketplacePDA, traitOffer.paymentMint),
+            traitOffer: traitOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).instruction()
+    }
+
+    async removeTraitOffer(
+        traitOfferPDA: PublicKey,
+        buyerPayingAccount: PublicKey,
+        buyer: Keypair,
+    ): Promise<string> {
+        let ix = await this.removeTraitOfferInstruction(traitOfferPDA, buyerPayingAccount, buyer.publicKey)
+        return this._sendInstruction(ix, [buyer])
+    }
+
+    async executeTraitOfferInstruction(
+        traitOfferPDA: PublicKey,
+        nftMints: PublicKey[],
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerFundsDestAccount: PublicKey,
+        seller: PublicKey,
+        referrer?: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let traitOffer = await this.program.account.traitOffer.fetch(traitOfferPDA)
+        let metadata = await getMetadata(anchor.getProvider().connection, nftMint)
+
+        let collection = await this.getCollection()
+        let creatorsAccounts = []
+        if (!collection.ignoreCreatorFee) {
+            for (let creator of metadata.data.creators) {
+                let creatorAccount = await getRoyaltyVaultPDA(this.marketplacePDA, traitOffer.paymentMint, new PublicKey(creator.address))
+                creatorsAccounts.push({ pubkey: creatorAccount, isWritable: true, isSigner: false })
+            }
+        }
+
+        // the first fee split is paid to marketplaceDestAccount
+        let paymentMintPDA = await getPaymentMintPDA(this.marketplacePDA, traitOffer.paymentMint)
+        let paymentMintAccount = await this.program.account.paymentMint.fetch(paymentMintPDA)
+        let feeSplitsAccounts = paymentMintAccount.feeSplits.slice(1).map((feeSplit) => (
+            { pubkey: feeSplit.destination, isWritable: true, isSigner: false }
+        ))
+
+        // the referrer can be neither the buyer nor the seller
+        let referralAccounts = []
+        if (referrer) {
+            let referrerStatsPDA = await getReferrerStatsPDA(this.marketplacePDA, paymentMintAccount.mint, referrer)
+            let referrerStats = await this.program.account.referrerStats.fetch(referrerStatsPDA)
+            referralAccounts.push({ pubkey: referrerStatsPDA, isWritable: true, isSigner: false })
+            referralAccounts.push({ pubkey: referrerStats.destination, isWritable: true, isSigner: false })
+        }
+
+        let traderStatsAccounts = []
+        let traderStatsPDA = await getTraderStatsPDA(this.marketplacePDA, paymentMintAccount.mint, seller)
+        if (await anchor.getProvider().connection.getAccountInfo(traderStatsPDA)) {
+            traderStatsAccounts.push({ pubkey: traderStatsPDA, isWritable: true, isSigner: false })
+        }
+
+        // the unused maker fee goes back to the buyer wallet for native offers, to its associated token account otherwise
+        let buyerPayingAccount = traitOffer.paymentMint.equals(NATIVE_MINT)
+            ? traitOffer.authority
+            : await getAssociatedTokenAddress(traitOffer.authority, paymentMintAccount.mint)
+
+        // the proof is built from the same mint list the offer root was computed from
+        let proof = getMerkleProof(nftMints, nftMint).map((node) => [...node])
+        return await this.program.methods.executeTraitOffer(proof).accounts({
+            seller: seller,
+            buyer: traitOffer.authority,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: paymentMintPDA,
+            marketplaceDestAccount: paymentMintAccount.feesDestination,
+            escrow: await getEscrowPDA(this.marketplacePDA, traitOffer.paymentMint),
+            royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, traitOffer.paymentMint),
+            sellerFundsDestAccount: sellerFundsDestAccount,
+            buyerPayingAccount: buyerPayingAccount,
+            nftMint: nftMint,
+            sellerNftAccount: sellerNftAccount,
+            buyerNftAccount: await getAssociatedTokenAddress(traitOffer.authority, nftMint),
+            metadata: await Metadata.getPDA(nftMint),
+            traitOffer: traitOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).remainingAccounts([
+            ...creatorsAccounts,
+            ...feeSplitsAccounts,
+            ...referralAccounts,
+            ...traderStatsAccounts,
+        ]).instruction()
+    }
+
+    async executeTraitOffer(
+        traitOfferPDA: PublicKey,
+        nftMints: PublicKey[],
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerFundsDestAccount: PublicKey,
+        seller: Keypair,
+        referrer?: PublicKey,
+    ): Promise<string> {
+        let ix = await this.executeTraitOfferInstruction(
+            traitOfferPDA,
+            nftMints,
+            nftMint,
+            sellerNftAccount,
+            sellerFundsDestAccount,
+            seller.publicKey,
+            referrer,
+        )
+        return this._sendInstruction(ix, [seller])
+    }
+
+    async createAuctionInstruction(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerDestination: PublicKey,
+        reservePrice: anchor.BN,
+        minBidIncrement: anchor.BN,
+        endTime: anchor.BN,
+        seller: PublicKey,
+        paymentMint?: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let auctionPDA = await getAuctionPDA(this.marketplacePDA, seller, nftMint)
+        if (!paymentMint) {
+            let marketplaceAccount = await this.program.account.marketplace.fetch(this.marketplacePDA)
+            paymentMint = marketplaceAccount.mint
+        }
+        return await this.program.methods.createAuction(reservePrice, minBidIncrement, endTime, sellerDestination).accounts({
+            payer: seller,
+            sellerNftTokenAccount: sellerNftAccount,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, paymentMint),
+            mint: nftMint,
+            metadata: await Metadata.getPDA(nftMint),
+            auction: auctionPDA,
+            vault: await getAuctionVaultPDA(this.marketplacePDA, auctionPDA),
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).instruction()
+    }
+
+    async createAuction(
+        nftMint: PublicKey,
+        sellerNftAccount: PublicKey,
+        sellerDestination: PublicKey,
+        reservePrice: anchor.BN,
+        minBidIncrement: anchor.BN,
+        endTime: anchor.BN,
+        seller: Keypair,
+        paymentMint?: PublicKey,
+    ): Promise<string> {
+        let ix = await this.createAuctionInstruction(
+            nftMint, sellerNftAccount, sellerDestination,
+            reservePrice, minBidIncrement, endTime, seller.publicKey, paymentMint,
+        )
+        return this._sendInstruction(ix, [seller])
+    }
+
+    async placeBidInstruction(
+        auctionPDA: PublicKey,
+        amount: anchor.BN,
+        bidderPayingAccount: PublicKey,
+        bidder: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let auction = await this.program.account.auction.fetch(auctionPDA)
+        // the outbid bidder is refunded to its wallet for native auctions, to its associated token account otherwise,
+        // unused before the first bid
+        let previousBidderPayingAccount = auction.highestBid.isZero()
+            ? bidderPayingAccount
+            : auction.paymentMint.equals(NATIVE_MINT)
+                ? auction.highestBidder
+                : await getAssociatedTokenAddress(auction.highestBidder, auction.paymentMint)
+        return await this.program.methods.placeBid(amount).accounts({
+            bidder: bidder,
+            bidderPayingAccount: bidderPayingAccount,
+            previousBidderPayingAccount: previousBidderPayingAccount,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            escrow: await getEscrowPDA(this.marketplacePDA, auction.paymentMint),
+            auction: auctionPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).instruction()
+    }
+
+    async placeBid(
+        auctionPDA: PublicKey,
+        amount: anchor.BN,
+        bidderPayingAccount: PublicKey,
+        bidder: Keypair,
+    ): Promise<string> {
+        let ix = await this.placeBidInstruction(auctionPDA, amount, bidderPayingAccount, bidder.publicKey)
+        return this._sendInstruction(ix, [bidder])
+    }
+
+    async settleAuctionInstruction(
+        auctionPDA: PublicKey,
+        payer: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let auction = await this.program.account.auction.fetch(auctionPDA)
+
+        // royalties and fee splits are only paid when the auction got a bid
+        let collection = await this.getCollection()
+        let paymentMintPDA = await getPaymentMintPDA(this.marketplacePDA, auction.paymentMint)
+        let paymentMintAccount = await this.program.account.paymentMint.fetch(paymentMintPDA)
+        let creatorsAccounts = []
+        let feeSplitsAccounts = []
+        let traderStatsAccounts = []
+        if (!auction.highestBid.isZero()) {
+            if (!collection.ignoreCreatorFee) {
+                let metadata = await getMetadata(anchor.getProvider().connection, auction.mint)
+                for (let creator of metadata.data.creators) {
+                    let creatorAccount = await getRoyaltyVaultPDA(this.marketplacePDA, auction.paymentMint, new PublicKey(creator.address))
+                    creatorsAccounts.push({ pubkey: creatorAccount, isWritable: true, isSigner: false })
+                }
+            }
+            feeSplitsAccounts = paymentMintAccount.feeSplits.slice(1).map((feeSplit) => (
+                { pubkey: feeSplit.destination, isWritable: true, isSigner: false }
+            ))
+            // the seller takes the highest bid
+            let traderStatsPDA = await getTraderStatsPDA(this.marketplacePDA, paymentMintAccount.mint, auction.authority)
+            if (await anchor.getProvider().connection.getAccountInfo(traderStatsPDA)) {
+                traderStatsAccounts.push({ pubkey: traderStatsPDA, isWritable: true, isSigner: false })
+            }
+        }
+
+        // the unused maker fee goes back to the bidder wallet for native auctions, to its associated token account
+        // otherwise, unused without a bid
+        let highestBidderPayingAccount = auction.highestBid.isZero()
+            ? auction.destination
+            : auction.paymentMint.equals(NATIVE_MINT)
+                ? auction.highestBidder
+                : await getAssociatedTokenAddress(auction.highestBidder, auction.paymentMint)
+        return await this.program.methods.settleAuction().accounts({
+            payer: payer,
+            authority: auction.authority,
+            highestBidder: auction.highestBidder,
+            marketplace: this.marketplacePDA,
+            collection: this.collectionPDA,
+            paymentMint: paymentMintPDA,
+            marketplaceDestAccount: paymentMintAccount.feesDestination,
+            escrow: await getEscrowPDA(this.marketplacePDA, auction.paymentMint),
+            royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, auction.paymentMint),
+            sellerFundsDestAccount: auction.destination,
+            highestBidderPayingAccount: highestBidderPayingAccount,
+            mint: auction.mint,
+            metadata: await Metadata.getPDA(auction.mint),
+            winnerNftAccount: await getAssociatedTokenAddress(auction.highestBidder, auction.mint),
+            auction: auctionPDA,
+            vault: await getAuctionVaultPDA(this.marketplacePDA, auctionPDA),
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).remainingAccounts([...creatorsAccounts, ...feeSplitsAccounts, ...traderStatsAccounts]).instruction()
+    }
+
+    async settleAuction(
+        auctionPDA: PublicKey,
+        payer: Keypair,
+    ): Promise<string> {
+        let ix = await this.settleAuctionInstruction(auctionPDA, payer.publicKey)
+        return this._sendInstruction(ix, [payer])
+    }
+
+    async cancelAuctionInstruction(
+        auctionPDA: PublicKey,
+        sellerNftAccount: PublicKey,
+        seller: PublicKey,
+    ): Promise<TransactionInstruction> {
+        let auction = await this.program.account.auction.fetch(auctionPDA)
+        return await this.program.methods.cancelAuction().accounts({
+            authority: seller,
+            sellerNftTokenAccount: sellerNftAccount,
+            marketplace: this.marketplacePDA,
+            paymentMint: await getPaymentMintPDA(this.marketplacePDA, auction.paymentMint),
+            auction: auctionPDA,
+            vault: await getAuctionVaultPDA(this.marketplacePDA, auctionPDA),
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).instruction()
+    }
+
+    async cancelAuction(
+        auctionPDA: PublicKey,
+        sellerNftAccount: PublicKey,
+        seller: Keypair,
+    ): Promise<string> {
+        let ix = await this.cancelAuctionInstruction(auctionPDA, sellerNftAccount, seller.publicKey)
+        return this._sendInstruction(ix, [seller])
+    }
+
     async getCollection(): Promise<IdlAccounts<MarketplaceDefinition>["collection"]> {
         if (this.collectionCache) {
             return this.collectionCache
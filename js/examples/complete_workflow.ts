@@ -24,7 +24,8 @@ async function workflow(marketplaceMint: PublicKey, nftMint: PublicKey) {
         await getAssociatedTokenAddress(
             anchor.Wallet.local().payer.publicKey,
             marketplaceMint,
-        )
+        ),
+        new anchor.BN(24 * 60 * 60),
     )
 
     await marketplace.createCollection(
@@ -43,6 +44,7 @@ async function workflow(marketplaceMint: PublicKey, nftMint: PublicKey) {
 
     let collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
 
+    let sellNonce = new anchor.BN(0)
     let sellPrice = new anchor.BN(1000)
     let sellQuantity = new anchor.BN(1)
 
@@ -51,6 +53,7 @@ async function workflow(marketplaceMint: PublicKey, nftMint: PublicKey) {
         nftMint,
         userNftAccount,
         userTokenAccount,
+        sellNonce,
         sellPrice,
         sellQuantity,
         anchor.Wallet.local().payer
@@ -59,10 +62,11 @@ async function workflow(marketplaceMint: PublicKey, nftMint: PublicKey) {
     //We buy our own asset just for demonstration
     await collection.buy(
         nftMint,
-        [await getSellOrderPDA(userNftAccount, sellPrice)],
+        [await getSellOrderPDA(userNftAccount, sellNonce)],
         userNftAccount,
         userTokenAccount,
         sellQuantity,
+        sellPrice.mul(sellQuantity),
         anchor.Wallet.local().payer
     )
 }
@@ -26,6 +26,82 @@ export const getEscrowPDA = async (marketplacePDA: PublicKey, marketplaceMint: P
     ))[0]
 }
 
+// holds the SPL royalties accrued for the creators of a payment mint
+export const getRoyaltyEscrowPDA = async (marketplacePDA: PublicKey, mint: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            mint.toBuffer(),
+            Buffer.from('ROYALTY_ESCROW'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getBuyerEscrowPDA = async (
+    marketplacePDA: PublicKey,
+    marketplaceMint: PublicKey,
+    buyer: PublicKey,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            marketplaceMint.toBuffer(),
+            buyer.toBuffer(),
+            Buffer.from('ESCROW'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getPaymentMintPDA = async (marketplacePDA: PublicKey, mint: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            mint.toBuffer(),
+            Buffer.from('PAYMENT_MINT'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getReferrerStatsPDA = async (
+    marketplacePDA: PublicKey,
+    paymentMint: PublicKey,
+    referrer: PublicKey,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            paymentMint.toBuffer(),
+            referrer.toBuffer(),
+            Buffer.from('REFERRER'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getRoyaltyVaultPDA = async (
+    marketplacePDA: PublicKey,
+    paymentMint: PublicKey,
+    creator: PublicKey,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            paymentMint.toBuffer(),
+            creator.toBuffer(),
+            Buffer.from('ROYALTY_VAULT'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
 export const getCollectionPDA = async (marketplacePDA: PublicKey, symbol: string): Promise<PublicKey> => {
     return (await anchor.web3.PublicKey.findProgramAddress(
         [
@@ -37,19 +113,104 @@ export const getCollectionPDA = async (marketplacePDA: PublicKey, symbol: string
     ))[0]
 }
 
-export const getNftVaultPDA = async (nftMint: PublicKey): Promise<PublicKey> => {
+export const getSellOrderVaultPDA = async (marketplacePDA: PublicKey, sellOrderPDA: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            sellOrderPDA.toBuffer(),
+            Buffer.from('vault'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getAuctionVaultPDA = async (marketplacePDA: PublicKey, auctionPDA: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            auctionPDA.toBuffer(),
+            Buffer.from('vault'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+// Shared per mint vault used before vaults were split per sell order, only needed to migrate legacy orders
+export const getLegacyNftVaultPDA = async (nftMint: PublicKey): Promise<PublicKey> => {
     return (await anchor.web3.PublicKey.findProgramAddress(
         [Buffer.from('MARKETPLACE'), Buffer.from('vault'), nftMint.toBuffer()],
         MARKETPLACE_PROGRAM_ID,
     ))[0]
 }
 
-export const getSellOrderPDA = async (sellerTokenAccount: PublicKey, price: anchor.BN): Promise<PublicKey> => {
+// Holds the price of one legacy buy offer once the authority recorded the mint it was escrowed in
+export const getLegacyEscrowPDA = async (legacyBuyOfferPDA: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from('MARKETPLACE'), legacyBuyOfferPDA.toBuffer(), Buffer.from('LEGACY_ESCROW')],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getSellOrderPDA = async (sellerTokenAccount: PublicKey, nonce: anchor.BN): Promise<PublicKey> => {
     return (await anchor.web3.PublicKey.findProgramAddress(
         [
             Buffer.from('MARKETPLACE'),
             sellerTokenAccount.toBuffer(),
-            Buffer.from(price.toString())
+            Buffer.from(nonce.toString()),
+            Buffer.from('SELL_ORDER'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getCollectionOfferPDA = async (
+    marketplacePDA: PublicKey,
+    buyer: PublicKey,
+    collectionPDA: PublicKey,
+    price: anchor.BN,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            buyer.toBuffer(),
+            collectionPDA.toBuffer(),
+            Buffer.from(price.toString()),
+            Buffer.from('COLLECTION_OFFER'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getTraitOfferPDA = async (
+    marketplacePDA: PublicKey,
+    buyer: PublicKey,
+    merkleRoot: Buffer,
+    price: anchor.BN,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            buyer.toBuffer(),
+            merkleRoot,
+            Buffer.from(price.toString()),
+            Buffer.from('TRAIT_OFFER'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
+
+export const getAuctionPDA = async (marketplacePDA: PublicKey, seller: PublicKey, nftMint: PublicKey): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            seller.toBuffer(),
+            nftMint.toBuffer(),
+            Buffer.from('AUCTION'),
         ],
         MARKETPLACE_PROGRAM_ID,
     ))[0]
@@ -63,4 +224,21 @@ export const getAssociatedTokenAddress = async (addr: PublicKey, mint: PublicKey
         addr,
         false,
     )
-}
\ No newline at end of file
+}
+
+export const getTraderStatsPDA = async (
+    marketplacePDA: PublicKey,
+    paymentMint: PublicKey,
+    trader: PublicKey,
+): Promise<PublicKey> => {
+    return (await anchor.web3.PublicKey.findProgramAddress(
+        [
+            Buffer.from('MARKETPLACE'),
+            marketplacePDA.toBuffer(),
+            paymentMint.toBuffer(),
+            trader.toBuffer(),
+            Buffer.from('TRADER'),
+        ],
+        MARKETPLACE_PROGRAM_ID,
+    ))[0]
+}
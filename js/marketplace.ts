@@ -5,7 +5,7 @@ import * as idl from './types/marketplace.json'
 
 import { Keypair, PublicKey } from '@solana/web3.js'
 import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
-import { getCollectionPDA, getMarketplacePDA, getEscrowPDA } from './getPDAs'
+import { getCollectionPDA, getMarketplacePDA, getEscrowPDA, getLegacyEscrowPDA, getPaymentMintPDA, getReferrerStatsPDA, getRoyaltyEscrowPDA, getRoyaltyVaultPDA, getTraderStatsPDA } from './getPDAs'
 
 export class Marketplace {
     program: anchor.Program<MarketplaceDefinition>
@@ -24,6 +24,7 @@ export class Marketplace {
         mint: PublicKey,
         fees: number,
         feesDestination: PublicKey,
+        feeTimelock: anchor.BN,
     ): Promise<string> {
         let marketplacePDA = await getMarketplacePDA(owner.publicKey)
         this.marketplacePDA = marketplacePDA
@@ -39,12 +40,14 @@ export class Marketplace {
             console.log("Already created")
             return;
         }
-        return await this.program.methods.createMarketplace(mint, fees, feesDestination, owner.publicKey).accounts(
+        return await this.program.methods.createMarketplace(mint, fees, feesDestination, owner.publicKey, feeTimelock).accounts(
             {
                 payer: owner.publicKey,
                 marketplace: marketplacePDA,
                 mint: mint,
                 escrow: escrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, mint),
+                paymentMint: await getPaymentMintPDA(marketplacePDA, mint),
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -52,6 +55,200 @@ export class Marketplace {
         ).signers([owner]).rpc()
     }
 
+    // legacy marketplaces are migrated in place, the mint has to be the marketplace mint
+    async migrateMarketplace(authority: Keypair, mint: PublicKey, feeTimelock: anchor.BN): Promise<string> {
+        return await this.program.methods.migrateMarketplace(feeTimelock).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                mint: mint,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+                royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, mint),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    async migrateCollection(authority: Keypair, collectionSymbol: string): Promise<string> {
+        return await this.program.methods.migrateCollection().accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                collection: await getCollectionPDA(this.marketplacePDA, collectionSymbol),
+                systemProgram: anchor.web3.SystemProgram.programId,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    // the mint is the one the legacy offer was escrowed in, its price moves out of the pooled escrow of that mint
+    async recordLegacyBuyOffer(authority: Keypair, legacyBuyOfferPDA: PublicKey, mint: PublicKey): Promise<string> {
+        return await this.program.methods.recordLegacyBuyOffer().accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                mint: mint,
+                escrow: await getEscrowPDA(this.marketplacePDA, mint),
+                legacyEscrow: await getLegacyEscrowPDA(legacyBuyOfferPDA),
+                legacyBuyOffer: legacyBuyOfferPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    async refundLegacyBuyOffer(
+        buyer: Keypair,
+        legacyBuyOfferPDA: PublicKey,
+        buyerPayingAccount: PublicKey,
+    ): Promise<string> {
+        return await this.program.methods.refundLegacyBuyOffer().accounts(
+            {
+                buyer: buyer.publicKey,
+                marketplace: this.marketplacePDA,
+                buyerPayingAccount: buyerPayingAccount,
+                legacyEscrow: await getLegacyEscrowPDA(legacyBuyOfferPDA),
+                legacyBuyOffer: legacyBuyOfferPDA,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }
+        ).signers([buyer]).rpc()
+    }
+
+    async addPaymentMint(
+        authority: Keypair,
+        mint: PublicKey,
+        feesDestination: PublicKey,
+    ): Promise<string> {
+        return await this.program.methods.addPaymentMint(feesDestination).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                mint: mint,
+                escrow: await getEscrowPDA(this.marketplacePDA, mint),
+                royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, mint),
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    async removePaymentMint(authority: Keypair, mint: PublicKey): Promise<string> {
+        return await this.program.methods.removePaymentMint().accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+            }
+        ).signers([authority]).rpc()
+    }
+
+    // shares are basis points of the marketplace fee and have to sum to 10000, the first destination gets the rounding dust
+    async setFeeSplits(
+        authority: Keypair,
+        mint: PublicKey,
+        feeSplits: { destination: PublicKey, share: number }[],
+    ): Promise<string> {
+        return await this.program.methods.setFeeSplits(feeSplits).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+            }
+        ).signers([authority]).rpc()
+    }
+
+    // volume is the trader volume in the payment mint from which the tier fees apply, sorted ascending
+    async setFeeTiers(
+        authority: Keypair,
+        mint: PublicKey,
+        feeTiers: { volume: anchor.BN, fees: number }[],
+    ): Promise<string> {
+        return await this.program.methods.setFeeTiers(feeTiers).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+            }
+        ).signers([authority]).rpc()
+    }
+
+    async createTraderStats(trader: Keypair, mint: PublicKey): Promise<string> {
+        return await this.program.methods.createTraderStats().accounts(
+            {
+                trader: trader.publicKey,
+                marketplace: this.marketplacePDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+                traderStats: await getTraderStatsPDA(this.marketplacePDA, mint, trader.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([trader]).rpc()
+    }
+
+    // destination is the creator wallet for native mints, a token account of the mint otherwise
+    async claimRoyalties(creator: Keypair, mint: PublicKey, destination: PublicKey): Promise<string> {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(this.marketplacePDA, mint, creator.publicKey)
+        let royaltyVault = await this.program.account.royaltyVault.fetch(royaltyVaultPDA)
+        return await this.program.methods.claimRoyalties().accounts(
+            {
+                creator: creator.publicKey,
+                marketplace: this.marketplacePDA,
+                royaltyVault: royaltyVaultPDA,
+                rentPayer: royaltyVault.rentPayer,
+                royaltyEscrow: await getRoyaltyEscrowPDA(this.marketplacePDA, mint),
+                destination: destination,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            }
+        ).signers([creator]).rpc()
+    }
+
+    // royalty vaults created by fills are paid from the lamports the marketplace holds above its own rent
+    async fundRoyaltyVaults(payer: Keypair, lamports: number): Promise<string> {
+        let tx = new anchor.web3.Transaction().add(anchor.web3.SystemProgram.transfer({
+            fromPubkey: payer.publicKey,
+            toPubkey: this.marketplacePDA,
+            lamports: lamports,
+        }))
+        return await this.program.provider.send(tx, [payer])
+    }
+
+    // feeDiscount is { pass: { mint: { mint } } } or { pass: { collection: { collection } } } with the discounted fees
+    async setFeeDiscount(authority: Keypair, feeDiscount: any): Promise<string> {
+        return await this.program.methods.setFeeDiscount(feeDiscount).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    async setReferralFee(authority: Keypair, referralFee: number): Promise<string> {
+        return await this.program.methods.setReferralFee(referralFee).accounts(
+            {
+                authority: authority.publicKey,
+                marketplace: this.marketplacePDA,
+            }
+        ).signers([authority]).rpc()
+    }
+
+    // destination is the referrer wallet for native payment mints, otherwise a token account of the payment mint
+    async createReferrer(referrer: Keypair, mint: PublicKey, destination: PublicKey): Promise<string> {
+        return await this.program.methods.createReferrer(destination).accounts(
+            {
+                referrer: referrer.publicKey,
+                marketplace: this.marketplacePDA,
+                paymentMint: await getPaymentMintPDA(this.marketplacePDA, mint),
+                referrerStats: await getReferrerStatsPDA(this.marketplacePDA, mint, referrer.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([referrer]).rpc()
+    }
+
     async createCollection(
         authority: Keypair,
         name: string,
@@ -59,6 +256,7 @@ export class Marketplace {
         collection_symbol: string,
         ignore_creators: boolean,
         fee?: number,
+        royaltyPolicy: any = { full: {} },
     ): Promise<string> {
         let collectionPDA = await getCollectionPDA(this.marketplacePDA, collection_symbol)
         const cPDAAccount = await this.program.provider.connection.getAccountInfo(collectionPDA);
@@ -70,7 +268,7 @@ export class Marketplace {
             fee = null
         }
 
-        return await this.program.methods.createCollection(collection_symbol, required_metadata_signer, fee, ignore_creators).accounts(
+        return await this.program.methods.createCollection(collection_symbol, required_metadata_signer, fee, ignore_creators, royaltyPolicy).accounts(
             {
                 authority: authority.publicKey,
                 marketplace: this.marketplacePDA,
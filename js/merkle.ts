@@ -0,0 +1,50 @@
+import { PublicKey } from '@solana/web3.js'
+import { keccak_256 } from 'js-sha3'
+
+const TRAIT_OFFER_LEAF = Buffer.from('TRAIT_OFFER_LEAF')
+
+const hashPair = (a: Buffer, b: Buffer): Buffer => {
+    // pairs are hashed sorted so proofs don't need to carry the side of each node
+    return Buffer.compare(a, b) <= 0
+        ? Buffer.from(keccak_256.arrayBuffer(Buffer.concat([a, b])))
+        : Buffer.from(keccak_256.arrayBuffer(Buffer.concat([b, a])))
+}
+
+export const getTraitLeaf = (nftMint: PublicKey): Buffer => {
+    return Buffer.from(keccak_256.arrayBuffer(Buffer.concat([TRAIT_OFFER_LEAF, nftMint.toBuffer()])))
+}
+
+const getLevels = (nftMints: PublicKey[]): Buffer[][] => {
+    let levels = [nftMints.map(getTraitLeaf)]
+    while (levels[levels.length - 1].length > 1) {
+        let level = levels[levels.length - 1]
+        let next = []
+        for (let i = 0; i < level.length; i += 2) {
+            // the last node of an odd level is carried up as is
+            next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i])
+        }
+        levels.push(next)
+    }
+    return levels
+}
+
+export const getMerkleRoot = (nftMints: PublicKey[]): Buffer => {
+    let levels = getLevels(nftMints)
+    return levels[levels.length - 1][0]
+}
+
+export const getMerkleProof = (nftMints: PublicKey[], nftMint: PublicKey): Buffer[] => {
+    let index = nftMints.findIndex((mint) => mint.equals(nftMint))
+    if (index < 0) {
+        throw new Error('mint is not part of the merkle tree')
+    }
+    let proof = []
+    for (let level of getLevels(nftMints).slice(0, -1)) {
+        let sibling = index % 2 == 0 ? index + 1 : index - 1
+        if (sibling < level.length) {
+            proof.push(level[sibling])
+        }
+        index = Math.floor(index / 2)
+    }
+    return proof
+}
@@ -25,6 +25,16 @@ export type Marketplace = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -48,7 +58,7 @@ export type Marketplace = {
         },
         {
           "name": "fees",
-          "type": "u16"
+          "type": "i16"
         },
         {
           "name": "feesDestination",
@@ -57,46 +67,15 @@ export type Marketplace = {
         {
           "name": "authority",
           "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "updateMarketplace",
-      "accounts": [
-        {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "marketplace",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "optionalFees",
-          "type": {
-            "option": "u16"
-          }
         },
         {
-          "name": "optionalFeesDestination",
-          "type": {
-            "option": "publicKey"
-          }
-        },
-        {
-          "name": "optionalAuthority",
-          "type": {
-            "option": "publicKey"
-          }
+          "name": "feeTimelock",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "updateMarketplaceMint",
+      "name": "migrateMarketplace",
       "accounts": [
         {
           "name": "authority",
@@ -114,7 +93,12 @@ export type Marketplace = {
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
           "isMut": true,
           "isSigner": false
         },
@@ -136,17 +120,13 @@ export type Marketplace = {
       ],
       "args": [
         {
-          "name": "mint",
-          "type": "publicKey"
-        },
-        {
-          "name": "feesDestination",
-          "type": "publicKey"
+          "name": "feeTimelock",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "createCollection",
+      "name": "migrateCollection",
       "accounts": [
         {
           "name": "authority",
@@ -155,7 +135,7 @@ export type Marketplace = {
         },
         {
           "name": "marketplace",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -167,36 +147,12 @@ export type Marketplace = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "symbol",
-          "type": "string"
-        },
-        {
-          "name": "requiredVerifier",
-          "type": "publicKey"
-        },
-        {
-          "name": "fee",
-          "type": {
-            "option": "u16"
-          }
-        },
-        {
-          "name": "ignoreFee",
-          "type": "bool"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "updateCollection",
+      "name": "updateMarketplace",
       "accounts": [
         {
           "name": "authority",
@@ -205,136 +161,131 @@ export type Marketplace = {
         },
         {
           "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "optionalFee",
+          "name": "optionalFees",
           "type": {
-            "option": "u16"
+            "option": "i16"
           }
         },
         {
-          "name": "optionalSymbol",
+          "name": "optionalFeesDestination",
           "type": {
-            "option": "string"
+            "option": "publicKey"
           }
         },
         {
-          "name": "optionalRequiredVerifier",
+          "name": "optionalFeeTimelock",
           "type": {
-            "option": "publicKey"
+            "option": "i64"
           }
         },
         {
-          "name": "optionalIgnoreCreatorFee",
+          "name": "optionalTakerFees",
           "type": {
-            "option": "bool"
+            "option": "u16"
           }
         }
       ]
     },
     {
-      "name": "createSellOrder",
+      "name": "proposeAuthority",
       "accounts": [
         {
-          "name": "payer",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "sellerNftTokenAccount",
+          "name": "marketplace",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "accounts": [
         {
-          "name": "collection",
+          "name": "newAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "mint",
-          "isMut": false,
+          "name": "marketplace",
+          "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "accounts": [
         {
-          "name": "metadata",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "sellOrder",
+          "name": "marketplace",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setReferralFee",
+      "accounts": [
         {
-          "name": "tokenProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "marketplace",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "price",
-          "type": "u64"
-        },
-        {
-          "name": "quantity",
-          "type": "u64"
-        },
-        {
-          "name": "destination",
-          "type": "publicKey"
+          "name": "referralFee",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "removeSellOrder",
+      "name": "createReferrer",
       "accounts": [
         {
-          "name": "authority",
+          "name": "referrer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "sellerNftTokenAccount",
-          "isMut": true,
+          "name": "marketplace",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellOrder",
-          "isMut": true,
+          "name": "paymentMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "referrerStats",
           "isMut": true,
           "isSigner": false
         },
@@ -343,11 +294,6 @@ export type Marketplace = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "rent",
           "isMut": false,
@@ -356,97 +302,144 @@ export type Marketplace = {
       ],
       "args": [
         {
-          "name": "quantityToUnlist",
-          "type": "u64"
+          "name": "destination",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "addQuantityToSellOrder",
+      "name": "setGuardian",
       "accounts": [
         {
           "name": "authority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "sellerNftTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellOrder",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
+          "name": "marketplace",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeDiscount",
+      "accounts": [
         {
-          "name": "tokenProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "marketplace",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "quantityToAdd",
-          "type": "u64"
+          "name": "feeDiscount",
+          "type": {
+            "option": {
+              "defined": "FeeDiscount"
+            }
+          }
         }
       ]
     },
     {
-      "name": "buy",
+      "name": "setMarketplacePaused",
       "accounts": [
         {
-          "name": "buyer",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "buyerNftTokenAccount",
+          "name": "marketplace",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "buyerPayingTokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "paused",
+          "type": "bool"
         },
         {
-          "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
+          "name": "reason",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setCollectionPaused",
+      "accounts": [
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "marketplaceDestAccount",
-          "isMut": true,
+          "name": "marketplace",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "collection",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
         },
         {
-          "name": "metadata",
+          "name": "reason",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateMarketplaceMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
         },
@@ -459,103 +452,173 @@ export type Marketplace = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "askQuantity",
-          "type": "u64"
+          "name": "mint",
+          "type": "publicKey"
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "createBuyOffer",
+      "name": "addPaymentMint",
       "accounts": [
         {
-          "name": "payer",
+          "name": "authority",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "nftMint",
+          "name": "marketplace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketplace",
-          "isMut": false,
+          "name": "escrow",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collection",
+          "name": "royaltyEscrow",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerPayingAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "buyerNftAccount",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "buyOffer",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "updatePaymentMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "marketplace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "paymentMint",
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setFeeSplits",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "marketplace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "paymentMint",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "priceProposition",
-          "type": "u64"
+          "name": "feeSplits",
+          "type": {
+            "vec": {
+              "defined": "FeeSplit"
+            }
+          }
         }
       ]
     },
     {
-      "name": "removeBuyOffer",
+      "name": "setFeeTiers",
       "accounts": [
         {
-          "name": "buyer",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "buyerPayingAccount",
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": {
+              "defined": "FeeTier"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimRoyalties",
+      "accounts": [
+        {
+          "name": "creator",
+          "isMut": false,
+          "isSigner": true
         },
         {
           "name": "marketplace",
@@ -563,27 +626,27 @@ export type Marketplace = {
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "royaltyVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyOffer",
+          "name": "rentPayer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "royaltyEscrow",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "destination",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -591,70 +654,82 @@ export type Marketplace = {
       "args": []
     },
     {
-      "name": "executeOffer",
+      "name": "createTraderStats",
       "accounts": [
         {
-          "name": "seller",
-          "isMut": false,
+          "name": "trader",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "buyer",
-          "isMut": true,
+          "name": "marketplace",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketplace",
+          "name": "paymentMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collection",
+          "name": "traderStats",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marketplaceDestAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrow",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removePaymentMint",
+      "accounts": [
         {
-          "name": "sellerFundsDestAccount",
+          "name": "authority",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "destination",
-          "isMut": true,
+          "name": "marketplace",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerNftAccount",
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createCollection",
+      "accounts": [
         {
-          "name": "metadata",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "buyOffer",
+          "name": "marketplace",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "collection",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -664,271 +739,118 @@ export type Marketplace = {
           "isSigner": false
         }
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
+      "args": [
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "requiredVerifier",
+          "type": "publicKey"
+        },
+        {
+          "name": "fee",
+          "type": {
+            "option": "i16"
+          }
+        },
+        {
+          "name": "ignoreFee",
+          "type": "bool"
+        },
+        {
+          "name": "royaltyPolicy",
+          "type": {
+            "defined": "RoyaltyPolicy"
+          }
+        }
+      ]
+    },
     {
-      "name": "marketplace",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "fees",
-            "type": "u16"
-          },
-          {
-            "name": "feesDestination",
-            "type": "publicKey"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          }
-        ]
-      }
-    },
-    {
-      "name": "sellOrder",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplace",
-            "type": "publicKey"
-          },
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "quantity",
-            "type": "u64"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "destination",
-            "type": "publicKey"
-          }
-        ]
-      }
-    },
-    {
-      "name": "collection",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplaceKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "requiredVerifier",
-            "type": "publicKey"
-          },
-          {
-            "name": "fees",
-            "type": {
-              "option": "u16"
-            }
-          },
-          {
-            "name": "ignoreCreatorFee",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "buyOffer",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplace",
-            "type": "publicKey"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "proposedPrice",
-            "type": "u64"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "destination",
-            "type": "publicKey"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "ErrFeeShouldLowerOrEqualThan10000",
-      "msg": "Fee should be <= 10000"
-    },
-    {
-      "code": 6001,
-      "name": "ErrTryingToUnlistMoreThanOwned",
-      "msg": "Trying to unlist more than owned"
-    },
-    {
-      "code": 6002,
-      "name": "ErrCouldNotBuyEnoughItem",
-      "msg": "Could not buy the required quantity of items"
-    },
-    {
-      "code": 6003,
-      "name": "ErrMetaDataMintDoesNotMatchItemMint",
-      "msg": "metadata mint does not match item mint"
-    },
-    {
-      "code": 6004,
-      "name": "ErrNftNotPartOfCollection",
-      "msg": "nft not part of collection"
-    },
-    {
-      "code": 6005,
-      "name": "DerivedKeyInvalid",
-      "msg": "Derived key invalid"
-    },
-    {
-      "code": 6006,
-      "name": "NotInitialized",
-      "msg": "AccountNotInitialized"
-    }
-  ]
-};
-
-export const IDL: Marketplace = {
-  "version": "0.1.0",
-  "name": "marketplace",
-  "instructions": [
-    {
-      "name": "createMarketplace",
+      "name": "updateCollection",
       "accounts": [
         {
-          "name": "payer",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
           "name": "marketplace",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "collection",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "mint",
-          "type": "publicKey"
-        },
-        {
-          "name": "fees",
-          "type": "u16"
+          "name": "optionalFee",
+          "type": {
+            "option": "i16"
+          }
         },
         {
-          "name": "feesDestination",
-          "type": "publicKey"
+          "name": "optionalSymbol",
+          "type": {
+            "option": "string"
+          }
         },
         {
-          "name": "authority",
-          "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "updateMarketplace",
-      "accounts": [
-        {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
+          "name": "optionalRequiredVerifier",
+          "type": {
+            "option": "publicKey"
+          }
         },
         {
-          "name": "marketplace",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "optionalFees",
+          "name": "optionalIgnoreCreatorFee",
           "type": {
-            "option": "u16"
+            "option": "bool"
           }
         },
         {
-          "name": "optionalFeesDestination",
+          "name": "optionalTakerFee",
           "type": {
-            "option": "publicKey"
+            "option": "u16"
           }
         },
         {
-          "name": "optionalAuthority",
+          "name": "optionalRoyaltyPolicy",
           "type": {
-            "option": "publicKey"
+            "option": {
+              "defined": "RoyaltyPolicy"
+            }
           }
         }
       ]
     },
     {
-      "name": "updateMarketplaceMint",
+      "name": "createSellOrder",
       "accounts": [
         {
-          "name": "authority",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
         },
@@ -938,7 +860,17 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
@@ -960,67 +892,80 @@ export const IDL: Marketplace = {
       ],
       "args": [
         {
-          "name": "mint",
-          "type": "publicKey"
+          "name": "nonce",
+          "type": "u64"
         },
         {
-          "name": "feesDestination",
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        },
+        {
+          "name": "destination",
           "type": "publicKey"
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "dutchAuction",
+          "type": {
+            "option": {
+              "defined": "DutchAuction"
+            }
+          }
         }
       ]
     },
     {
-      "name": "createCollection",
+      "name": "expireSellOrder",
       "accounts": [
         {
           "name": "authority",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "marketplace",
+          "name": "sellerNftTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collection",
+          "name": "sellOrder",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "paymentMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "vault",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "symbol",
-          "type": "string"
-        },
-        {
-          "name": "requiredVerifier",
-          "type": "publicKey"
         },
         {
-          "name": "fee",
-          "type": {
-            "option": "u16"
-          }
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "ignoreFee",
-          "type": "bool"
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "updateCollection",
+      "name": "updateSellOrderPrice",
       "accounts": [
         {
           "name": "authority",
@@ -1028,48 +973,23 @@ export const IDL: Marketplace = {
           "isSigner": true
         },
         {
-          "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
+          "name": "sellOrder",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "optionalFee",
-          "type": {
-            "option": "u16"
-          }
-        },
-        {
-          "name": "optionalSymbol",
-          "type": {
-            "option": "string"
-          }
-        },
-        {
-          "name": "optionalRequiredVerifier",
-          "type": {
-            "option": "publicKey"
-          }
-        },
-        {
-          "name": "optionalIgnoreCreatorFee",
-          "type": {
-            "option": "bool"
-          }
+          "name": "price",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "createSellOrder",
+      "name": "removeSellOrder",
       "accounts": [
         {
-          "name": "payer",
+          "name": "authority",
           "isMut": true,
           "isSigner": true
         },
@@ -1079,32 +999,17 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "mint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "metadata",
-          "isMut": false,
+          "name": "sellOrder",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "paymentMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellOrder",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
@@ -1126,21 +1031,13 @@ export const IDL: Marketplace = {
       ],
       "args": [
         {
-          "name": "price",
-          "type": "u64"
-        },
-        {
-          "name": "quantity",
+          "name": "quantityToUnlist",
           "type": "u64"
-        },
-        {
-          "name": "destination",
-          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "removeSellOrder",
+      "name": "migrateLegacySellOrder",
       "accounts": [
         {
           "name": "authority",
@@ -1153,37 +1050,22 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "sellOrder",
+          "name": "legacySellOrder",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "legacyVault",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "quantityToUnlist",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
       "name": "addQuantityToSellOrder",
@@ -1203,6 +1085,26 @@ export const IDL: Marketplace = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "vault",
           "isMut": true,
@@ -1236,7 +1138,7 @@ export const IDL: Marketplace = {
       "accounts": [
         {
           "name": "buyer",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
         {
@@ -1251,7 +1153,12 @@ export const IDL: Marketplace = {
         },
         {
           "name": "marketplace",
-          "isMut": false,
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1260,18 +1167,18 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "collection",
-          "isMut": false,
+          "name": "royaltyEscrow",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "collection",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vault",
-          "isMut": true,
+          "name": "metadata",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1289,6 +1196,20 @@ export const IDL: Marketplace = {
         {
           "name": "askQuantity",
           "type": "u64"
+        },
+        {
+          "name": "maxTotalPrice",
+          "type": "u64"
+        },
+        {
+          "name": "maxUnitPrice",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "royaltyTip",
+          "type": "u16"
         }
       ]
     },
@@ -1321,7 +1242,17 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
           "isMut": true,
           "isSigner": false
         },
@@ -1365,6 +1296,12 @@ export const IDL: Marketplace = {
         {
           "name": "priceProposition",
           "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          }
         }
       ]
     },
@@ -1387,7 +1324,7 @@ export const IDL: Marketplace = {
           "isSigner": false
         },
         {
-          "name": "escrow",
+          "name": "buyerEscrow",
           "isMut": true,
           "isSigner": false
         },
@@ -1415,196 +1352,9505 @@ export const IDL: Marketplace = {
       "args": []
     },
     {
-      "name": "executeOffer",
+      "name": "expireBuyOffer",
       "accounts": [
-        {
-          "name": "seller",
-          "isMut": false,
-          "isSigner": true
-        },
         {
           "name": "buyer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marketplace",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "marketplaceDestAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "escrow",
+          "name": "buyerPayingAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellerFundsDestAccount",
-          "isMut": true,
+          "name": "marketplace",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destination",
+          "name": "buyerEscrow",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellerNftAccount",
+          "name": "buyOffer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "buyOffer",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reconcileBuyerEscrow",
+      "accounts": [
         {
-          "name": "systemProgram",
+          "name": "buyer",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "marketplace",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "buyerEscrow",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "recordLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
+    {
+      "name": "refundLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "makerRebateAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createCollectionOffer",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeCollectionOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeCollectionOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createTraitOffer",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeTraitOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeTraitOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "createAuction",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "reservePrice",
+          "type": "u64"
+        },
+        {
+          "name": "minBidIncrement",
+          "type": "u64"
+        },
+        {
+          "name": "endTime",
+          "type": "i64"
+        },
+        {
+          "name": "destination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "placeBid",
+      "accounts": [
+        {
+          "name": "bidder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "bidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousBidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "settleAuction",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "highestBidder",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "highestBidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "winnerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuction",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createMultisig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "signers",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "createAdminProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "action",
+          "type": {
+            "defined": "AdminAction"
+          }
+        }
+      ]
+    },
+    {
+      "name": "approveAdminProposal",
+      "accounts": [
+        {
+          "name": "approver",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAdminProposal",
+      "accounts": [
+        {
+          "name": "canceller",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeMarketplaceProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeAddPaymentMintProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "executePaymentMintProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeUpdateMarketplaceMintProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newMarketplaceMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "executeCreateCollectionProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "symbol",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "executeUpdateCollectionProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeRecordLegacyBuyOfferProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeMigrateCollectionProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "Marketplace",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "feeTimelock",
+            "type": "i64"
+          },
+          {
+            "name": "pendingFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "guardian",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pauseReason",
+            "type": "u8"
+          },
+          {
+            "name": "referralFee",
+            "type": "u16"
+          },
+          {
+            "name": "takerFees",
+            "type": "u16"
+          },
+          {
+            "name": "pendingTakerFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "feeDiscount",
+            "type": {
+              "option": {
+                "defined": "FeeDiscount"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SellOrder",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "dutchAuction",
+            "type": {
+              "option": {
+                "defined": "DutchAuction"
+              }
+            }
+          },
+          {
+            "name": "vaultBump",
+            "type": "u8"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Collection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplaceKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "requiredVerifier",
+            "type": "publicKey"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "ignoreCreatorFee",
+            "type": "bool"
+          },
+          {
+            "name": "pendingFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pauseReason",
+            "type": "u8"
+          },
+          {
+            "name": "takerFees",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "pendingTakerFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "royaltyPolicy",
+            "type": {
+              "defined": "RoyaltyPolicy"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BuyOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedPrice",
+            "type": "u64"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CollectionOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TraitOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Auction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "reservePrice",
+            "type": "u64"
+          },
+          {
+            "name": "minBidIncrement",
+            "type": "u64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "highestBidder",
+            "type": "publicKey"
+          },
+          {
+            "name": "highestBid",
+            "type": "u64"
+          },
+          {
+            "name": "highestBidMakerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PaymentMint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "openListings",
+            "type": "u64"
+          },
+          {
+            "name": "feeSplits",
+            "type": {
+              "vec": {
+                "defined": "FeeSplit"
+              }
+            }
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "vec": {
+                "defined": "FeeTier"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ReferrerStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "referrer",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "feesEarned",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TraderStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "trader",
+            "type": "publicKey"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Multisig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "signers",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "proposalCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AdminProposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "multisig",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposer",
+            "type": "publicKey"
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": "AdminAction"
+            }
+          },
+          {
+            "name": "approvedBy",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "LegacyMarketplace",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyCollection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplaceKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "requiredVerifier",
+            "type": "publicKey"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "ignoreCreatorFee",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacySellOrder",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBuyOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedPrice",
+            "type": "u64"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PendingFees",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "effectiveAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Full"
+          },
+          {
+            "name": "Tip",
+            "fields": [
+              {
+                "name": "floor",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "Cap",
+            "fields": [
+              {
+                "name": "maxFees",
+                "type": "u16"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeDiscount",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pass",
+            "type": {
+              "defined": "FeePass"
+            }
+          },
+          {
+            "name": "fees",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeePass",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Mint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "Collection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "DutchAuction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startPrice",
+            "type": "u64"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeSplit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "share",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AdminAction",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "UpdateMarketplace",
+            "fields": [
+              {
+                "name": "optionalFees",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "optionalFeesDestination",
+                "type": {
+                  "option": "publicKey"
+                }
+              },
+              {
+                "name": "optionalFeeTimelock",
+                "type": {
+                  "option": "i64"
+                }
+              },
+              {
+                "name": "optionalTakerFees",
+                "type": {
+                  "option": "u16"
+                }
+              }
+            ]
+          },
+          {
+            "name": "ProposeAuthority",
+            "fields": [
+              {
+                "name": "newAuthority",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "SetGuardian",
+            "fields": [
+              {
+                "name": "guardian",
+                "type": {
+                  "option": "publicKey"
+                }
+              }
+            ]
+          },
+          {
+            "name": "UpdateMarketplaceMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "CreateCollection",
+            "fields": [
+              {
+                "name": "symbol",
+                "type": "string"
+              },
+              {
+                "name": "requiredVerifier",
+                "type": "publicKey"
+              },
+              {
+                "name": "fee",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "ignoreFee",
+                "type": "bool"
+              },
+              {
+                "name": "royaltyPolicy",
+                "type": {
+                  "defined": "RoyaltyPolicy"
+                }
+              }
+            ]
+          },
+          {
+            "name": "UpdateCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              },
+              {
+                "name": "optionalFee",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "optionalSymbol",
+                "type": {
+                  "option": "string"
+                }
+              },
+              {
+                "name": "optionalRequiredVerifier",
+                "type": {
+                  "option": "publicKey"
+                }
+              },
+              {
+                "name": "optionalIgnoreCreatorFee",
+                "type": {
+                  "option": "bool"
+                }
+              },
+              {
+                "name": "optionalTakerFee",
+                "type": {
+                  "option": "u16"
+                }
+              },
+              {
+                "name": "optionalRoyaltyPolicy",
+                "type": {
+                  "option": {
+                    "defined": "RoyaltyPolicy"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "CancelAuthorityTransfer"
+          },
+          {
+            "name": "SetReferralFee",
+            "fields": [
+              {
+                "name": "referralFee",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "SetFeeDiscount",
+            "fields": [
+              {
+                "name": "feeDiscount",
+                "type": {
+                  "option": {
+                    "defined": "FeeDiscount"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "AddPaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "UpdatePaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "RemovePaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "SetFeeSplits",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feeSplits",
+                "type": {
+                  "vec": {
+                    "defined": "FeeSplit"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "SetFeeTiers",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feeTiers",
+                "type": {
+                  "vec": {
+                    "defined": "FeeTier"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "SetSigners",
+            "fields": [
+              {
+                "name": "signers",
+                "type": {
+                  "vec": "publicKey"
+                }
+              },
+              {
+                "name": "threshold",
+                "type": "u8"
+              }
+            ]
+          },
+          {
+            "name": "RecordLegacyBuyOffer",
+            "fields": [
+              {
+                "name": "legacyBuyOffer",
+                "type": "publicKey"
+              },
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "MigrateCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "CreatorShare",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "SellOrderCreated",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderUpdated",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderFilled",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unitPrice",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderCancelled",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferExecuted",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expired",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MarketplaceUpdated",
+      "fields": [
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fees",
+          "type": "i16",
+          "index": false
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feeTimelock",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "pendingFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "pauseReason",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerFees",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "pendingTakerFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "feeDiscount",
+          "type": {
+            "option": {
+              "defined": "FeeDiscount"
+            }
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionUpdated",
+      "fields": [
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "symbol",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "requiredVerifier",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fees",
+          "type": {
+            "option": "i16"
+          },
+          "index": false
+        },
+        {
+          "name": "pendingFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "ignoreCreatorFee",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "pauseReason",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "takerFees",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "pendingTakerFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "royaltyPolicy",
+          "type": {
+            "defined": "RoyaltyPolicy"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PaymentMintUpdated",
+      "fields": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feeSplits",
+          "type": {
+            "vec": {
+              "defined": "FeeSplit"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": {
+              "defined": "FeeTier"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "removed",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionOfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionOfferFilled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionOfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferFilled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionCreated",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reservePrice",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minBidIncrement",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BidPlaced",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "bidder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionSettled",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "highestBidder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "highestBid",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionCancelled",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LegacyBuyOfferRecorded",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RoyaltiesClaimed",
+      "fields": [
+        {
+          "name": "royaltyVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferrerCreated",
+      "fields": [
+        {
+          "name": "referrerStats",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraderStatsCreated",
+      "fields": [
+        {
+          "name": "traderStats",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "trader",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AdminProposalUpdated",
+      "fields": [
+        {
+          "name": "proposal",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "multisig",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "action",
+          "type": {
+            "defined": "AdminAction"
+          },
+          "index": false
+        },
+        {
+          "name": "approvedBy",
+          "type": {
+            "vec": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "expiresAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cancelled",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "ErrFeeShouldLowerOrEqualThan10000",
+      "msg": "Fee should be <= 10000"
+    },
+    {
+      "code": 6001,
+      "name": "ErrTryingToUnlistMoreThanOwned",
+      "msg": "Trying to unlist more than owned"
+    },
+    {
+      "code": 6002,
+      "name": "ErrCouldNotBuyEnoughItem",
+      "msg": "Could not buy the required quantity of items"
+    },
+    {
+      "code": 6003,
+      "name": "ErrMetaDataMintDoesNotMatchItemMint",
+      "msg": "metadata mint does not match item mint"
+    },
+    {
+      "code": 6004,
+      "name": "ErrNftNotPartOfCollection",
+      "msg": "nft not part of collection"
+    },
+    {
+      "code": 6005,
+      "name": "DerivedKeyInvalid",
+      "msg": "Derived key invalid"
+    },
+    {
+      "code": 6006,
+      "name": "NotInitialized",
+      "msg": "AccountNotInitialized"
+    },
+    {
+      "code": 6007,
+      "name": "ErrExpiryShouldBeInTheFuture",
+      "msg": "Expiry should be in the future"
+    },
+    {
+      "code": 6008,
+      "name": "ErrSellOrderNotExpired",
+      "msg": "Sell order is not expired"
+    },
+    {
+      "code": 6009,
+      "name": "ErrBuyOfferNotExpired",
+      "msg": "Buy offer is not expired"
+    },
+    {
+      "code": 6010,
+      "name": "ErrBuyOfferExpired",
+      "msg": "Buy offer is expired"
+    },
+    {
+      "code": 6011,
+      "name": "ErrQuantityShouldBeGreaterThanZero",
+      "msg": "Quantity should be greater than 0"
+    },
+    {
+      "code": 6012,
+      "name": "ErrNftNotPartOfTraitOffer",
+      "msg": "nft not part of trait offer"
+    },
+    {
+      "code": 6013,
+      "name": "ErrAuctionEnded",
+      "msg": "Auction has ended"
+    },
+    {
+      "code": 6014,
+      "name": "ErrAuctionNotEnded",
+      "msg": "Auction has not ended"
+    },
+    {
+      "code": 6015,
+      "name": "ErrBidTooLow",
+      "msg": "Bid is too low"
+    },
+    {
+      "code": 6016,
+      "name": "ErrInvalidPreviousBidderAccount",
+      "msg": "Previous bidder account does not match the highest bidder"
+    },
+    {
+      "code": 6017,
+      "name": "ErrInvalidDutchAuction",
+      "msg": "Dutch auction should start above the floor price and end after it starts"
+    },
+    {
+      "code": 6018,
+      "name": "ErrUnitPriceAboveMaximum",
+      "msg": "Sell order unit price is above the maximum unit price"
+    },
+    {
+      "code": 6019,
+      "name": "ErrTotalPriceAboveMaximum",
+      "msg": "Total price is above the maximum total price"
+    },
+    {
+      "code": 6020,
+      "name": "ErrInvalidLegacySellOrder",
+      "msg": "Legacy sell order is invalid"
+    },
+    {
+      "code": 6021,
+      "name": "ErrInvalidBuyOfferForReconciliation",
+      "msg": "Buy offer does not belong to the reconciled escrow or is duplicated"
+    },
+    {
+      "code": 6022,
+      "name": "ErrEscrowOutOfBalance",
+      "msg": "Escrow balance does not match open buy offers"
+    },
+    {
+      "code": 6023,
+      "name": "ErrInvalidRefundAccount",
+      "msg": "Refund account does not belong to the buyer"
+    },
+    {
+      "code": 6024,
+      "name": "ErrNativeMintNotSupported",
+      "msg": "Native mint is not supported for this instruction"
+    },
+    {
+      "code": 6025,
+      "name": "ErrCannotRemovePrimaryPaymentMint",
+      "msg": "The marketplace mint cannot be removed from the payment mints"
+    },
+    {
+      "code": 6026,
+      "name": "ErrInvalidLegacyBuyOffer",
+      "msg": "Invalid legacy buy offer"
+    },
+    {
+      "code": 6027,
+      "name": "ErrNotPendingAuthority",
+      "msg": "Signer is not the pending authority"
+    },
+    {
+      "code": 6028,
+      "name": "ErrInvalidMultisig",
+      "msg": "Multisig needs 1 to 10 distinct signers and a threshold between 1 and the number of signers"
+    },
+    {
+      "code": 6029,
+      "name": "ErrNotMultisigSigner",
+      "msg": "Signer is not part of the multisig"
+    },
+    {
+      "code": 6030,
+      "name": "ErrProposalAlreadyApproved",
+      "msg": "Proposal already approved by this signer"
+    },
+    {
+      "code": 6031,
+      "name": "ErrNotEnoughApprovals",
+      "msg": "Proposal does not have enough approvals"
+    },
+    {
+      "code": 6032,
+      "name": "ErrInvalidProposalAction",
+      "msg": "Proposal action does not match this instruction"
+    },
+    {
+      "code": 6033,
+      "name": "ErrInvalidFeeTimelock",
+      "msg": "Fee timelock can only be raised, up to 30 days"
+    },
+    {
+      "code": 6034,
+      "name": "ErrMarketplacePaused",
+      "msg": "Marketplace is paused"
+    },
+    {
+      "code": 6035,
+      "name": "ErrCollectionPaused",
+      "msg": "Collection is paused"
+    },
+    {
+      "code": 6036,
+      "name": "ErrNotAuthorityOrGuardian",
+      "msg": "Signer is neither the marketplace authority nor its guardian"
+    },
+    {
+      "code": 6037,
+      "name": "ErrInvalidFeeSplits",
+      "msg": "Fee splits should have at most 5 unique destinations with non zero shares summing to 10000"
+    },
+    {
+      "code": 6038,
+      "name": "ErrInvalidFeeSplitAccounts",
+      "msg": "Fee split destinations do not match the payment mint fee splits"
+    },
+    {
+      "code": 6039,
+      "name": "ErrInvalidReferral",
+      "msg": "Referrer stats do not belong to this marketplace and payment mint or the destination does not match"
+    },
+    {
+      "code": 6040,
+      "name": "ErrFeeBelowMinimum",
+      "msg": "Fee is below the minimum allowed"
+    },
+    {
+      "code": 6041,
+      "name": "ErrInvalidMakerRebateAccount",
+      "msg": "Maker rebate account should belong to the buyer and hold the payment mint"
+    },
+    {
+      "code": 6042,
+      "name": "ErrInvalidFeeTiers",
+      "msg": "Fee tiers should be sorted by volume, at most 5 and lower or equal than 10000"
+    },
+    {
+      "code": 6043,
+      "name": "ErrInvalidTraderStats",
+      "msg": "Trader stats do not belong to the taker of this marketplace and payment mint"
+    },
+    {
+      "code": 6044,
+      "name": "ErrInvalidFeePass",
+      "msg": "Fee pass account should be a token account of the pass held by the buyer"
+    },
+    {
+      "code": 6045,
+      "name": "ErrInvalidAuction",
+      "msg": "Auction reserve price and minimum bid increment should be greater than zero"
+    },
+    {
+      "code": 6046,
+      "name": "ErrAuctionHasBids",
+      "msg": "Auction already has bids"
+    },
+    {
+      "code": 6047,
+      "name": "ErrInvalidLegacyMarketplace",
+      "msg": "Account is not a legacy marketplace of this authority and mint"
+    },
+    {
+      "code": 6048,
+      "name": "ErrInvalidLegacyCollection",
+      "msg": "Account is not a legacy collection of this marketplace"
+    },
+    {
+      "code": 6049,
+      "name": "ErrProposalExpired",
+      "msg": "Admin proposal expired"
+    },
+    {
+      "code": 6050,
+      "name": "ErrSelfReferral",
+      "msg": "Referrer can't be the buyer or the seller of the fill"
+    },
+    {
+      "code": 6051,
+      "name": "ErrFeesExceedPrice",
+      "msg": "Fees and royalties can't exceed the price"
+    },
+    {
+      "code": 6052,
+      "name": "ErrRoyaltyVaultUnfunded",
+      "msg": "Neither the marketplace nor the signer of the fill hold enough lamports to fund the royalty vault"
+    },
+    {
+      "code": 6053,
+      "name": "ErrPaymentMintInUse",
+      "msg": "The payment mint still has open sell orders or auctions"
+    },
+    {
+      "code": 6054,
+      "name": "ErrNotProposer",
+      "msg": "Only the proposer can cancel a proposal before it expires"
+    },
+    {
+      "code": 6055,
+      "name": "ErrInvalidSellOrder",
+      "msg": "Sell order does not belong to this marketplace, nft mint and payment mint"
+    },
+    {
+      "code": 6056,
+      "name": "ErrInvalidSellOrderVault",
+      "msg": "Vault does not belong to the sell order"
+    },
+    {
+      "code": 6057,
+      "name": "ErrInvalidSellerDestination",
+      "msg": "Seller account does not match the sell order destination"
+    },
+    {
+      "code": 6058,
+      "name": "ErrInvalidRoyaltyVault",
+      "msg": "Royalty vault does not belong to the creator, marketplace and payment mint"
+    }
+  ]
+};
+
+export const IDL: Marketplace = {
+  "version": "0.1.0",
+  "name": "marketplace",
+  "instructions": [
+    {
+      "name": "createMarketplace",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        },
+        {
+          "name": "fees",
+          "type": "i16"
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        },
+        {
+          "name": "authority",
+          "type": "publicKey"
+        },
+        {
+          "name": "feeTimelock",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "migrateMarketplace",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTimelock",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "migrateCollection",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateMarketplace",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "optionalFees",
+          "type": {
+            "option": "i16"
+          }
+        },
+        {
+          "name": "optionalFeesDestination",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "optionalFeeTimelock",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "optionalTakerFees",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "proposeAuthority",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "accounts": [
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setReferralFee",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "referralFee",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createReferrer",
+      "accounts": [
+        {
+          "name": "referrer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referrerStats",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "destination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setGuardian",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeDiscount",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeDiscount",
+          "type": {
+            "option": {
+              "defined": "FeeDiscount"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMarketplacePaused",
+      "accounts": [
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        },
+        {
+          "name": "reason",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setCollectionPaused",
+      "accounts": [
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        },
+        {
+          "name": "reason",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateMarketplaceMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "mint",
+          "type": "publicKey"
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "addPaymentMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "updatePaymentMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feesDestination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setFeeSplits",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeSplits",
+          "type": {
+            "vec": {
+              "defined": "FeeSplit"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeTiers",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": {
+              "defined": "FeeTier"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimRoyalties",
+      "accounts": [
+        {
+          "name": "creator",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rentPayer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createTraderStats",
+      "accounts": [
+        {
+          "name": "trader",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removePaymentMint",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createCollection",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "requiredVerifier",
+          "type": "publicKey"
+        },
+        {
+          "name": "fee",
+          "type": {
+            "option": "i16"
+          }
+        },
+        {
+          "name": "ignoreFee",
+          "type": "bool"
+        },
+        {
+          "name": "royaltyPolicy",
+          "type": {
+            "defined": "RoyaltyPolicy"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateCollection",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "optionalFee",
+          "type": {
+            "option": "i16"
+          }
+        },
+        {
+          "name": "optionalSymbol",
+          "type": {
+            "option": "string"
+          }
+        },
+        {
+          "name": "optionalRequiredVerifier",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "optionalIgnoreCreatorFee",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "optionalTakerFee",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "optionalRoyaltyPolicy",
+          "type": {
+            "option": {
+              "defined": "RoyaltyPolicy"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "createSellOrder",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u64"
+        },
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        },
+        {
+          "name": "destination",
+          "type": "publicKey"
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "dutchAuction",
+          "type": {
+            "option": {
+              "defined": "DutchAuction"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "expireSellOrder",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateSellOrderPrice",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeSellOrder",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "quantityToUnlist",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migrateLegacySellOrder",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacySellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "addQuantityToSellOrder",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellOrder",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "quantityToAdd",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "buy",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "askQuantity",
+          "type": "u64"
+        },
+        {
+          "name": "maxTotalPrice",
+          "type": "u64"
+        },
+        {
+          "name": "maxUnitPrice",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "royaltyTip",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createBuyOffer",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "priceProposition",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeBuyOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "expireBuyOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reconcileBuyerEscrow",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "recordLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "refundLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeLegacyBuyOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "makerRebateAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createCollectionOffer",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeCollectionOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeCollectionOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collectionOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createTraitOffer",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeTraitOffer",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeTraitOffer",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traitOffer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "createAuction",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "reservePrice",
+          "type": "u64"
+        },
+        {
+          "name": "minBidIncrement",
+          "type": "u64"
+        },
+        {
+          "name": "endTime",
+          "type": "i64"
+        },
+        {
+          "name": "destination",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "placeBid",
+      "accounts": [
+        {
+          "name": "bidder",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "bidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "previousBidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "settleAuction",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "highestBidder",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplaceDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerFundsDestAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "highestBidderPayingAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "winnerNftAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuction",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auction",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createMultisig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "signers",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "createAdminProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "action",
+          "type": {
+            "defined": "AdminAction"
+          }
+        }
+      ]
+    },
+    {
+      "name": "approveAdminProposal",
+      "accounts": [
+        {
+          "name": "approver",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAdminProposal",
+      "accounts": [
+        {
+          "name": "canceller",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeMarketplaceProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeAddPaymentMintProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "executePaymentMintProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeUpdateMarketplaceMintProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newMarketplaceMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "executeCreateCollectionProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "symbol",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "executeUpdateCollectionProposal",
+      "accounts": [
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeRecordLegacyBuyOfferProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyEscrow",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "legacyBuyOffer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeMigrateCollectionProposal",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "multisig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marketplace",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "Marketplace",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "feeTimelock",
+            "type": "i64"
+          },
+          {
+            "name": "pendingFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "guardian",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pauseReason",
+            "type": "u8"
+          },
+          {
+            "name": "referralFee",
+            "type": "u16"
+          },
+          {
+            "name": "takerFees",
+            "type": "u16"
+          },
+          {
+            "name": "pendingTakerFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "feeDiscount",
+            "type": {
+              "option": {
+                "defined": "FeeDiscount"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SellOrder",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "dutchAuction",
+            "type": {
+              "option": {
+                "defined": "DutchAuction"
+              }
+            }
+          },
+          {
+            "name": "vaultBump",
+            "type": "u8"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Collection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplaceKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "requiredVerifier",
+            "type": "publicKey"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "ignoreCreatorFee",
+            "type": "bool"
+          },
+          {
+            "name": "pendingFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pauseReason",
+            "type": "u8"
+          },
+          {
+            "name": "takerFees",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "pendingTakerFees",
+            "type": {
+              "option": {
+                "defined": "PendingFees"
+              }
+            }
+          },
+          {
+            "name": "royaltyPolicy",
+            "type": {
+              "defined": "RoyaltyPolicy"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "BuyOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedPrice",
+            "type": "u64"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CollectionOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TraitOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Auction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "reservePrice",
+            "type": "u64"
+          },
+          {
+            "name": "minBidIncrement",
+            "type": "u64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "highestBidder",
+            "type": "publicKey"
+          },
+          {
+            "name": "highestBid",
+            "type": "u64"
+          },
+          {
+            "name": "highestBidMakerFee",
+            "type": "u64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PaymentMint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "openListings",
+            "type": "u64"
+          },
+          {
+            "name": "feeSplits",
+            "type": {
+              "vec": {
+                "defined": "FeeSplit"
+              }
+            }
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "vec": {
+                "defined": "FeeTier"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ReferrerStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "referrer",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "feesEarned",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TraderStats",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "trader",
+            "type": "publicKey"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Multisig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "signers",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "proposalCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AdminProposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "multisig",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposer",
+            "type": "publicKey"
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": "AdminAction"
+            }
+          },
+          {
+            "name": "approvedBy",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "LegacyMarketplace",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "feesDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyCollection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplaceKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "requiredVerifier",
+            "type": "publicKey"
+          },
+          {
+            "name": "fees",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "ignoreCreatorFee",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacySellOrder",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LegacyBuyOffer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketplace",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedPrice",
+            "type": "u64"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "destination",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PendingFees",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fees",
+            "type": "i16"
+          },
+          {
+            "name": "effectiveAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyPolicy",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Full"
+          },
+          {
+            "name": "Tip",
+            "fields": [
+              {
+                "name": "floor",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "Cap",
+            "fields": [
+              {
+                "name": "maxFees",
+                "type": "u16"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeDiscount",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pass",
+            "type": {
+              "defined": "FeePass"
+            }
+          },
+          {
+            "name": "fees",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeePass",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Mint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "Collection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "DutchAuction",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startPrice",
+            "type": "u64"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeSplit",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "share",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AdminAction",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "UpdateMarketplace",
+            "fields": [
+              {
+                "name": "optionalFees",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "optionalFeesDestination",
+                "type": {
+                  "option": "publicKey"
+                }
+              },
+              {
+                "name": "optionalFeeTimelock",
+                "type": {
+                  "option": "i64"
+                }
+              },
+              {
+                "name": "optionalTakerFees",
+                "type": {
+                  "option": "u16"
+                }
+              }
+            ]
+          },
+          {
+            "name": "ProposeAuthority",
+            "fields": [
+              {
+                "name": "newAuthority",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "SetGuardian",
+            "fields": [
+              {
+                "name": "guardian",
+                "type": {
+                  "option": "publicKey"
+                }
+              }
+            ]
+          },
+          {
+            "name": "UpdateMarketplaceMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "CreateCollection",
+            "fields": [
+              {
+                "name": "symbol",
+                "type": "string"
+              },
+              {
+                "name": "requiredVerifier",
+                "type": "publicKey"
+              },
+              {
+                "name": "fee",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "ignoreFee",
+                "type": "bool"
+              },
+              {
+                "name": "royaltyPolicy",
+                "type": {
+                  "defined": "RoyaltyPolicy"
+                }
+              }
+            ]
+          },
+          {
+            "name": "UpdateCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              },
+              {
+                "name": "optionalFee",
+                "type": {
+                  "option": "i16"
+                }
+              },
+              {
+                "name": "optionalSymbol",
+                "type": {
+                  "option": "string"
+                }
+              },
+              {
+                "name": "optionalRequiredVerifier",
+                "type": {
+                  "option": "publicKey"
+                }
+              },
+              {
+                "name": "optionalIgnoreCreatorFee",
+                "type": {
+                  "option": "bool"
+                }
+              },
+              {
+                "name": "optionalTakerFee",
+                "type": {
+                  "option": "u16"
+                }
+              },
+              {
+                "name": "optionalRoyaltyPolicy",
+                "type": {
+                  "option": {
+                    "defined": "RoyaltyPolicy"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "CancelAuthorityTransfer"
+          },
+          {
+            "name": "SetReferralFee",
+            "fields": [
+              {
+                "name": "referralFee",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "SetFeeDiscount",
+            "fields": [
+              {
+                "name": "feeDiscount",
+                "type": {
+                  "option": {
+                    "defined": "FeeDiscount"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "AddPaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "UpdatePaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feesDestination",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "RemovePaymentMint",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "SetFeeSplits",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feeSplits",
+                "type": {
+                  "vec": {
+                    "defined": "FeeSplit"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "SetFeeTiers",
+            "fields": [
+              {
+                "name": "mint",
+                "type": "publicKey"
+              },
+              {
+                "name": "feeTiers",
+                "type": {
+                  "vec": {
+                    "defined": "FeeTier"
+                  }
+                }
+              }
+            ]
+          },
+          {
+            "name": "SetSigners",
+            "fields": [
+              {
+                "name": "signers",
+                "type": {
+                  "vec": "publicKey"
+                }
+              },
+              {
+                "name": "threshold",
+                "type": "u8"
+              }
+            ]
+          },
+          {
+            "name": "RecordLegacyBuyOffer",
+            "fields": [
+              {
+                "name": "legacyBuyOffer",
+                "type": "publicKey"
+              },
+              {
+                "name": "mint",
+                "type": "publicKey"
+              }
+            ]
+          },
+          {
+            "name": "MigrateCollection",
+            "fields": [
+              {
+                "name": "collection",
+                "type": "publicKey"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "CreatorShare",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "destination",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "SellOrderCreated",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderUpdated",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderFilled",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unitPrice",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SellOrderCancelled",
+      "fields": [
+        {
+          "name": "sellOrder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expiry",
+          "type": {
+            "option": "i64"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferExecuted",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expired",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MarketplaceUpdated",
+      "fields": [
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "guardian",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fees",
+          "type": "i16",
+          "index": false
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feeTimelock",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "pendingFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "pauseReason",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerFees",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "pendingTakerFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "feeDiscount",
+          "type": {
+            "option": {
+              "defined": "FeeDiscount"
+            }
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionUpdated",
+      "fields": [
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "symbol",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "requiredVerifier",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fees",
+          "type": {
+            "option": "i16"
+          },
+          "index": false
+        },
+        {
+          "name": "pendingFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "ignoreCreatorFee",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "pauseReason",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "takerFees",
+          "type": {
+            "option": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "pendingTakerFees",
+          "type": {
+            "option": {
+              "defined": "PendingFees"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "royaltyPolicy",
+          "type": {
+            "defined": "RoyaltyPolicy"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PaymentMintUpdated",
+      "fields": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feesDestination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "feeSplits",
+          "type": {
+            "vec": {
+              "defined": "FeeSplit"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": {
+              "defined": "FeeTier"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "removed",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionOfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CollectionOfferFilled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
     {
-      "name": "marketplace",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "fees",
-            "type": "u16"
+      "name": "CollectionOfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferCreated",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
           },
-          {
-            "name": "feesDestination",
-            "type": "publicKey"
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferFilled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
           },
-          {
-            "name": "authority",
-            "type": "publicKey"
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "referralShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "remainingQuantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TraitOfferCancelled",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionCreated",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "collection",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reservePrice",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minBidIncrement",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BidPlaced",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "bidder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionSettled",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "highestBidder",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "highestBid",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketplaceShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorShares",
+          "type": {
+            "vec": {
+              "defined": "CreatorShare"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "sellerShare",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerRebate",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuctionCancelled",
+      "fields": [
+        {
+          "name": "auction",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "seller",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LegacyBuyOfferRecorded",
+      "fields": [
+        {
+          "name": "offer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "sellOrder",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplace",
-            "type": "publicKey"
-          },
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "quantity",
-            "type": "u64"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "destination",
-            "type": "publicKey"
-          }
-        ]
-      }
+      "name": "RoyaltiesClaimed",
+      "fields": [
+        {
+          "name": "royaltyVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "collection",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplaceKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "requiredVerifier",
-            "type": "publicKey"
-          },
-          {
-            "name": "fees",
-            "type": {
-              "option": "u16"
-            }
-          },
-          {
-            "name": "ignoreCreatorFee",
-            "type": "bool"
-          }
-        ]
-      }
+      "name": "ReferrerCreated",
+      "fields": [
+        {
+          "name": "referrerStats",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "buyOffer",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "marketplace",
-            "type": "publicKey"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "proposedPrice",
-            "type": "u64"
+      "name": "TraderStatsCreated",
+      "fields": [
+        {
+          "name": "traderStats",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketplace",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "trader",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AdminProposalUpdated",
+      "fields": [
+        {
+          "name": "proposal",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "multisig",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "action",
+          "type": {
+            "defined": "AdminAction"
           },
-          {
-            "name": "authority",
-            "type": "publicKey"
+          "index": false
+        },
+        {
+          "name": "approvedBy",
+          "type": {
+            "vec": "publicKey"
           },
-          {
-            "name": "destination",
-            "type": "publicKey"
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "expiresAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cancelled",
+          "type": "bool",
+          "index": false
+        }
+      ]
     }
   ],
   "errors": [
@@ -1642,6 +10888,266 @@ export const IDL: Marketplace = {
       "code": 6006,
       "name": "NotInitialized",
       "msg": "AccountNotInitialized"
+    },
+    {
+      "code": 6007,
+      "name": "ErrExpiryShouldBeInTheFuture",
+      "msg": "Expiry should be in the future"
+    },
+    {
+      "code": 6008,
+      "name": "ErrSellOrderNotExpired",
+      "msg": "Sell order is not expired"
+    },
+    {
+      "code": 6009,
+      "name": "ErrBuyOfferNotExpired",
+      "msg": "Buy offer is not expired"
+    },
+    {
+      "code": 6010,
+      "name": "ErrBuyOfferExpired",
+      "msg": "Buy offer is expired"
+    },
+    {
+      "code": 6011,
+      "name": "ErrQuantityShouldBeGreaterThanZero",
+      "msg": "Quantity should be greater than 0"
+    },
+    {
+      "code": 6012,
+      "name": "ErrNftNotPartOfTraitOffer",
+      "msg": "nft not part of trait offer"
+    },
+    {
+      "code": 6013,
+      "name": "ErrAuctionEnded",
+      "msg": "Auction has ended"
+    },
+    {
+      "code": 6014,
+      "name": "ErrAuctionNotEnded",
+      "msg": "Auction has not ended"
+    },
+    {
+      "code": 6015,
+      "name": "ErrBidTooLow",
+      "msg": "Bid is too low"
+    },
+    {
+      "code": 6016,
+      "name": "ErrInvalidPreviousBidderAccount",
+      "msg": "Previous bidder account does not match the highest bidder"
+    },
+    {
+      "code": 6017,
+      "name": "ErrInvalidDutchAuction",
+      "msg": "Dutch auction should start above the floor price and end after it starts"
+    },
+    {
+      "code": 6018,
+      "name": "ErrUnitPriceAboveMaximum",
+      "msg": "Sell order unit price is above the maximum unit price"
+    },
+    {
+      "code": 6019,
+      "name": "ErrTotalPriceAboveMaximum",
+      "msg": "Total price is above the maximum total price"
+    },
+    {
+      "code": 6020,
+      "name": "ErrInvalidLegacySellOrder",
+      "msg": "Legacy sell order is invalid"
+    },
+    {
+      "code": 6021,
+      "name": "ErrInvalidBuyOfferForReconciliation",
+      "msg": "Buy offer does not belong to the reconciled escrow or is duplicated"
+    },
+    {
+      "code": 6022,
+      "name": "ErrEscrowOutOfBalance",
+      "msg": "Escrow balance does not match open buy offers"
+    },
+    {
+      "code": 6023,
+      "name": "ErrInvalidRefundAccount",
+      "msg": "Refund account does not belong to the buyer"
+    },
+    {
+      "code": 6024,
+      "name": "ErrNativeMintNotSupported",
+      "msg": "Native mint is not supported for this instruction"
+    },
+    {
+      "code": 6025,
+      "name": "ErrCannotRemovePrimaryPaymentMint",
+      "msg": "The marketplace mint cannot be removed from the payment mints"
+    },
+    {
+      "code": 6026,
+      "name": "ErrInvalidLegacyBuyOffer",
+      "msg": "Invalid legacy buy offer"
+    },
+    {
+      "code": 6027,
+      "name": "ErrNotPendingAuthority",
+      "msg": "Signer is not the pending authority"
+    },
+    {
+      "code": 6028,
+      "name": "ErrInvalidMultisig",
+      "msg": "Multisig needs 1 to 10 distinct signers and a threshold between 1 and the number of signers"
+    },
+    {
+      "code": 6029,
+      "name": "ErrNotMultisigSigner",
+      "msg": "Signer is not part of the multisig"
+    },
+    {
+      "code": 6030,
+      "name": "ErrProposalAlreadyApproved",
+      "msg": "Proposal already approved by this signer"
+    },
+    {
+      "code": 6031,
+      "name": "ErrNotEnoughApprovals",
+      "msg": "Proposal does not have enough approvals"
+    },
+    {
+      "code": 6032,
+      "name": "ErrInvalidProposalAction",
+      "msg": "Proposal action does not match this instruction"
+    },
+    {
+      "code": 6033,
+      "name": "ErrInvalidFeeTimelock",
+      "msg": "Fee timelock can only be raised, up to 30 days"
+    },
+    {
+      "code": 6034,
+      "name": "ErrMarketplacePaused",
+      "msg": "Marketplace is paused"
+    },
+    {
+      "code": 6035,
+      "name": "ErrCollectionPaused",
+      "msg": "Collection is paused"
+    },
+    {
+      "code": 6036,
+      "name": "ErrNotAuthorityOrGuardian",
+      "msg": "Signer is neither the marketplace authority nor its guardian"
+    },
+    {
+      "code": 6037,
+      "name": "ErrInvalidFeeSplits",
+      "msg": "Fee splits should have at most 5 unique destinations with non zero shares summing to 10000"
+    },
+    {
+      "code": 6038,
+      "name": "ErrInvalidFeeSplitAccounts",
+      "msg": "Fee split destinations do not match the payment mint fee splits"
+    },
+    {
+      "code": 6039,
+      "name": "ErrInvalidReferral",
+      "msg": "Referrer stats do not belong to this marketplace and payment mint or the destination does not match"
+    },
+    {
+      "code": 6040,
+      "name": "ErrFeeBelowMinimum",
+      "msg": "Fee is below the minimum allowed"
+    },
+    {
+      "code": 6041,
+      "name": "ErrInvalidMakerRebateAccount",
+      "msg": "Maker rebate account should belong to the buyer and hold the payment mint"
+    },
+    {
+      "code": 6042,
+      "name": "ErrInvalidFeeTiers",
+      "msg": "Fee tiers should be sorted by volume, at most 5 and lower or equal than 10000"
+    },
+    {
+      "code": 6043,
+      "name": "ErrInvalidTraderStats",
+      "msg": "Trader stats do not belong to the taker of this marketplace and payment mint"
+    },
+    {
+      "code": 6044,
+      "name": "ErrInvalidFeePass",
+      "msg": "Fee pass account should be a token account of the pass held by the buyer"
+    },
+    {
+      "code": 6045,
+      "name": "ErrInvalidAuction",
+      "msg": "Auction reserve price and minimum bid increment should be greater than zero"
+    },
+    {
+      "code": 6046,
+      "name": "ErrAuctionHasBids",
+      "msg": "Auction already has bids"
+    },
+    {
+      "code": 6047,
+      "name": "ErrInvalidLegacyMarketplace",
+      "msg": "Account is not a legacy marketplace of this authority and mint"
+    },
+    {
+      "code": 6048,
+      "name": "ErrInvalidLegacyCollection",
+      "msg": "Account is not a legacy collection of this marketplace"
+    },
+    {
+      "code": 6049,
+      "name": "ErrProposalExpired",
+      "msg": "Admin proposal expired"
+    },
+    {
+      "code": 6050,
+      "name": "ErrSelfReferral",
+      "msg": "Referrer can't be the buyer or the seller of the fill"
+    },
+    {
+      "code": 6051,
+      "name": "ErrFeesExceedPrice",
+      "msg": "Fees and royalties can't exceed the price"
+    },
+    {
+      "code": 6052,
+      "name": "ErrRoyaltyVaultUnfunded",
+      "msg": "Neither the marketplace nor the signer of the fill hold enough lamports to fund the royalty vault"
+    },
+    {
+      "code": 6053,
+      "name": "ErrPaymentMintInUse",
+      "msg": "The payment mint still has open sell orders or auctions"
+    },
+    {
+      "code": 6054,
+      "name": "ErrNotProposer",
+      "msg": "Only the proposer can cancel a proposal before it expires"
+    },
+    {
+      "code": 6055,
+      "name": "ErrInvalidSellOrder",
+      "msg": "Sell order does not belong to this marketplace, nft mint and payment mint"
+    },
+    {
+      "code": 6056,
+      "name": "ErrInvalidSellOrderVault",
+      "msg": "Vault does not belong to the sell order"
+    },
+    {
+      "code": 6057,
+      "name": "ErrInvalidSellerDestination",
+      "msg": "Seller account does not match the sell order destination"
+    },
+    {
+      "code": 6058,
+      "name": "ErrInvalidRoyaltyVault",
+      "msg": "Royalty vault does not belong to the creator, marketplace and payment mint"
     }
   ]
 };
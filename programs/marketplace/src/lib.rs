@@ -2,28 +2,35 @@ mod transfer;
 
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use anchor_lang::prelude::*;
-use anchor_lang::AccountsClose;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::{AccountsClose, Discriminator};
 use metaplex_token_metadata::state::PREFIX as METAPLEX_PREFIX;
 use metaplex_token_metadata::state::{Creator, Metadata};
-use std::str::FromStr;
 use anchor_spl::associated_token::AssociatedToken;
 use metaplex_token_metadata::utils::{assert_derivation};
-use crate::constant::{ASSOCIATED_TOKEN_PROGRAM};
-use crate::constant::{PREFIX, ESCROW};
+use crate::constant::{PREFIX, ESCROW, SELL_ORDER, LEGACY_SELL_ORDER_SPACE, LEGACY_BUY_OFFER_SPACE, MARKETPLACE_SPACE, LEGACY_MARKETPLACE_SPACE, COLLECTION_SPACE, LEGACY_COLLECTION_SPACE, COLLECTION_OFFER, TRAIT_OFFER, TRAIT_OFFER_LEAF, AUCTION, AUCTION_EXTENSION_WINDOW, PAYMENT_MINT, MULTISIG, ADMIN_PROPOSAL, MAX_MULTISIG_SIGNERS, ADMIN_PROPOSAL_LIFETIME, MAX_FEE_TIMELOCK, MAX_FEE_SPLITS, REFERRER, TRADER, MAX_FEE_TIERS, ROYALTY_VAULT, ROYALTY_VAULT_SPACE, ROYALTY_ESCROW, PAYMENT_MINT_SPACE, LEGACY_ESCROW};
+use crate::transfer::{pay, pay_with_signer, close_with_signer, pay_native, pay_native_from_escrow};
 
 declare_id!("fHuLXbJW45hJJmk8Ryi7mfopauynVRLnvB3yHNB64bA");
 
 #[program]
 pub mod marketplace {
-    use crate::transfer::{pay, pay_with_signer};
     use super::*;
     use anchor_lang::solana_program::{
-        program::{invoke, invoke_signed},
+        program::invoke_signed,
         system_instruction,
     };
 
     pub fn create_marketplace(
-        ctx: Context<CreateMarketplace>, mint: Pubkey, fees: u16, fees_destination: Pubkey, authority: Pubkey,
+        ctx: Context<CreateMarketplace>,
+        mint: Pubkey,
+        fees: i16,
+        fees_destination: Pubkey,
+        authority: Pubkey,
+        fee_timelock: i64,
     ) -> Result<()> {
         let marketplace = &mut ctx.accounts.marketplace;
 
@@ -31,29 +38,172 @@ pub mod marketplace {
         marketplace.fees_destination = fees_destination;
         marketplace.authority = authority;
         marketplace.mint = mint;
+        marketplace.fee_timelock = fee_timelock;
+
+        let payment_mint = &mut ctx.accounts.payment_mint;
+        payment_mint.marketplace = marketplace.key();
+        payment_mint.mint = mint;
+        payment_mint.fees_destination = fees_destination;
+
+        marketplace.validate()?;
+        emit_marketplace_updated(marketplace);
+        emit_payment_mint_updated(payment_mint, false);
+        Ok(())
+    }
+
+    pub fn migrate_marketplace(ctx: Context<MigrateMarketplace>, fee_timelock: i64) -> Result<()> {
+        let marketplace_info = ctx.accounts.marketplace.to_account_info();
+        if marketplace_info.owner != ctx.program_id {
+            return Err(error!(ErrorCode::ErrInvalidLegacyMarketplace));
+        }
+
+        let legacy_marketplace = {
+            let data = marketplace_info.try_borrow_data()?;
+            if data.len() != LEGACY_MARKETPLACE_SPACE || data[..8] != Marketplace::discriminator() {
+                return Err(error!(ErrorCode::ErrInvalidLegacyMarketplace));
+            }
+            LegacyMarketplace::deserialize(&mut &data[8..])
+                .map_err(|_| error!(ErrorCode::ErrInvalidLegacyMarketplace))?
+        };
+        if legacy_marketplace.authority != ctx.accounts.authority.key()
+            || legacy_marketplace.mint != ctx.accounts.mint.key() {
+            return Err(error!(ErrorCode::ErrInvalidLegacyMarketplace));
+        }
 
+        //Fields added since the legacy layout start from the defaults of a new marketplace
+        let marketplace = Marketplace {
+            fees: legacy_marketplace.fees,
+            fees_destination: legacy_marketplace.fees_destination,
+            authority: legacy_marketplace.authority,
+            mint: legacy_marketplace.mint,
+            pending_authority: None,
+            fee_timelock,
+            pending_fees: None,
+            guardian: None,
+            paused: false,
+            pause_reason: 0,
+            referral_fee: 0,
+            taker_fees: 0,
+            pending_taker_fees: None,
+            fee_discount: None,
+        };
         marketplace.validate()?;
+        resize_account(
+            &marketplace_info,
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            MARKETPLACE_SPACE,
+        )?;
+        marketplace.try_serialize(&mut &mut marketplace_info.try_borrow_mut_data()?[..])?;
+
+        let payment_mint = &mut ctx.accounts.payment_mint;
+        payment_mint.marketplace = marketplace_info.key();
+        payment_mint.mint = marketplace.mint;
+        payment_mint.fees_destination = marketplace.fees_destination;
+
+        emit_marketplace_updated(&Account::<Marketplace>::try_from(&marketplace_info)?);
+        emit_payment_mint_updated(payment_mint, false);
         Ok(())
     }
 
+    pub fn migrate_collection(ctx: Context<MigrateCollection>) -> Result<()> {
+        migrate_legacy_collection(
+            &ctx.accounts.collection.to_account_info(),
+            ctx.accounts.marketplace.key(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
     pub fn update_marketplace(
         ctx: Context<UpdateMarketplace>,
-        optional_fees: Option<u16>,
+        optional_fees: Option<i16>,
         optional_fees_destination: Option<Pubkey>,
-        optional_authority: Option<Pubkey>,
+        optional_fee_timelock: Option<i64>,
+        optional_taker_fees: Option<u16>,
     ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
         let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.update(optional_fees, optional_fees_destination, optional_fee_timelock, optional_taker_fees, now)?;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
 
-        if let Some(fees) = optional_fees {
-            marketplace.fees = fees;
-        }
-        if let Some(fees_destination) = optional_fees_destination {
-            marketplace.fees_destination = fees_destination;
-        }
-        if let Some(authority) = optional_authority {
-            marketplace.authority = authority;
-        }
+    pub fn propose_authority(ctx: Context<UpdateMarketplace>, new_authority: Pubkey) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.pending_authority = Some(new_authority);
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.authority = ctx.accounts.new_authority.key();
+        marketplace.pending_authority = None;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<UpdateMarketplace>) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.pending_authority = None;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn set_referral_fee(ctx: Context<UpdateMarketplace>, referral_fee: u16) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.referral_fee = referral_fee;
+        marketplace.validate()?;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn create_referrer(ctx: Context<CreateReferrer>, destination: Pubkey) -> Result<()> {
+        let referrer_stats = &mut ctx.accounts.referrer_stats;
+        referrer_stats.marketplace = ctx.accounts.marketplace.key();
+        referrer_stats.payment_mint = ctx.accounts.payment_mint.mint;
+        referrer_stats.referrer = ctx.accounts.referrer.key();
+        referrer_stats.destination = destination;
+
+        emit!(ReferrerCreated {
+            referrer_stats: referrer_stats.key(),
+            marketplace: referrer_stats.marketplace,
+            payment_mint: referrer_stats.payment_mint,
+            referrer: referrer_stats.referrer,
+            destination,
+        });
+        Ok(())
+    }
+
+    pub fn set_guardian(ctx: Context<UpdateMarketplace>, guardian: Option<Pubkey>) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.guardian = guardian;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn set_fee_discount(ctx: Context<UpdateMarketplace>, fee_discount: Option<FeeDiscount>) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.fee_discount = fee_discount;
         marketplace.validate()?;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn set_marketplace_paused(ctx: Context<SetMarketplacePaused>, paused: bool, reason: u8) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.paused = paused;
+        marketplace.pause_reason = if paused { reason } else { 0 };
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn set_collection_paused(ctx: Context<SetCollectionPaused>, paused: bool, reason: u8) -> Result<()> {
+        let collection = &mut ctx.accounts.collection;
+        collection.paused = paused;
+        collection.pause_reason = if paused { reason } else { 0 };
+        emit_collection_updated(collection);
         Ok(())
     }
 
@@ -63,60 +213,161 @@ pub mod marketplace {
         fees_destination: Pubkey,
     ) -> Result<()> {
         let marketplace = &mut ctx.accounts.marketplace;
-        marketplace.mint = mint;
-        marketplace.fees_destination = fees_destination;
-        marketplace.validate()?;
+        ctx.accounts.payment_mint.set(marketplace.key(), mint, fees_destination);
+        marketplace.update_mint(mint, fees_destination)?;
+        emit_marketplace_updated(marketplace);
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
         Ok(())
     }
 
-    pub fn create_collection(
-        ctx: Context<CreateCollection>, symbol: String, required_verifier: Pubkey, fee: Option<u16>, ignore_fee: bool,
-    ) -> Result<()> {
-        let collection = &mut ctx.accounts.collection;
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>, fees_destination: Pubkey) -> Result<()> {
+        let payment_mint = &mut ctx.accounts.payment_mint;
+        payment_mint.marketplace = ctx.accounts.marketplace.key();
+        payment_mint.mint = ctx.accounts.mint.key();
+        payment_mint.fees_destination = fees_destination;
+        emit_payment_mint_updated(payment_mint, false);
+        Ok(())
+    }
+
+    pub fn update_payment_mint(ctx: Context<UpdatePaymentMint>, fees_destination: Pubkey) -> Result<()> {
+        ctx.accounts.payment_mint.set_fees_destination(fees_destination);
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
+        Ok(())
+    }
+
+    pub fn set_fee_splits(ctx: Context<UpdatePaymentMint>, fee_splits: Vec<FeeSplit>) -> Result<()> {
+        ctx.accounts.payment_mint.set_fee_splits(fee_splits)?;
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
+        Ok(())
+    }
+
+    pub fn set_fee_tiers(ctx: Context<UpdatePaymentMint>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        ctx.accounts.payment_mint.fee_tiers = fee_tiers;
+        ctx.accounts.payment_mint.validate()?;
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
+        Ok(())
+    }
+
+    pub fn claim_royalties(ctx: Context<ClaimRoyalties>) -> Result<()> {
+        let amount = ctx.accounts.royalty_vault.amount;
+        if ctx.accounts.royalty_vault.is_native() {
+            //Native royalties are kept in the vault on top of its rent
+            pay_native_from_escrow(
+                &ctx.accounts.royalty_vault.to_account_info(),
+                &ctx.accounts.destination.to_account_info(),
+                amount,
+            )?;
+        } else {
+            let seeds = &[
+                PREFIX.as_bytes(),
+                ctx.accounts.marketplace.to_account_info().key.as_ref(),
+                ctx.accounts.royalty_vault.payment_mint.as_ref(),
+                ROYALTY_ESCROW.as_bytes(),
+                &[*ctx.bumps.get("royalty_escrow").unwrap()], ];
+            let signer: &[&[&[u8]]] = &[&seeds[..]];
+            pay_with_signer(
+                ctx.accounts.royalty_escrow.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.royalty_escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+                signer,
+            )?;
+        }
+
+        let royalty_vault = &ctx.accounts.royalty_vault;
+        emit!(RoyaltiesClaimed {
+            royalty_vault: royalty_vault.key(),
+            marketplace: royalty_vault.marketplace,
+            creator: royalty_vault.creator,
+            payment_mint: royalty_vault.payment_mint,
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+        //The vault is closed to its rent payer, the next fill paying the creator opens it again
+        Ok(())
+    }
 
-        collection.marketplace_key = ctx.accounts.marketplace.key();
-        collection.required_verifier = required_verifier;
-        collection.symbol = symbol;
-        collection.fees = fee;
-        collection.ignore_creator_fee = ignore_fee;
+    pub fn create_trader_stats(ctx: Context<CreateTraderStats>) -> Result<()> {
+        let trader_stats = &mut ctx.accounts.trader_stats;
+        trader_stats.marketplace = ctx.accounts.marketplace.key();
+        trader_stats.payment_mint = ctx.accounts.payment_mint.mint;
+        trader_stats.trader = ctx.accounts.trader.key();
 
-        collection.validate()?;
+        emit!(TraderStatsCreated {
+            trader_stats: trader_stats.key(),
+            marketplace: trader_stats.marketplace,
+            payment_mint: trader_stats.payment_mint,
+            trader: trader_stats.trader,
+        });
+        Ok(())
+    }
+
+    pub fn remove_payment_mint(ctx: Context<RemovePaymentMint>) -> Result<()> {
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, true);
+        Ok(())
+    }
+
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        symbol: String,
+        required_verifier: Pubkey,
+        fee: Option<i16>,
+        ignore_fee: bool,
+        royalty_policy: RoyaltyPolicy,
+    ) -> Result<()> {
+        let marketplace_key = ctx.accounts.marketplace.key();
+        ctx.accounts.collection.init(marketplace_key, symbol, required_verifier, fee, ignore_fee, royalty_policy)?;
+        ctx.accounts.collection.validate_total_fees(&ctx.accounts.marketplace, Clock::get()?.unix_timestamp)?;
+        emit_collection_updated(&ctx.accounts.collection);
         Ok(())
     }
 
     pub fn update_collection(
         ctx: Context<UpdateCollection>,
-        optional_fee: Option<u16>,
+        optional_fee: Option<i16>,
         optional_symbol: Option<String>,
         optional_required_verifier: Option<Pubkey>,
         optional_ignore_creator_fee: Option<bool>,
+        optional_taker_fee: Option<u16>,
+        optional_royalty_policy: Option<RoyaltyPolicy>,
     ) -> Result<()> {
-        let collection = &mut ctx.accounts.collection;
-
-        if let Some(fee_share) = optional_fee {
-            collection.fees = Some(fee_share);
-        }
-        if let Some(symbol) = optional_symbol {
-            collection.symbol = symbol;
-        }
-        if let Some(required_verifier) = optional_required_verifier {
-            collection.required_verifier = required_verifier;
-        }
-        if let Some(ignore_creator_fee) = optional_ignore_creator_fee {
-            collection.ignore_creator_fee = ignore_creator_fee;
-        }
-
-        collection.validate()?;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.collection.update(
+            &ctx.accounts.marketplace,
+            optional_fee,
+            optional_symbol,
+            optional_required_verifier,
+            optional_ignore_creator_fee,
+            optional_taker_fee,
+            optional_royalty_policy,
+            now,
+        )?;
+        emit_collection_updated(&ctx.accounts.collection);
         Ok(())
     }
 
-    pub fn create_sell_order(ctx: Context<CreateSellOrder>, price: u64, quantity: u64, destination: Pubkey) -> Result<()> {
+    pub fn create_sell_order(
+        ctx: Context<CreateSellOrder>,
+        _nonce: u64,
+        price: u64,
+        quantity: u64,
+        destination: Pubkey,
+        expiry: Option<i64>,
+        dutch_auction: Option<DutchAuction>,
+    ) -> Result<()> {
         verify_metadata_and_derivation(
             ctx.accounts.metadata.as_ref(),
             &ctx.accounts.seller_nft_token_account.mint.key(),
             &ctx.accounts.collection,
         )?;
 
+        if let Some(expiry) = expiry {
+            if expiry <= Clock::get()?.unix_timestamp {
+                return Err(error!(ErrorCode::ErrExpiryShouldBeInTheFuture));
+            }
+        }
+
         let cpi_accounts = Transfer {
             from: ctx.accounts.seller_nft_token_account.to_account_info(),
             to: ctx.accounts.vault.to_account_info(),
@@ -132,6 +383,80 @@ pub mod marketplace {
         sell_order.mint = ctx.accounts.seller_nft_token_account.mint;
         sell_order.authority = ctx.accounts.payer.key();
         sell_order.destination = destination;
+        sell_order.expiry = expiry;
+        sell_order.dutch_auction = dutch_auction;
+        sell_order.vault_bump = *ctx.bumps.get("vault").unwrap();
+        sell_order.payment_mint = ctx.accounts.payment_mint.mint;
+
+        sell_order.validate()?;
+        ctx.accounts.payment_mint.list(quantity);
+        emit!(SellOrderCreated {
+            sell_order: sell_order.key(),
+            marketplace: sell_order.marketplace,
+            mint: sell_order.mint,
+            seller: sell_order.authority,
+            payment_mint: sell_order.payment_mint,
+            price,
+            quantity,
+        });
+        Ok(())
+    }
+
+    pub fn expire_sell_order(ctx: Context<ExpireSellOrder>) -> Result<()> {
+        if !ctx.accounts.sell_order.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ErrSellOrderNotExpired));
+        }
+
+        let sell_order_key = ctx.accounts.sell_order.key();
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.sell_order.marketplace.as_ref(),
+            sell_order_key.as_ref(),
+            "vault".as_bytes(),
+            &[ctx.accounts.sell_order.vault_bump], ];
+        let signer = &[&seeds[..]];
+
+        //Return the whole vault balance, tokens sent to it outside of the sell order would block the close
+        pay_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.seller_nft_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault.amount,
+            signer,
+        )?;
+
+        close_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+        )?;
+
+        ctx.accounts.payment_mint.unlist(ctx.accounts.sell_order.quantity);
+
+        emit!(SellOrderCancelled {
+            sell_order: sell_order_key,
+            marketplace: ctx.accounts.sell_order.marketplace,
+            mint: ctx.accounts.sell_order.mint,
+            seller: ctx.accounts.sell_order.authority,
+            quantity: ctx.accounts.sell_order.quantity,
+            remaining_quantity: 0,
+        });
+        Ok(())
+    }
+
+    pub fn update_sell_order_price(ctx: Context<UpdateSellOrderPrice>, price: u64) -> Result<()> {
+        let sell_order = &mut ctx.accounts.sell_order;
+        sell_order.price = price;
+
+        sell_order.validate()?;
+        emit!(SellOrderUpdated {
+            sell_order: sell_order.key(),
+            price: sell_order.price,
+            quantity: sell_order.quantity,
+        });
         Ok(())
     }
 
@@ -140,11 +465,14 @@ pub mod marketplace {
             return Err(error!(ErrorCode::ErrTryingToUnlistMoreThanOwned));
         }
 
+        let marketplace_key = ctx.accounts.sell_order.marketplace;
+        let sell_order_key = ctx.accounts.sell_order.key();
         let seeds = &[
             PREFIX.as_bytes(),
+            marketplace_key.as_ref(),
+            sell_order_key.as_ref(),
             "vault".as_bytes(),
-            ctx.accounts.seller_nft_token_account.mint.as_ref(),
-            &[*ctx.bumps.get("vault").unwrap()], ];
+            &[ctx.accounts.sell_order.vault_bump], ];
         let signer = &[&seeds[..]];
 
         let cpi_accounts = Transfer {
@@ -156,16 +484,106 @@ pub mod marketplace {
         let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
         token::transfer(cpi_ctx, quantity_to_unlist)?;
 
+        ctx.accounts.payment_mint.unlist(quantity_to_unlist);
         let sell_order = &mut ctx.accounts.sell_order;
         sell_order.quantity = sell_order.quantity.checked_sub(quantity_to_unlist).unwrap();
 
+        emit!(SellOrderCancelled {
+            sell_order: sell_order_key,
+            marketplace: marketplace_key,
+            mint: sell_order.mint,
+            seller: sell_order.authority,
+            quantity: quantity_to_unlist,
+            remaining_quantity: sell_order.quantity,
+        });
+
         if ctx.accounts.sell_order.quantity == 0 {
+            //Tokens sent to the vault outside of the sell order would block the close
+            ctx.accounts.vault.reload()?;
+            if ctx.accounts.vault.amount > 0 {
+                pay_with_signer(
+                    ctx.accounts.vault.to_account_info(),
+                    ctx.accounts.seller_nft_token_account.to_account_info(),
+                    ctx.accounts.vault.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.vault.amount,
+                    signer,
+                )?;
+            }
+            close_with_signer(
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                signer,
+            )?;
             ctx.accounts.sell_order.close(ctx.accounts.authority.to_account_info())?;
         }
         Ok(())
     }
 
+    pub fn migrate_legacy_sell_order(ctx: Context<MigrateLegacySellOrder>) -> Result<()> {
+        let legacy_sell_order_info = ctx.accounts.legacy_sell_order.to_account_info();
+        if legacy_sell_order_info.owner != ctx.program_id {
+            return Err(error!(ErrorCode::ErrInvalidLegacySellOrder));
+        }
+
+        let legacy_sell_order = {
+            let data = legacy_sell_order_info.try_borrow_data()?;
+            if data.len() != LEGACY_SELL_ORDER_SPACE || data[..8] != SellOrder::discriminator() {
+                return Err(error!(ErrorCode::ErrInvalidLegacySellOrder));
+            }
+            LegacySellOrder::deserialize(&mut &data[8..])
+                .map_err(|_| error!(ErrorCode::ErrInvalidLegacySellOrder))?
+        };
+        if legacy_sell_order.authority != ctx.accounts.authority.key()
+            || legacy_sell_order.mint != ctx.accounts.seller_nft_token_account.mint {
+            return Err(error!(ErrorCode::ErrInvalidLegacySellOrder));
+        }
+
+        let seeds = &[
+            PREFIX.as_bytes(),
+            "vault".as_bytes(),
+            ctx.accounts.seller_nft_token_account.mint.as_ref(),
+            &[*ctx.bumps.get("legacy_vault").unwrap()], ];
+        let signer = &[&seeds[..]];
+
+        pay_with_signer(
+            ctx.accounts.legacy_vault.to_account_info(),
+            ctx.accounts.seller_nft_token_account.to_account_info(),
+            ctx.accounts.legacy_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            legacy_sell_order.quantity,
+            signer,
+        )?;
+
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let refunded_lamports = authority_info.lamports()
+            .checked_add(legacy_sell_order_info.lamports())
+            .unwrap();
+        **authority_info.lamports.borrow_mut() = refunded_lamports;
+        **legacy_sell_order_info.lamports.borrow_mut() = 0;
+        legacy_sell_order_info.try_borrow_mut_data()?.fill(0);
+
+        emit!(SellOrderCancelled {
+            sell_order: legacy_sell_order_info.key(),
+            marketplace: legacy_sell_order.marketplace,
+            mint: legacy_sell_order.mint,
+            seller: legacy_sell_order.authority,
+            quantity: legacy_sell_order.quantity,
+            remaining_quantity: 0,
+        });
+        Ok(())
+    }
+
     pub fn add_quantity_to_sell_order(ctx: Context<SellOrderAddQuantity>, quantity_to_add: u64) -> Result<()> {
+        //Sell orders don't record their collection, the nft has to prove it belongs to the unpaused one passed
+        verify_metadata_and_derivation(
+            &ctx.accounts.metadata,
+            &ctx.accounts.sell_order.mint,
+            &ctx.accounts.collection,
+        )?;
+
         let cpi_accounts = Transfer {
             from: ctx.accounts.seller_nft_token_account.to_account_info(),
             to: ctx.accounts.vault.to_account_info(),
@@ -174,13 +592,25 @@ pub mod marketplace {
         let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
         token::transfer(cpi_ctx, quantity_to_add)?;
 
+        ctx.accounts.payment_mint.list(quantity_to_add);
         let sell_order = &mut ctx.accounts.sell_order;
         sell_order.quantity = sell_order.quantity.checked_add(quantity_to_add).unwrap();
 
+        emit!(SellOrderUpdated {
+            sell_order: sell_order.key(),
+            price: sell_order.price,
+            quantity: sell_order.quantity,
+        });
         Ok(())
     }
 
-    pub fn buy<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, Buy<'info>>, ask_quantity: u64) -> Result<()> {
+    pub fn buy<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Buy<'info>>,
+        ask_quantity: u64,
+        max_total_price: u64,
+        max_unit_price: Option<u64>,
+        royalty_tip: u16,
+    ) -> Result<()> {
         let metadata = verify_metadata_and_derivation(
             ctx.accounts.metadata.as_ref(),
             &ctx.accounts.buyer_nft_token_account.mint.key(),
@@ -188,100 +618,258 @@ pub mod marketplace {
         )?;
         let mut index = 0;
 
-        let mut creators_distributions_option: Option<Vec<(&AccountInfo, u8)>> = None;
+        let mut creators_distributions_option: Option<Vec<(&AccountInfo, u8, Pubkey, u8)>> = None;
         if !ctx.accounts.collection.ignore_creator_fee {
             if let Some(creators)  = metadata.data.creators {
                 index = creators.len();
-                let creators_distributions = verify_and_get_creators(creators, ctx.remaining_accounts, ctx.accounts.marketplace.mint);
+                let creators_distributions = verify_and_get_creators(
+                    creators,
+                    ctx.remaining_accounts,
+                    ctx.accounts.marketplace.key(),
+                    ctx.accounts.payment_mint.mint,
+                )?;
                 creators_distributions_option = Some(creators_distributions);
             }
         }
 
-        let mut marketplace_fee = ctx.accounts.marketplace.fees;
-        if let Some(collection_share) = ctx.accounts.collection.fees {
-            marketplace_fee = collection_share;
+        let fee_splits = &ctx.accounts.payment_mint.fee_splits;
+        let split_accounts = get_split_accounts(fee_splits, ctx.remaining_accounts, index)?;
+        index = index + split_accounts.len();
+        let mut referral = get_referral(
+            ctx.accounts.marketplace.key(),
+            ctx.accounts.payment_mint.mint,
+            ctx.remaining_accounts,
+            index,
+            &[Some(ctx.accounts.buyer.key())],
+        )?;
+        if referral.is_some() {
+            index = index + 2;
+        }
+        let mut trader_stats = get_trader_stats(
+            ctx.accounts.marketplace.key(),
+            ctx.accounts.payment_mint.mint,
+            ctx.accounts.buyer.key(),
+            ctx.remaining_accounts,
+            index,
+        )?;
+        if trader_stats.is_some() {
+            index = index + 1;
+        }
+        let fee_discount = ctx.accounts.marketplace.fee_discount;
+        let discount_fees = get_discount_fees(fee_discount, ctx.accounts.buyer.key(), ctx.remaining_accounts, index)?;
+        if let (Some(fee_discount), Some(_)) = (fee_discount, discount_fees) {
+            index = index + fee_discount.pass.accounts_len();
         }
 
-        let seeds = &[
-            PREFIX.as_bytes(),
-            "vault".as_bytes(),
-            ctx.accounts.buyer_nft_token_account.mint.as_ref(),
-            &[*ctx.bumps.get("vault").unwrap()], ];
-        let signer = &[&seeds[..]];
+        let now = Clock::get()?.unix_timestamp;
+        //The seller is the maker of a sell order, the buyer takes it
+        let maker_fee = ctx.accounts.collection.effective_fees(&ctx.accounts.marketplace, now);
+        let mut taker_fee = ctx.accounts.collection.effective_taker_fees(&ctx.accounts.marketplace, now);
+        if let Some(trader_stats) = trader_stats.as_ref() {
+            taker_fee = get_tier_fees(&ctx.accounts.payment_mint.fee_tiers, trader_stats.volume, taker_fee);
+        }
+        //The pass of the buyer only discounts its own taker fee, the seller keeps paying its maker fee
+        if let Some(discount_fees) = discount_fees {
+            taker_fee = taker_fee.min(discount_fees);
+        }
 
+        let marketplace_key = ctx.accounts.marketplace.key();
+        let is_native = ctx.accounts.payment_mint.is_native();
+        let royalty_accounts = RoyaltyAccounts {
+            payer: ctx.accounts.marketplace.to_account_info(),
+            signer: ctx.accounts.buyer.to_account_info(),
+            escrow: ctx.accounts.royalty_escrow.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
         let mut remaining_to_buy = ask_quantity;
+        let mut total_paid: u64 = 0;
 
         while index < ctx.remaining_accounts.len() {
             let sell_order_result= Account::<'info, SellOrder>::try_from(&ctx.remaining_accounts[index]);
             if sell_order_result.is_err() {
-                index = index + 2;
+                index = index + 3;
                 continue
             }
 
             let mut sell_order = sell_order_result.unwrap();
-            assert_eq!(sell_order.marketplace, ctx.accounts.marketplace.key());
-            assert_eq!(sell_order.mint, ctx.accounts.buyer_nft_token_account.mint.key());
+            require!(
+                sell_order.marketplace == marketplace_key
+                    && sell_order.mint == ctx.accounts.buyer_nft_token_account.mint.key()
+                    && sell_order.payment_mint == ctx.accounts.payment_mint.mint,
+                ErrorCode::ErrInvalidSellOrder
+            );
+            if let Some((referrer_stats, _)) = referral.as_ref() {
+                if referrer_stats.referrer == sell_order.authority {
+                    return Err(error!(ErrorCode::ErrSelfReferral));
+                }
+            }
+
+            if sell_order.is_expired(now) {
+                index = index + 3;
+                continue
+            }
 
+            let sell_order_key = sell_order.key();
             index = index + 1;
 
             let mut to_buy = remaining_to_buy;
             if sell_order.quantity < to_buy {
                 to_buy = sell_order.quantity;
             }
-            
+
+            let vault = &ctx.remaining_accounts[index];
+            index = index + 1;
+            let seeds = &[
+                PREFIX.as_bytes(),
+                marketplace_key.as_ref(),
+                sell_order_key.as_ref(),
+                "vault".as_bytes(),
+                &[sell_order.vault_bump], ];
+            let signer = &[&seeds[..]];
+            let vault_key = Pubkey::create_program_address(seeds, ctx.program_id)
+                .map_err(|_| error!(ErrorCode::DerivedKeyInvalid))?;
+            require!(vault.key() == vault_key, ErrorCode::ErrInvalidSellOrderVault);
+
             pay_with_signer(
-                ctx.accounts.vault.to_account_info(),
+                vault.to_account_info(),
                 ctx.accounts.buyer_nft_token_account.to_account_info(),
-                ctx.accounts.vault.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
+                vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
                 to_buy,
                 signer,
             )?;
-            
+
 
             let seller_token_account = &ctx.remaining_accounts[index];
             index = index + 1;
-            assert_eq!(seller_token_account.key(), sell_order.destination);
-            let total_amount = sell_order.price.checked_mul(to_buy).unwrap();
-            let mut creators_share: u64 = 0;
+            require!(seller_token_account.key() == sell_order.destination, ErrorCode::ErrInvalidSellerDestination);
+            let unit_price = sell_order.current_price(now);
+            if let Some(max_unit_price) = max_unit_price {
+                if unit_price > max_unit_price {
+                    return Err(error!(ErrorCode::ErrUnitPriceAboveMaximum));
+                }
+            }
+            let total_amount = unit_price.checked_mul(to_buy).unwrap();
+            let fill_fees = get_fill_fees(total_amount, maker_fee, taker_fee);
+
+            let mut royalties = (0, 0);
             if !ctx.accounts.collection.ignore_creator_fee {
-                creators_share = calculate_fee(total_amount, metadata.data.seller_fee_basis_points, 10000);
+                royalties = ctx.accounts.collection.royalty_policy.royalties(
+                    total_amount,
+                    metadata.data.seller_fee_basis_points,
+                    royalty_tip,
+                );
+            }
+            let (royalty_share, royalty_tip_share) = royalties;
+            let creators_share = royalty_share.checked_add(royalty_tip_share).unwrap();
+
+            //The taker fee and the royalty tip are paid by the buyer on top of the price
+            total_paid = total_paid
+                .checked_add(total_amount)
+                .unwrap()
+                .checked_add(fill_fees.taker_share)
+                .unwrap()
+                .checked_add(royalty_tip_share)
+                .unwrap();
+            if total_paid > max_total_price {
+                return Err(error!(ErrorCode::ErrTotalPriceAboveMaximum));
+            }
+
+            let mut marketplace_share = fill_fees.marketplace_share();
+            let seller_share = total_amount
+                .checked_sub(royalty_share)
+                .and_then(|share| share.checked_sub(fill_fees.maker_share))
+                .ok_or_else(|| error!(ErrorCode::ErrFeesExceedPrice))?
+                .checked_add(fill_fees.maker_rebate)
+                .unwrap();
+
+            let mut referral_share = 0;
+            if let Some((referrer_stats, referral_dest_account)) = referral.as_mut() {
+                referral_share = calculate_fee(marketplace_share, ctx.accounts.marketplace.referral_fee, 10000);
+                marketplace_share = marketplace_share - referral_share;
+                referrer_stats.record(total_amount, referral_share);
+                pay_from_buyer(
+                    is_native,
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.buyer_paying_token_account.to_account_info(),
+                    referral_dest_account.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    referral_share,
+                )?;
             }
-            let marketplace_share = calculate_fee(total_amount, marketplace_fee, 10000);
-            let seller_share = total_amount.checked_sub(creators_share).unwrap().checked_sub(marketplace_share).unwrap();
-            
-            pay(
+
+            pay_from_buyer(
+                is_native,
+                ctx.accounts.buyer.to_account_info(),
                 ctx.accounts.buyer_paying_token_account.to_account_info(),
                 seller_token_account.to_account_info(),
-                ctx.accounts.buyer.to_account_info(),
                 ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
                 seller_share,
             )?;
-            
-            pay(
-                ctx.accounts.buyer_paying_token_account.to_account_info(),
+
+            let fee_payments = get_fee_payments(
+                fee_splits,
                 ctx.accounts.marketplace_dest_account.to_account_info(),
-                ctx.accounts.buyer.to_account_info(),
-                ctx.accounts.token_program.to_account_info(),
+                split_accounts,
                 marketplace_share,
             )?;
+            for (fee_dest_account, fee_share) in fee_payments {
+                pay_from_buyer(
+                    is_native,
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.buyer_paying_token_account.to_account_info(),
+                    fee_dest_account,
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    fee_share,
+                )?;
+            }
 
+            let mut creator_shares = Vec::new();
             if let Some(creators) = creators_distributions_option.as_ref() {
                 for creator in creators {
                     let creator_share = calculate_fee(creators_share, creator.1 as u16, 100);
-                    pay(
-                        ctx.accounts.buyer_paying_token_account.to_account_info(),
-                        creator.0.to_account_info(),
+                    accrue_royalties(&royalty_accounts, creator.0, creator.2, creator.3, marketplace_key, ctx.accounts.payment_mint.mint, creator_share)?;
+                    pay_from_buyer(
+                        is_native,
                         ctx.accounts.buyer.to_account_info(),
+                        ctx.accounts.buyer_paying_token_account.to_account_info(),
+                        get_royalty_destination(is_native, creator.0, &royalty_accounts),
                         ctx.accounts.token_program.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
                         creator_share,
                     )?;
+                    creator_shares.push(CreatorShare { destination: creator.0.key(), amount: creator_share });
                 }
             }
 
+            if let Some(trader_stats) = trader_stats.as_mut() {
+                trader_stats.record(total_amount);
+            }
+
             sell_order.quantity = sell_order.quantity - to_buy;
             sell_order.exit(ctx.program_id)?;
 
+            emit!(SellOrderFilled {
+                sell_order: sell_order_key,
+                marketplace: marketplace_key,
+                mint: sell_order.mint,
+                seller: sell_order.authority,
+                buyer: ctx.accounts.buyer.key(),
+                payment_mint: sell_order.payment_mint,
+                quantity: to_buy,
+                unit_price,
+                marketplace_share,
+                creator_shares,
+                seller_share,
+                maker_rebate: fill_fees.maker_rebate,
+                referrer: referral.as_ref().map(|(referrer_stats, _)| referrer_stats.referrer),
+                referral_share,
+                remaining_quantity: sell_order.quantity,
+            });
+
             remaining_to_buy = remaining_to_buy - to_buy;
             if remaining_to_buy == 0 {
                 break;
@@ -291,44 +879,114 @@ pub mod marketplace {
         if remaining_to_buy != 0 {
             return Err(error!(ErrorCode::ErrCouldNotBuyEnoughItem));
         }
+        ctx.accounts.payment_mint.unlist(ask_quantity);
+        if let Some((referrer_stats, _)) = referral {
+            referrer_stats.exit(ctx.program_id)?;
+        }
+        if let Some(trader_stats) = trader_stats {
+            trader_stats.exit(ctx.program_id)?;
+        }
         Ok(())
     }
 
-    pub fn create_buy_offer(ctx: Context<CreateBuyOffer>, price_proposition: u64) -> Result<()> {
+    pub fn create_buy_offer(ctx: Context<CreateBuyOffer>, price_proposition: u64, expiry: Option<i64>) -> Result<()> {
         verify_metadata_and_derivation(
             ctx.accounts.metadata.as_ref(),
             &ctx.accounts.nft_mint.key(),
             &ctx.accounts.collection,
         )?;
 
-        invoke(
-            &system_instruction::transfer(
-                ctx.accounts.owner.key,
-                creator.key,
-                creator_royalty,
-            ),
-            &[
-                ctx.accounts.owner.clone(),
-                creator.clone(),
-                ctx.accounts.system_program.clone(),
-            ],
-        )?;
+        if let Some(expiry) = expiry {
+            if expiry <= Clock::get()?.unix_timestamp {
+                return Err(error!(ErrorCode::ErrExpiryShouldBeInTheFuture));
+            }
+        }
+
+        let is_native = ctx.accounts.payment_mint.is_native();
+        //The escrow is created on the first offer, following the anchor init flow so a pre-funded address cannot block it
+        if ctx.accounts.buyer_escrow.owner == &System::id() {
+            let seeds = &[
+                PREFIX.as_bytes(),
+                ctx.accounts.marketplace.to_account_info().key.as_ref(),
+                ctx.accounts.payment_mint.mint.as_ref(),
+                ctx.accounts.payer.to_account_info().key.as_ref(),
+                ESCROW.as_bytes(),
+                &[*ctx.bumps.get("buyer_escrow").unwrap()], ];
+            let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+            //Native escrows are program owned and only hold lamports, SPL escrows are token accounts owned by themselves
+            let (space, owner) = if is_native {
+                (0, *ctx.program_id)
+            } else {
+                (spl_token::state::Account::LEN, token::ID)
+            };
+            let required_lamports = ctx.accounts.rent.minimum_balance(space)
+                .saturating_sub(ctx.accounts.buyer_escrow.lamports());
+            if required_lamports > 0 {
+                pay_native(
+                    ctx.accounts.payer.to_account_info(),
+                    ctx.accounts.buyer_escrow.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    required_lamports,
+                )?;
+            }
+            invoke_signed(
+                &system_instruction::allocate(ctx.accounts.buyer_escrow.key, space as u64),
+                &[ctx.accounts.buyer_escrow.to_account_info(), ctx.accounts.system_program.to_account_info()],
+                signer,
+            )?;
+            invoke_signed(
+                &system_instruction::assign(ctx.accounts.buyer_escrow.key, &owner),
+                &[ctx.accounts.buyer_escrow.to_account_info(), ctx.accounts.system_program.to_account_info()],
+                signer,
+            )?;
+
+            if !is_native {
+                token::initialize_account(CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::InitializeAccount {
+                        account: ctx.accounts.buyer_escrow.to_account_info(),
+                        mint: ctx.accounts.marketplace_mint.to_account_info(),
+                        authority: ctx.accounts.buyer_escrow.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                ))?;
+            }
+        }
 
         let buy_offer = &mut ctx.accounts.buy_offer;
         buy_offer.mint = ctx.accounts.nft_mint.key();
         buy_offer.authority = ctx.accounts.payer.key();
         buy_offer.proposed_price = price_proposition;
+        buy_offer.maker_fee = ctx.accounts.collection.offer_maker_fee(
+            &ctx.accounts.marketplace,
+            price_proposition,
+            Clock::get()?.unix_timestamp,
+        );
         buy_offer.marketplace = ctx.accounts.marketplace.key();
         buy_offer.destination = ctx.accounts.buyer_nft_account.key();
+        buy_offer.expiry = expiry;
+        buy_offer.payment_mint = ctx.accounts.payment_mint.mint;
 
-        pay(
-            ctx.accounts.buyer_paying_account.to_account_info(),
-            ctx.accounts.escrow.to_account_info(),
+        pay_from_buyer(
+            is_native,
             ctx.accounts.payer.to_account_info(),
+            ctx.accounts.buyer_paying_account.to_account_info(),
+            ctx.accounts.buyer_escrow.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
-            price_proposition,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.buy_offer.escrowed_amount(),
         )?;
 
+        emit!(OfferCreated {
+            offer: ctx.accounts.buy_offer.key(),
+            marketplace: ctx.accounts.buy_offer.marketplace,
+            mint: ctx.accounts.buy_offer.mint,
+            buyer: ctx.accounts.buy_offer.authority,
+            payment_mint: ctx.accounts.buy_offer.payment_mint,
+            price: price_proposition,
+            expiry,
+        });
         Ok(())
     }
 
@@ -336,23 +994,162 @@ pub mod marketplace {
         let seeds = &[
             PREFIX.as_bytes(),
             ctx.accounts.marketplace.to_account_info().key.as_ref(),
-            ctx.accounts.marketplace.mint.as_ref(),
+            ctx.accounts.buy_offer.payment_mint.as_ref(),
+            ctx.accounts.buyer.to_account_info().key.as_ref(),
             ESCROW.as_bytes(),
-            &[*ctx.bumps.get("escrow").unwrap()], ];
+            &[*ctx.bumps.get("buyer_escrow").unwrap()], ];
 
         let signer: &[&[&[u8]]] = &[&seeds[..]];
-        pay_with_signer(
+        //Native offers are refunded straight to the buyer wallet
+        let refund_account = if ctx.accounts.buy_offer.is_native() {
+            ctx.accounts.buyer.to_account_info()
+        } else {
+            ctx.accounts.buyer_paying_account.to_account_info()
+        };
+        pay_from_escrow(
+            ctx.accounts.buy_offer.is_native(),
+            ctx.accounts.buyer_escrow.to_account_info(),
+            refund_account,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.buy_offer.escrowed_amount(),
+            signer,
+        )?;
+        emit_offer_cancelled(&ctx.accounts.buy_offer, false);
+        Ok(())
+    }
+
+    pub fn expire_buy_offer(ctx: Context<ExpireBuyOffer>) -> Result<()> {
+        if !ctx.accounts.buy_offer.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ErrBuyOfferNotExpired));
+        }
+
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.marketplace.to_account_info().key.as_ref(),
+            ctx.accounts.buy_offer.payment_mint.as_ref(),
+            ctx.accounts.buyer.to_account_info().key.as_ref(),
+            ESCROW.as_bytes(),
+            &[*ctx.bumps.get("buyer_escrow").unwrap()], ];
+
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+        //Anyone can crank an expiry so the refund account has to belong to the buyer
+        let refund_account = if ctx.accounts.buy_offer.is_native() {
+            ctx.accounts.buyer.to_account_info()
+        } else {
+            let buyer_paying_account = Account::<TokenAccount>::try_from(&ctx.accounts.buyer_paying_account.to_account_info())?;
+            if buyer_paying_account.owner != ctx.accounts.buyer.key() || buyer_paying_account.mint != ctx.accounts.buy_offer.payment_mint {
+                return Err(error!(ErrorCode::ErrInvalidRefundAccount));
+            }
+            ctx.accounts.buyer_paying_account.to_account_info()
+        };
+        pay_from_escrow(
+            ctx.accounts.buy_offer.is_native(),
+            ctx.accounts.buyer_escrow.to_account_info(),
+            refund_account,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.buy_offer.escrowed_amount(),
+            signer,
+        )?;
+        emit_offer_cancelled(&ctx.accounts.buy_offer, true);
+        Ok(())
+    }
+
+    pub fn reconcile_buyer_escrow<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ReconcileBuyerEscrow<'info>>, payment_mint: Pubkey,
+    ) -> Result<()> {
+        let mut seen_offers: Vec<Pubkey> = Vec::new();
+        let mut total_proposed: u64 = 0;
+        for account in ctx.remaining_accounts.iter() {
+            let buy_offer = Account::<'info, BuyOffer>::try_from(account)?;
+            if buy_offer.marketplace != ctx.accounts.marketplace.key()
+                || buy_offer.authority != ctx.accounts.buyer.key()
+                || buy_offer.payment_mint != payment_mint
+                || seen_offers.contains(&account.key()) {
+                return Err(error!(ErrorCode::ErrInvalidBuyOfferForReconciliation));
+            }
+            seen_offers.push(account.key());
+            total_proposed = total_proposed.checked_add(buy_offer.escrowed_amount()).unwrap();
+        }
+
+        let escrow_balance = get_escrow_balance(
+            &ctx.accounts.buyer_escrow.to_account_info(),
+            payment_mint == spl_token::native_mint::id(),
+        )?;
+
+        if total_proposed != escrow_balance {
+            return Err(error!(ErrorCode::ErrEscrowOutOfBalance));
+        }
+        Ok(())
+    }
+
+    pub fn record_legacy_buy_offer(ctx: Context<RecordLegacyBuyOffer>) -> Result<()> {
+        escrow_legacy_buy_offer(
+            &ctx.accounts.legacy_buy_offer.to_account_info(),
+            &ctx.accounts.marketplace.to_account_info(),
+            ctx.accounts.mint.key(),
             ctx.accounts.escrow.to_account_info(),
+            *ctx.bumps.get("escrow").unwrap(),
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )
+    }
+
+    pub fn refund_legacy_buy_offer(ctx: Context<RefundLegacyBuyOffer>) -> Result<()> {
+        let legacy_buy_offer_info = ctx.accounts.legacy_buy_offer.to_account_info();
+        let legacy_buy_offer = load_legacy_buy_offer(&legacy_buy_offer_info, ctx.accounts.marketplace.key())?;
+        if legacy_buy_offer.authority != ctx.accounts.buyer.key() {
+            return Err(error!(ErrorCode::ErrInvalidLegacyBuyOffer));
+        }
+
+        //The whole balance goes back so the legacy escrow can be closed even if somebody sent tokens to it
+        let legacy_buy_offer_key = legacy_buy_offer_info.key();
+        let seeds = &[
+            PREFIX.as_bytes(),
+            legacy_buy_offer_key.as_ref(),
+            LEGACY_ESCROW.as_bytes(),
+            &[*ctx.bumps.get("legacy_escrow").unwrap()], ];
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+        pay_with_signer(
+            ctx.accounts.legacy_escrow.to_account_info(),
             ctx.accounts.buyer_paying_account.to_account_info(),
-            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.legacy_escrow.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.buy_offer.proposed_price,
+            ctx.accounts.legacy_escrow.amount,
             signer,
         )?;
-        Ok(())
+        close_with_signer(
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.marketplace.to_account_info(),
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+        )?;
+
+        emit!(OfferCancelled {
+            offer: legacy_buy_offer_info.key(),
+            marketplace: legacy_buy_offer.marketplace,
+            mint: legacy_buy_offer.mint,
+            buyer: legacy_buy_offer.authority,
+            price: legacy_buy_offer.proposed_price,
+            expired: false,
+        });
+        close_legacy_buy_offer(&legacy_buy_offer_info, &ctx.accounts.buyer.to_account_info())
     }
 
-    pub fn execute_offer<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, ExecuteOffer<'info>>) -> Result<()> {
+    pub fn execute_legacy_buy_offer<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, ExecuteLegacyBuyOffer<'info>>) -> Result<()> {
+        //The legacy escrow is a token account even for wrapped SOL, those offers can only be refunded
+        if ctx.accounts.payment_mint.mint == spl_token::native_mint::id() {
+            return Err(error!(ErrorCode::ErrNativeMintNotSupported));
+        }
+
+        let legacy_buy_offer_info = ctx.accounts.legacy_buy_offer.to_account_info();
+        let legacy_buy_offer = load_legacy_buy_offer(&legacy_buy_offer_info, ctx.accounts.marketplace.key())?;
+        if legacy_buy_offer.authority != ctx.accounts.buyer.key()
+            || legacy_buy_offer.destination != ctx.accounts.destination.key()
+            || legacy_buy_offer.mint != ctx.accounts.seller_nft_account.mint {
+            return Err(error!(ErrorCode::ErrInvalidLegacyBuyOffer));
+        }
+
         let metadata = verify_metadata_and_derivation(
             &ctx.accounts.metadata,
             &ctx.accounts.seller_nft_account.mint,
@@ -362,197 +1159,1269 @@ pub mod marketplace {
         //Transfer NFT to buyer
         pay(
             ctx.accounts.seller_nft_account.to_account_info(),
-        ctx.accounts.destination.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
             ctx.accounts.seller.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
             1
         )?;
 
-        let mut creators_distributions_option: Option<Vec<(&AccountInfo, u8)>> = None;
-        if !ctx.accounts.collection.ignore_creator_fee {
-            if let Some(creators) = metadata.data.creators {
-                let creators_distributions = verify_and_get_creators(creators, ctx.remaining_accounts, ctx.accounts.marketplace.mint);
-                creators_distributions_option = Some(creators_distributions);
-            }
-        }
+        let legacy_buy_offer_key = legacy_buy_offer_info.key();
+        let seeds = &[
+            PREFIX.as_bytes(),
+            legacy_buy_offer_key.as_ref(),
+            LEGACY_ESCROW.as_bytes(),
+            &[*ctx.bumps.get("legacy_escrow").unwrap()], ];
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+        let fill_shares = pay_offer_from_escrow(
+            legacy_buy_offer.proposed_price,
+            metadata,
+            &ctx.accounts.collection,
+            &ctx.accounts.marketplace,
+            ctx.remaining_accounts,
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.marketplace_dest_account.to_account_info(),
+            ctx.accounts.seller_funds_dest_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+            RoyaltyAccounts {
+                payer: ctx.accounts.marketplace.to_account_info(),
+                signer: ctx.accounts.seller.to_account_info(),
+                escrow: ctx.accounts.royalty_escrow.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.accounts.payment_mint.mint,
+            &ctx.accounts.payment_mint.fee_splits,
+            &ctx.accounts.payment_mint.fee_tiers,
+            false,
+            ctx.accounts.buyer.key(),
+            Some(ctx.accounts.seller.key()),
+            None,
+            0,
+            ctx.accounts.buyer_paying_account.to_account_info(),
+        )?;
 
-        let mut marketplace_fee = ctx.accounts.marketplace.fees;
-        if let Some(collection_share) = ctx.accounts.collection.fees {
-            marketplace_fee = collection_share;
+        //Tokens sent to the legacy escrow on top of the price go back to the buyer before it is closed
+        ctx.accounts.legacy_escrow.reload()?;
+        if ctx.accounts.legacy_escrow.amount > 0 {
+            pay_with_signer(
+                ctx.accounts.legacy_escrow.to_account_info(),
+                ctx.accounts.buyer_paying_account.to_account_info(),
+                ctx.accounts.legacy_escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.legacy_escrow.amount,
+                signer,
+            )?;
         }
+        close_with_signer(
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.marketplace.to_account_info(),
+            ctx.accounts.legacy_escrow.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+        )?;
 
-        let total_amount = ctx.accounts.buy_offer.proposed_price;
-        let mut creators_share = 0;
-        if !ctx.accounts.collection.ignore_creator_fee {
-            creators_share = calculate_fee(total_amount, metadata.data.seller_fee_basis_points, 10000);
+        emit!(OfferExecuted {
+            offer: legacy_buy_offer_info.key(),
+            marketplace: legacy_buy_offer.marketplace,
+            mint: legacy_buy_offer.mint,
+            buyer: legacy_buy_offer.authority,
+            seller: ctx.accounts.seller.key(),
+            payment_mint: ctx.accounts.payment_mint.mint,
+            price: legacy_buy_offer.proposed_price,
+            marketplace_share: fill_shares.marketplace_share,
+            creator_shares: fill_shares.creator_shares,
+            seller_share: fill_shares.seller_share,
+            maker_rebate: fill_shares.maker_rebate,
+            referrer: fill_shares.referrer,
+            referral_share: fill_shares.referral_share,
+        });
+        close_legacy_buy_offer(&legacy_buy_offer_info, &ctx.accounts.buyer.to_account_info())
+    }
+
+    pub fn execute_offer<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, ExecuteOffer<'info>>) -> Result<()> {
+        if ctx.accounts.buy_offer.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ErrBuyOfferExpired));
         }
-        let marketplace_share = calculate_fee(total_amount, marketplace_fee, 10000);
-        let seller_share = total_amount.checked_sub(creators_share).unwrap().checked_sub(marketplace_share).unwrap();
+
+        let metadata = verify_metadata_and_derivation(
+            &ctx.accounts.metadata,
+            &ctx.accounts.seller_nft_account.mint,
+            &ctx.accounts.collection,
+        )?;
+
+        //Transfer NFT to buyer
+        pay(
+            ctx.accounts.seller_nft_account.to_account_info(),
+        ctx.accounts.destination.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1
+        )?;
 
         let seeds = &[
             PREFIX.as_bytes(),
             ctx.accounts.marketplace.to_account_info().key.as_ref(),
-            ctx.accounts.marketplace.mint.as_ref(),
+            ctx.accounts.buy_offer.payment_mint.as_ref(),
+            ctx.accounts.buyer.to_account_info().key.as_ref(),
             ESCROW.as_bytes(),
-            &[*ctx.bumps.get("escrow").unwrap()], ];
+            &[*ctx.bumps.get("buyer_escrow").unwrap()], ];
         let signer: &[&[&[u8]]] = &[&seeds[..]];
 
-        if let Some(creators) = creators_distributions_option.as_ref() {
-            for creator in creators {
-                let creator_share = calculate_fee(creators_share, creator.1 as u16, 100);
-                pay_with_signer(
-                    ctx.accounts.escrow.to_account_info(),
-                    creator.0.to_account_info(),
-                    ctx.accounts.escrow.to_account_info(),
-                    ctx.accounts.token_program.to_account_info(),
-                    creator_share,
-                    signer
-                )?;
-            }
+        let maker_rebate_account = ctx.accounts.maker_rebate_account.to_account_info();
+        if !is_refund_account(&maker_rebate_account, ctx.accounts.buyer.key(), ctx.accounts.buy_offer.payment_mint) {
+            return Err(error!(ErrorCode::ErrInvalidMakerRebateAccount));
         }
 
-        pay_with_signer(
-            ctx.accounts.escrow.to_account_info(),
+        let fill_shares = pay_offer_from_escrow(
+            ctx.accounts.buy_offer.proposed_price,
+            metadata,
+            &ctx.accounts.collection,
+            &ctx.accounts.marketplace,
+            ctx.remaining_accounts,
+            ctx.accounts.buyer_escrow.to_account_info(),
             ctx.accounts.marketplace_dest_account.to_account_info(),
-            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.seller_funds_dest_account.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
-            marketplace_share,
             signer,
+            RoyaltyAccounts {
+                payer: ctx.accounts.marketplace.to_account_info(),
+                signer: ctx.accounts.seller.to_account_info(),
+                escrow: ctx.accounts.royalty_escrow.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.accounts.buy_offer.payment_mint,
+            &ctx.accounts.payment_mint.fee_splits,
+            &ctx.accounts.payment_mint.fee_tiers,
+            true,
+            ctx.accounts.buyer.key(),
+            Some(ctx.accounts.seller.key()),
+            Some(ctx.accounts.buyer.key()),
+            ctx.accounts.buy_offer.maker_fee,
+            maker_rebate_account,
         )?;
 
-        pay_with_signer(
-            ctx.accounts.escrow.to_account_info(),
-            ctx.accounts.seller_funds_dest_account.to_account_info(),
-            ctx.accounts.escrow.to_account_info(),
+        emit!(OfferExecuted {
+            offer: ctx.accounts.buy_offer.key(),
+            marketplace: ctx.accounts.buy_offer.marketplace,
+            mint: ctx.accounts.buy_offer.mint,
+            buyer: ctx.accounts.buy_offer.authority,
+            seller: ctx.accounts.seller.key(),
+            payment_mint: ctx.accounts.buy_offer.payment_mint,
+            price: ctx.accounts.buy_offer.proposed_price,
+            marketplace_share: fill_shares.marketplace_share,
+            creator_shares: fill_shares.creator_shares,
+            seller_share: fill_shares.seller_share,
+            maker_rebate: fill_shares.maker_rebate,
+            referrer: fill_shares.referrer,
+            referral_share: fill_shares.referral_share,
+        });
+        Ok(())
+    }
+
+    pub fn create_collection_offer(ctx: Context<CreateCollectionOffer>, price: u64, quantity: u64) -> Result<()> {
+        if quantity == 0 {
+            return Err(error!(ErrorCode::ErrQuantityShouldBeGreaterThanZero));
+        }
+
+        let collection_offer = &mut ctx.accounts.collection_offer;
+        collection_offer.marketplace = ctx.accounts.marketplace.key();
+        collection_offer.collection = ctx.accounts.collection.key();
+        collection_offer.authority = ctx.accounts.payer.key();
+        collection_offer.price = price;
+        collection_offer.quantity = quantity;
+        collection_offer.maker_fee = ctx.accounts.collection.offer_maker_fee(
+            &ctx.accounts.marketplace,
+            price,
+            Clock::get()?.unix_timestamp,
+        );
+        collection_offer.payment_mint = ctx.accounts.payment_mint.mint;
+
+        //Native offers hold their lamports on top of their own rent, SPL offers share the payment mint escrow
+        let is_native = ctx.accounts.collection_offer.is_native();
+        let escrow = if is_native {
+            ctx.accounts.collection_offer.to_account_info()
+        } else {
+            ctx.accounts.escrow.to_account_info()
+        };
+        pay_from_buyer(
+            is_native,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.buyer_paying_account.to_account_info(),
+            escrow,
             ctx.accounts.token_program.to_account_info(),
-            seller_share,
-            signer,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.collection_offer.escrowed_amount(),
         )?;
 
+        let collection_offer = &ctx.accounts.collection_offer;
+        emit!(CollectionOfferCreated {
+            offer: collection_offer.key(),
+            marketplace: collection_offer.marketplace,
+            collection: collection_offer.collection,
+            buyer: collection_offer.authority,
+            payment_mint: collection_offer.payment_mint,
+            price: collection_offer.price,
+            quantity: collection_offer.quantity,
+        });
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(price_proposition: u64)]
-pub struct CreateBuyOffer<'info> {
-    #[account(mut)]
-    payer: Signer<'info>,
+    pub fn remove_collection_offer(ctx: Context<RemoveCollectionOffer>) -> Result<()> {
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.marketplace.to_account_info().key.as_ref(),
+            ctx.accounts.collection_offer.payment_mint.as_ref(),
+            ESCROW.as_bytes(),
+            &[*ctx.bumps.get("escrow").unwrap()], ];
 
-    nft_mint: Account<'info, Mint>,
-    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
-    metadata: UncheckedAccount<'info>,
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+        //Native offers hold their lamports themselves and refund them to the buyer when closed
+        if !ctx.accounts.collection_offer.is_native() {
+            pay_with_signer(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.buyer_paying_account.to_account_info(),
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.collection_offer.escrowed_amount(),
+                signer,
+            )?;
+        }
 
-    marketplace: Box<Account<'info, Marketplace>>,
-    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
-    collection: Box<Account<'info, Collection>>,
-    #[account(
-    mut,
-    seeds = [
-    PREFIX.as_bytes(),
-    marketplace.key().as_ref(),
-    marketplace.mint.as_ref(),
-    ESCROW.as_bytes()
-    ],
-    bump,
-    )]
-    escrow: Box<Account<'info, TokenAccount>>,
+        let collection_offer = &ctx.accounts.collection_offer;
+        emit!(CollectionOfferCancelled {
+            offer: collection_offer.key(),
+            marketplace: collection_offer.marketplace,
+            collection: collection_offer.collection,
+            buyer: collection_offer.authority,
+            price: collection_offer.price,
+            quantity: collection_offer.quantity,
+        });
+        Ok(())
+    }
 
-    #[account(mut)]
-    buyer_paying_account: Box<Account<'info, TokenAccount>>,
-    #[account(
-    init_if_needed,
-    payer = payer,
-    associated_token::mint = nft_mint,
-    associated_token::authority = payer,
-    )]
-    buyer_nft_account: Account<'info, TokenAccount>,
+    pub fn execute_collection_offer<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, ExecuteCollectionOffer<'info>>) -> Result<()> {
+        let metadata = verify_metadata_and_derivation(
+            &ctx.accounts.metadata,
+            &ctx.accounts.seller_nft_account.mint,
+            &ctx.accounts.collection,
+        )?;
 
-    #[account(
-    init,
-    seeds = [
-    PREFIX.as_bytes(),
-    marketplace.key().as_ref(),
-    payer.key.as_ref(),
-    nft_mint.key().as_ref(),
-    price_proposition.to_string().as_bytes(),
-    ESCROW.as_bytes(),
-    ],
-    bump,
-    payer = payer,
-    space = 144,
-    )]
-    buy_offer: Account<'info, BuyOffer>,
+        //Transfer NFT to buyer
+        pay(
+            ctx.accounts.seller_nft_account.to_account_info(),
+            ctx.accounts.buyer_nft_account.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1
+        )?;
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    associated_token_program: Program<'info, AssociatedToken>,
-    rent: Sysvar<'info, Rent>,
-}
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.marketplace.to_account_info().key.as_ref(),
+            ctx.accounts.payment_mint.mint.as_ref(),
+            ESCROW.as_bytes(),
+            &[*ctx.bumps.get("escrow").unwrap()], ];
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
 
-#[derive(Accounts)]
-pub struct RemoveBuyOffer<'info> {
-    #[account(mut)]
-    buyer: Signer<'info>,
+        let buyer_paying_account = ctx.accounts.buyer_paying_account.to_account_info();
+        if !is_refund_account(&buyer_paying_account, ctx.accounts.buyer.key(), ctx.accounts.payment_mint.mint) {
+            return Err(error!(ErrorCode::ErrInvalidMakerRebateAccount));
+        }
+        let escrow = if ctx.accounts.collection_offer.is_native() {
+            ctx.accounts.collection_offer.to_account_info()
+        } else {
+            ctx.accounts.escrow.to_account_info()
+        };
 
-    #[account(mut)]
-    buyer_paying_account: Account<'info, TokenAccount>,
+        let fill_shares = pay_offer_from_escrow(
+            ctx.accounts.collection_offer.price,
+            metadata,
+            &ctx.accounts.collection,
+            &ctx.accounts.marketplace,
+            ctx.remaining_accounts,
+            escrow,
+            ctx.accounts.marketplace_dest_account.to_account_info(),
+            ctx.accounts.seller_funds_dest_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+            RoyaltyAccounts {
+                payer: ctx.accounts.marketplace.to_account_info(),
+                signer: ctx.accounts.seller.to_account_info(),
+                escrow: ctx.accounts.royalty_escrow.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.accounts.payment_mint.mint,
+            &ctx.accounts.payment_mint.fee_splits,
+            &ctx.accounts.payment_mint.fee_tiers,
+            true,
+            ctx.accounts.buyer.key(),
+            Some(ctx.accounts.seller.key()),
+            None,
+            ctx.accounts.collection_offer.maker_fee,
+            buyer_paying_account,
+        )?;
 
-    marketplace: Account<'info, Marketplace>,
+        let collection_offer = &mut ctx.accounts.collection_offer;
+        collection_offer.quantity = collection_offer.quantity.checked_sub(1).unwrap();
 
-    #[account(
-    mut,
-    seeds = [
-    PREFIX.as_bytes(),
-    marketplace.key().as_ref(),
-    marketplace.mint.as_ref(),
-    ESCROW.as_bytes()
-    ],
-    bump,
-    )]
-    escrow: Account<'info, TokenAccount>,
+        emit!(CollectionOfferFilled {
+            offer: collection_offer.key(),
+            marketplace: collection_offer.marketplace,
+            collection: collection_offer.collection,
+            mint: ctx.accounts.seller_nft_account.mint,
+            buyer: collection_offer.authority,
+            seller: ctx.accounts.seller.key(),
+            payment_mint: collection_offer.payment_mint,
+            price: collection_offer.price,
+            marketplace_share: fill_shares.marketplace_share,
+            creator_shares: fill_shares.creator_shares,
+            seller_share: fill_shares.seller_share,
+            maker_rebate: fill_shares.maker_rebate,
+            referrer: fill_shares.referrer,
+            referral_share: fill_shares.referral_share,
+            remaining_quantity: collection_offer.quantity,
+        });
 
-    #[account(
-    mut,
-    close = buyer,
-    has_one = marketplace,
-    constraint = buy_offer.authority == buyer.key(),
-    )]
-    buy_offer: Account<'info, BuyOffer>,
+        if ctx.accounts.collection_offer.quantity == 0 {
+            ctx.accounts.collection_offer.close(ctx.accounts.buyer.to_account_info())?;
+        }
+        Ok(())
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
-}
+    pub fn create_trait_offer(
+        ctx: Context<CreateTraitOffer>, merkle_root: [u8; 32], price: u64, quantity: u64,
+    ) -> Result<()> {
+        if quantity == 0 {
+            return Err(error!(ErrorCode::ErrQuantityShouldBeGreaterThanZero));
+        }
 
-#[derive(Accounts)]
-pub struct ExecuteOffer<'info> {
-    seller: Signer<'info>,
+        let trait_offer = &mut ctx.accounts.trait_offer;
+        trait_offer.marketplace = ctx.accounts.marketplace.key();
+        trait_offer.collection = ctx.accounts.collection.key();
+        trait_offer.authority = ctx.accounts.payer.key();
+        trait_offer.merkle_root = merkle_root;
+        trait_offer.price = price;
+        trait_offer.quantity = quantity;
+        trait_offer.maker_fee = ctx.accounts.collection.offer_maker_fee(
+            &ctx.accounts.marketplace,
+            price,
+            Clock::get()?.unix_timestamp,
+        );
+        trait_offer.payment_mint = ctx.accounts.payment_mint.mint;
 
-    #[account(mut)]
-    buyer: SystemAccount<'info>,
+        //Native offers hold their lamports on top of their own rent, SPL offers share the payment mint escrow
+        let is_native = ctx.accounts.trait_offer.is_native();
+        let escrow = if is_native {
+            ctx.accounts.trait_offer.to_account_info()
+        } else {
+            ctx.accounts.escrow.to_account_info()
+        };
+        pay_from_buyer(
+            is_native,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.buyer_paying_account.to_account_info(),
+            escrow,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.trait_offer.escrowed_amount(),
+        )?;
 
-    marketplace: Box<Account<'info, Marketplace>>,
-    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
-    collection: Box<Account<'info, Collection>>,
+        let trait_offer = &ctx.accounts.trait_offer;
+        emit!(TraitOfferCreated {
+            offer: trait_offer.key(),
+            marketplace: trait_offer.marketplace,
+            collection: trait_offer.collection,
+            buyer: trait_offer.authority,
+            payment_mint: trait_offer.payment_mint,
+            merkle_root: trait_offer.merkle_root,
+            price: trait_offer.price,
+            quantity: trait_offer.quantity,
+        });
+        Ok(())
+    }
+
+    pub fn remove_trait_offer(ctx: Context<RemoveTraitOffer>) -> Result<()> {
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.marketplace.to_account_info().key.as_ref(),
+            ctx.accounts.trait_offer.payment_mint.as_ref(),
+            ESCROW.as_bytes(),
+            &[*ctx.bumps.get("escrow").unwrap()], ];
+
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+        //Native offers hold their lamports themselves and refund them to the buyer when closed
+        if !ctx.accounts.trait_offer.is_native() {
+            pay_with_signer(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.buyer_paying_account.to_account_info(),
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.trait_offer.escrowed_amount(),
+                signer,
+            )?;
+        }
+
+        let trait_offer = &ctx.accounts.trait_offer;
+        emit!(TraitOfferCancelled {
+            offer: trait_offer.key(),
+            marketplace: trait_offer.marketplace,
+            collection: trait_offer.collection,
+            buyer: trait_offer.authority,
+            price: trait_offer.price,
+            quantity: trait_offer.quantity,
+        });
+        Ok(())
+    }
+
+    pub fn execute_trait_offer<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecuteTraitOffer<'info>>, proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let metadata = verify_metadata_and_derivation(
+            &ctx.accounts.metadata,
+            &ctx.accounts.seller_nft_account.mint,
+            &ctx.accounts.collection,
+        )?;
+
+        let leaf = keccak::hashv(&[TRAIT_OFFER_LEAF.as_bytes(), ctx.accounts.seller_nft_account.mint.as_ref()]).0;
+        if !verify_merkle_proof(&proof, ctx.accounts.trait_offer.merkle_root, leaf) {
+            return Err(error!(ErrorCode::ErrNftNotPartOfTraitOffer));
+        }
+
+        //Transfer NFT to buyer
+        pay(
+            ctx.accounts.seller_nft_account.to_account_info(),
+            ctx.accounts.buyer_nft_account.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1
+        )?;
+
+        let seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.marketplace.to_account_info().key.as_ref(),
+            ctx.accounts.payment_mint.mint.as_ref(),
+            ESCROW.as_bytes(),
+            &[*ctx.bumps.get("escrow").unwrap()], ];
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+        let buyer_paying_account = ctx.accounts.buyer_paying_account.to_account_info();
+        if !is_refund_account(&buyer_paying_account, ctx.accounts.buyer.key(), ctx.accounts.payment_mint.mint) {
+            return Err(error!(ErrorCode::ErrInvalidMakerRebateAccount));
+        }
+        let escrow = if ctx.accounts.trait_offer.is_native() {
+            ctx.accounts.trait_offer.to_account_info()
+        } else {
+            ctx.accounts.escrow.to_account_info()
+        };
+
+        let fill_shares = pay_offer_from_escrow(
+            ctx.accounts.trait_offer.price,
+            metadata,
+            &ctx.accounts.collection,
+            &ctx.accounts.marketplace,
+            ctx.remaining_accounts,
+            escrow,
+            ctx.accounts.marketplace_dest_account.to_account_info(),
+            ctx.accounts.seller_funds_dest_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer,
+            RoyaltyAccounts {
+                payer: ctx.accounts.marketplace.to_account_info(),
+                signer: ctx.accounts.seller.to_account_info(),
+                escrow: ctx.accounts.royalty_escrow.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.accounts.payment_mint.mint,
+            &ctx.accounts.payment_mint.fee_splits,
+            &ctx.accounts.payment_mint.fee_tiers,
+            true,
+            ctx.accounts.buyer.key(),
+            Some(ctx.accounts.seller.key()),
+            None,
+            ctx.accounts.trait_offer.maker_fee,
+            buyer_paying_account,
+        )?;
+
+        let trait_offer = &mut ctx.accounts.trait_offer;
+        trait_offer.quantity = trait_offer.quantity.checked_sub(1).unwrap();
+
+        emit!(TraitOfferFilled {
+            offer: trait_offer.key(),
+            marketplace: trait_offer.marketplace,
+            collection: trait_offer.collection,
+            mint: ctx.accounts.seller_nft_account.mint,
+            buyer: trait_offer.authority,
+            seller: ctx.accounts.seller.key(),
+            payment_mint: trait_offer.payment_mint,
+            price: trait_offer.price,
+            marketplace_share: fill_shares.marketplace_share,
+            creator_shares: fill_shares.creator_shares,
+            seller_share: fill_shares.seller_share,
+            maker_rebate: fill_shares.maker_rebate,
+            referrer: fill_shares.referrer,
+            referral_share: fill_shares.referral_share,
+            remaining_quantity: trait_offer.quantity,
+        });
+
+        if ctx.accounts.trait_offer.quantity == 0 {
+            ctx.accounts.trait_offer.close(ctx.accounts.buyer.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>, reserve_price: u64, min_bid_increment: u64, end_time: i64, destination: Pubkey,
+    ) -> Result<()> {
+        verify_metadata_and_derivation(
+            ctx.accounts.metadata.as_ref(),
+            &ctx.accounts.seller_nft_token_account.mint.key(),
+            &ctx.accounts.collection,
+        )?;
+
+        if end_time <= Clock::get()?.unix_timestamp {
+            return Err(error!(ErrorCode::ErrExpiryShouldBeInTheFuture));
+        }
+
+        //A zero highest bid means nobody bid yet, so the first bid has to be above zero and each bid above the previous one
+        if reserve_price == 0 || min_bid_increment == 0 {
+            return Err(error!(ErrorCode::ErrInvalidAuction));
+        }
+
+        pay(
+            ctx.accounts.seller_nft_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1,
+        )?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.marketplace = ctx.accounts.marketplace.key();
+        auction.collection = ctx.accounts.collection.key();
+        auction.authority = ctx.accounts.payer.key();
+        auction.mint = ctx.accounts.mint.key();
+        auction.destination = destination;
+        auction.reserve_price = reserve_price;
+        auction.min_bid_increment = min_bid_increment;
+        auction.end_time = end_time;
+        auction.highest_bidder = ctx.accounts.payer.key();
+        auction.highest_bid = 0;
+        auction.highest_bid_maker_fee = 0;
+        auction.payment_mint = ctx.accounts.payment_mint.mint;
+
+        emit!(AuctionCreated {
+            auction: auction.key(),
+            marketplace: auction.marketplace,
+            collection: auction.collection,
+            mint: auction.mint,
+            seller: auction.authority,
+            payment_mint: auction.payment_mint,
+            reserve_price,
+            min_bid_increment,
+            end_time,
+        });
+        ctx.accounts.payment_mint.list(1);
+        Ok(())
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &ctx.accounts.auction;
+        if now >= auction.end_time {
+            return Err(error!(ErrorCode::ErrAuctionEnded));
+        }
+        if amount < auction.minimum_bid() {
+            return Err(error!(ErrorCode::ErrBidTooLow));
+        }
+
+        let maker_fee = ctx.accounts.collection.offer_maker_fee(&ctx.accounts.marketplace, amount, now);
+        //Native bids are held by the auction on top of its rent, SPL bids by the payment mint escrow
+        let is_native = auction.is_native();
+        let escrow = if is_native {
+            ctx.accounts.auction.to_account_info()
+        } else {
+            ctx.accounts.escrow.to_account_info()
+        };
+        pay_from_buyer(
+            is_native,
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.bidder_paying_account.to_account_info(),
+            escrow.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            amount.checked_add(maker_fee).unwrap(),
+        )?;
+
+        if auction.highest_bid > 0 {
+            let previous_bidder_paying_account = ctx.accounts.previous_bidder_paying_account.to_account_info();
+            if !is_refund_account(&previous_bidder_paying_account, auction.highest_bidder, auction.payment_mint) {
+                return Err(error!(ErrorCode::ErrInvalidPreviousBidderAccount));
+            }
+
+            let seeds = &[
+                PREFIX.as_bytes(),
+                ctx.accounts.marketplace.to_account_info().key.as_ref(),
+                ctx.accounts.auction.payment_mint.as_ref(),
+                ESCROW.as_bytes(),
+                &[*ctx.bumps.get("escrow").unwrap()], ];
+            let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+            pay_from_escrow(
+                is_native,
+                escrow,
+                previous_bidder_paying_account,
+                ctx.accounts.token_program.to_account_info(),
+                auction.escrowed_amount(),
+                signer,
+            )?;
+        }
+
+        let auction = &mut ctx.accounts.auction;
+        auction.highest_bidder = ctx.accounts.bidder.key();
+        auction.highest_bid = amount;
+        auction.highest_bid_maker_fee = maker_fee;
+        if auction.end_time - now < AUCTION_EXTENSION_WINDOW {
+            auction.end_time = now + AUCTION_EXTENSION_WINDOW;
+        }
+
+        emit!(BidPlaced {
+            auction: auction.key(),
+            marketplace: auction.marketplace,
+            bidder: auction.highest_bidder,
+            payment_mint: auction.payment_mint,
+            amount,
+            end_time: auction.end_time,
+        });
+        Ok(())
+    }
+
+    pub fn settle_auction<'a, 'b, 'c, 'info>(ctx: Context<'a, 'b, 'c, 'info, SettleAuction<'info>>) -> Result<()> {
+        if Clock::get()?.unix_timestamp < ctx.accounts.auction.end_time {
+            return Err(error!(ErrorCode::ErrAuctionNotEnded));
+        }
+
+        let metadata = verify_metadata_and_derivation(
+            &ctx.accounts.metadata,
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.collection,
+        )?;
+
+        let auction_key = ctx.accounts.auction.key();
+        let vault_seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.auction.marketplace.as_ref(),
+            auction_key.as_ref(),
+            "vault".as_bytes(),
+            &[*ctx.bumps.get("vault").unwrap()], ];
+        let vault_signer = &[&vault_seeds[..]];
+
+        //Transfer NFT to the highest bidder, or back to the seller if nobody bid
+        pay_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.winner_nft_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1,
+            vault_signer,
+        )?;
+
+        close_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+        )?;
 
-    #[account(mut, constraint = marketplace_dest_account.key() == marketplace.fees_destination)]
-    marketplace_dest_account: Box<Account<'info, TokenAccount>>,
+        let mut fill_shares = FillShares::default();
+        if ctx.accounts.auction.highest_bid > 0 {
+            let highest_bidder_paying_account = ctx.accounts.highest_bidder_paying_account.to_account_info();
+            if !is_refund_account(&highest_bidder_paying_account, ctx.accounts.highest_bidder.key(), ctx.accounts.payment_mint.mint) {
+                return Err(error!(ErrorCode::ErrInvalidMakerRebateAccount));
+            }
+            //The auction is closed to the seller after the fill, so native bids leave it before
+            let escrow = if ctx.accounts.auction.is_native() {
+                ctx.accounts.auction.to_account_info()
+            } else {
+                ctx.accounts.escrow.to_account_info()
+            };
+
+            let seeds = &[
+                PREFIX.as_bytes(),
+                ctx.accounts.marketplace.to_account_info().key.as_ref(),
+                ctx.accounts.payment_mint.mint.as_ref(),
+                ESCROW.as_bytes(),
+                &[*ctx.bumps.get("escrow").unwrap()], ];
+            let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+            fill_shares = pay_offer_from_escrow(
+                ctx.accounts.auction.highest_bid,
+                metadata,
+                &ctx.accounts.collection,
+                &ctx.accounts.marketplace,
+                ctx.remaining_accounts,
+                escrow,
+                ctx.accounts.marketplace_dest_account.to_account_info(),
+                ctx.accounts.seller_funds_dest_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                signer,
+                RoyaltyAccounts {
+                    payer: ctx.accounts.marketplace.to_account_info(),
+                    signer: ctx.accounts.payer.to_account_info(),
+                    escrow: ctx.accounts.royalty_escrow.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                ctx.accounts.payment_mint.mint,
+                &ctx.accounts.payment_mint.fee_splits,
+                &ctx.accounts.payment_mint.fee_tiers,
+                false,
+                ctx.accounts.highest_bidder.key(),
+                Some(ctx.accounts.authority.key()),
+                None,
+                ctx.accounts.auction.highest_bid_maker_fee,
+                highest_bidder_paying_account,
+            )?;
+        }
+
+        //Without a bid the nft went back to the seller, who is still recorded as the highest bidder
+        let auction = &ctx.accounts.auction;
+        emit!(AuctionSettled {
+            auction: auction.key(),
+            marketplace: auction.marketplace,
+            mint: auction.mint,
+            seller: auction.authority,
+            highest_bidder: auction.highest_bidder,
+            payment_mint: auction.payment_mint,
+            highest_bid: auction.highest_bid,
+            marketplace_share: fill_shares.marketplace_share,
+            creator_shares: fill_shares.creator_shares,
+            seller_share: fill_shares.seller_share,
+            maker_rebate: fill_shares.maker_rebate,
+        });
+        ctx.accounts.payment_mint.unlist(1);
+        Ok(())
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        let auction_key = ctx.accounts.auction.key();
+        let vault_seeds = &[
+            PREFIX.as_bytes(),
+            ctx.accounts.auction.marketplace.as_ref(),
+            auction_key.as_ref(),
+            "vault".as_bytes(),
+            &[*ctx.bumps.get("vault").unwrap()], ];
+        let vault_signer = &[&vault_seeds[..]];
+
+        //Return NFT to the seller
+        pay_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.seller_nft_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            1,
+            vault_signer,
+        )?;
+
+        close_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+        )?;
+
+        let auction = &ctx.accounts.auction;
+        emit!(AuctionCancelled {
+            auction: auction.key(),
+            marketplace: auction.marketplace,
+            mint: auction.mint,
+            seller: auction.authority,
+        });
+        ctx.accounts.payment_mint.unlist(1);
+        Ok(())
+    }
+
+    pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.marketplace = ctx.accounts.marketplace.key();
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.validate()?;
+
+        //From now on the configuration can only change through approved admin proposals
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.authority = multisig.key();
+        marketplace.pending_authority = None;
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn create_admin_proposal(ctx: Context<CreateAdminProposal>, action: AdminAction) -> Result<()> {
+        let proposer = ctx.accounts.proposer.key();
+        let multisig = &mut ctx.accounts.multisig;
+        if !multisig.signers.contains(&proposer) {
+            return Err(error!(ErrorCode::ErrNotMultisigSigner));
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.multisig = multisig.key();
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.approved_by = vec![proposer];
+        proposal.expires_at = Clock::get()?.unix_timestamp.checked_add(ADMIN_PROPOSAL_LIFETIME).unwrap();
+
+        multisig.proposal_count = multisig.proposal_count.checked_add(1).unwrap();
+        emit_admin_proposal_updated(proposal, false);
+        Ok(())
+    }
+
+    pub fn approve_admin_proposal(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+        let approver = ctx.accounts.approver.key();
+        if !ctx.accounts.multisig.signers.contains(&approver) {
+            return Err(error!(ErrorCode::ErrNotMultisigSigner));
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        if proposal.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ErrProposalExpired));
+        }
+        if proposal.approved_by.contains(&approver) {
+            return Err(error!(ErrorCode::ErrProposalAlreadyApproved));
+        }
+        proposal.approved_by.push(approver);
+        emit_admin_proposal_updated(proposal, false);
+        Ok(())
+    }
+
+    pub fn cancel_admin_proposal(ctx: Context<CancelAdminProposal>) -> Result<()> {
+        //Only the proposer can withdraw a live proposal so a single signer can't veto an approved one,
+        //anyone can clean up an expired one
+        if ctx.accounts.canceller.key() != ctx.accounts.proposal.proposer
+            && !ctx.accounts.proposal.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ErrNotProposer));
+        }
+        emit_admin_proposal_updated(&ctx.accounts.proposal, true);
+        Ok(())
+    }
+
+    pub fn execute_marketplace_proposal(ctx: Context<ExecuteMarketplaceProposal>) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        let marketplace = &mut ctx.accounts.marketplace;
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::UpdateMarketplace {
+                optional_fees,
+                optional_fees_destination,
+                optional_fee_timelock,
+                optional_taker_fees,
+            } => {
+                let now = Clock::get()?.unix_timestamp;
+                marketplace.update(
+                    optional_fees,
+                    optional_fees_destination,
+                    optional_fee_timelock,
+                    optional_taker_fees,
+                    now,
+                )?;
+            }
+            AdminAction::ProposeAuthority { new_authority } => {
+                marketplace.pending_authority = Some(new_authority);
+            }
+            AdminAction::SetGuardian { guardian } => {
+                marketplace.guardian = guardian;
+            }
+            AdminAction::CancelAuthorityTransfer => {
+                marketplace.pending_authority = None;
+            }
+            AdminAction::SetReferralFee { referral_fee } => {
+                marketplace.referral_fee = referral_fee;
+                marketplace.validate()?;
+            }
+            AdminAction::SetFeeDiscount { fee_discount } => {
+                marketplace.fee_discount = fee_discount;
+                marketplace.validate()?;
+            }
+            AdminAction::SetSigners { signers, threshold } => {
+                let multisig = &mut ctx.accounts.multisig;
+                multisig.signers = signers;
+                multisig.threshold = threshold;
+                multisig.validate()?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        emit_marketplace_updated(marketplace);
+        Ok(())
+    }
+
+    pub fn execute_add_payment_mint_proposal(ctx: Context<ExecuteAddPaymentMintProposal>, new_payment_mint: Pubkey) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::AddPaymentMint { mint, fees_destination } if mint == new_payment_mint => {
+                let marketplace_key = ctx.accounts.marketplace.key();
+                ctx.accounts.payment_mint.set(marketplace_key, mint, fees_destination);
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
+        Ok(())
+    }
+
+    pub fn execute_payment_mint_proposal(ctx: Context<ExecutePaymentMintProposal>) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        let payment_mint = &mut ctx.accounts.payment_mint;
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::UpdatePaymentMint { mint, fees_destination } if mint == payment_mint.mint => {
+                payment_mint.set_fees_destination(fees_destination);
+                emit_payment_mint_updated(payment_mint, false);
+            }
+            AdminAction::SetFeeSplits { mint, fee_splits } if mint == payment_mint.mint => {
+                payment_mint.set_fee_splits(fee_splits)?;
+                emit_payment_mint_updated(payment_mint, false);
+            }
+            AdminAction::SetFeeTiers { mint, fee_tiers } if mint == payment_mint.mint => {
+                payment_mint.fee_tiers = fee_tiers;
+                payment_mint.validate()?;
+                emit_payment_mint_updated(payment_mint, false);
+            }
+            AdminAction::RemovePaymentMint { mint } if mint == payment_mint.mint => {
+                if mint == ctx.accounts.marketplace.mint {
+                    return Err(error!(ErrorCode::ErrCannotRemovePrimaryPaymentMint));
+                }
+                if payment_mint.open_listings > 0 {
+                    return Err(error!(ErrorCode::ErrPaymentMintInUse));
+                }
+                emit_payment_mint_updated(payment_mint, true);
+                payment_mint.close(ctx.accounts.proposer.to_account_info())?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        Ok(())
+    }
+
+    pub fn execute_update_marketplace_mint_proposal(
+        ctx: Context<ExecuteUpdateMarketplaceMintProposal>,
+        new_marketplace_mint: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::UpdateMarketplaceMint { mint, fees_destination } if mint == new_marketplace_mint => {
+                let marketplace = &mut ctx.accounts.marketplace;
+                ctx.accounts.payment_mint.set(marketplace.key(), mint, fees_destination);
+                marketplace.update_mint(mint, fees_destination)?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        emit_marketplace_updated(&ctx.accounts.marketplace);
+        emit_payment_mint_updated(&ctx.accounts.payment_mint, false);
+        Ok(())
+    }
+
+    pub fn execute_create_collection_proposal(ctx: Context<ExecuteCreateCollectionProposal>, symbol: String) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::CreateCollection {
+                symbol: proposed_symbol,
+                required_verifier,
+                fee,
+                ignore_fee,
+                royalty_policy,
+            } if proposed_symbol == symbol => {
+                let marketplace_key = ctx.accounts.marketplace.key();
+                ctx.accounts.collection.init(marketplace_key, symbol, required_verifier, fee, ignore_fee, royalty_policy)?;
+                ctx.accounts.collection.validate_total_fees(&ctx.accounts.marketplace, Clock::get()?.unix_timestamp)?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        emit_collection_updated(&ctx.accounts.collection);
+        Ok(())
+    }
+
+    pub fn execute_update_collection_proposal(ctx: Context<ExecuteUpdateCollectionProposal>) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::UpdateCollection {
+                collection,
+                optional_fee,
+                optional_symbol,
+                optional_required_verifier,
+                optional_ignore_creator_fee,
+                optional_taker_fee,
+                optional_royalty_policy,
+            } if collection == ctx.accounts.collection.key() => {
+                let now = Clock::get()?.unix_timestamp;
+                ctx.accounts.collection.update(
+                    &ctx.accounts.marketplace,
+                    optional_fee,
+                    optional_symbol,
+                    optional_required_verifier,
+                    optional_ignore_creator_fee,
+                    optional_taker_fee,
+                    optional_royalty_policy,
+                    now,
+                )?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        emit_collection_updated(&ctx.accounts.collection);
+        Ok(())
+    }
+
+    pub fn execute_record_legacy_buy_offer_proposal(ctx: Context<ExecuteRecordLegacyBuyOfferProposal>) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::RecordLegacyBuyOffer { legacy_buy_offer, mint }
+            if legacy_buy_offer == ctx.accounts.legacy_buy_offer.key() && mint == ctx.accounts.mint.key() => {
+                escrow_legacy_buy_offer(
+                    &ctx.accounts.legacy_buy_offer.to_account_info(),
+                    &ctx.accounts.marketplace.to_account_info(),
+                    mint,
+                    ctx.accounts.escrow.to_account_info(),
+                    *ctx.bumps.get("escrow").unwrap(),
+                    ctx.accounts.legacy_escrow.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                )?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        Ok(())
+    }
+
+    pub fn execute_migrate_collection_proposal(ctx: Context<ExecuteMigrateCollectionProposal>) -> Result<()> {
+        ctx.accounts.proposal.verify_approved(&ctx.accounts.multisig, Clock::get()?.unix_timestamp)?;
+
+        match ctx.accounts.proposal.action.clone() {
+            AdminAction::MigrateCollection { collection } if collection == ctx.accounts.collection.key() => {
+                migrate_legacy_collection(
+                    &ctx.accounts.collection.to_account_info(),
+                    ctx.accounts.marketplace.key(),
+                    ctx.accounts.payer.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                )?;
+            }
+            _ => return Err(error!(ErrorCode::ErrInvalidProposalAction)),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(price_proposition: u64)]
+pub struct CreateBuyOffer<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    nft_mint: Account<'info, Mint>,
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
 
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(has_one = marketplace)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    #[account(constraint = marketplace_mint.key() == payment_mint.mint)]
+    marketplace_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Token account or lamport escrow depending on the payment mint, created on the first offer
     #[account(
     mut,
     seeds = [
     PREFIX.as_bytes(),
     marketplace.key().as_ref(),
-    marketplace.mint.as_ref(),
+    payment_mint.mint.as_ref(),
+    payer.key().as_ref(),
     ESCROW.as_bytes()
     ],
     bump,
     )]
-    escrow: Box<Account<'info, TokenAccount>>,
+    buyer_escrow: UncheckedAccount<'info>,
 
+    /// CHECK: SPL source checked by the token program, unused when the marketplace is native
     #[account(mut)]
-    seller_funds_dest_account: Box<Account<'info, TokenAccount>>,
+    buyer_paying_account: UncheckedAccount<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = nft_mint,
+    associated_token::authority = payer,
+    )]
+    buyer_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payer.key.as_ref(),
+    nft_mint.key().as_ref(),
+    price_proposition.to_string().as_bytes(),
+    ESCROW.as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    space = 193,
+    )]
+    buy_offer: Account<'info, BuyOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveBuyOffer<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+
+    /// CHECK: SPL refund account checked by the token program, unused when the marketplace is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    marketplace: Account<'info, Marketplace>,
+
+    /// CHECK: Token account or lamport escrow depending on the offer payment mint, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    buy_offer.payment_mint.as_ref(),
+    buyer.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    buyer_escrow: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    close = buyer,
+    has_one = marketplace,
+    constraint = buy_offer.authority == buyer.key(),
+    )]
+    buy_offer: Account<'info, BuyOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireBuyOffer<'info> {
+    #[account(mut)]
+    buyer: SystemAccount<'info>,
+
+    /// CHECK: Owner and mint are checked in the handler, unused when the marketplace is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    marketplace: Account<'info, Marketplace>,
+
+    /// CHECK: Token account or lamport escrow depending on the offer payment mint, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    buy_offer.payment_mint.as_ref(),
+    buyer.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    buyer_escrow: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    close = buyer,
+    has_one = marketplace,
+    constraint = buy_offer.authority == buyer.key(),
+    )]
+    buy_offer: Account<'info, BuyOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(payment_mint: Pubkey)]
+pub struct ReconcileBuyerEscrow<'info> {
+    buyer: SystemAccount<'info>,
+    marketplace: Account<'info, Marketplace>,
+
+    /// CHECK: Token account or lamport escrow depending on the payment mint, address checked by seeds
+    #[account(
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.as_ref(),
+    buyer.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    buyer_escrow: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOffer<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+
+    #[account(mut)]
+    buyer: SystemAccount<'info>,
+
+    #[account(mut, constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(has_one = marketplace, constraint = payment_mint.mint == buy_offer.payment_mint)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: Token account or wallet depending on the payment mint, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or lamport escrow depending on the offer payment mint, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    buy_offer.payment_mint.as_ref(),
+    buyer.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    buyer_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or wallet depending on the payment mint, chosen by the seller
+    #[account(mut)]
+    seller_funds_dest_account: UncheckedAccount<'info>,
+    /// CHECK: Buyer wallet or buyer token account depending on the payment mint, checked in the instruction
+    #[account(mut)]
+    maker_rebate_account: UncheckedAccount<'info>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    buy_offer.payment_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
 
     #[account(mut)]
     destination: Account<'info, TokenAccount>,
@@ -579,311 +2448,2881 @@ pub struct ExecuteOffer<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(marketplace_mint: Pubkey)]
-pub struct CreateMarketplace<'info> {
+#[instruction(price: u64)]
+pub struct CreateCollectionOffer<'info> {
     #[account(mut)]
     payer: Signer<'info>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(has_one = marketplace)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SPL source checked by the token program, unused when the payment mint is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
     #[account(
     init,
     seeds = [
-        PREFIX.as_bytes(),
-        payer.key.as_ref()
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payer.key.as_ref(),
+    collection.key().as_ref(),
+    price.to_string().as_bytes(),
+    COLLECTION_OFFER.as_bytes(),
     ],
     bump,
     payer = payer,
-    space = 112,
+    space = 160,
     )]
-    marketplace: Account<'info, Marketplace>,
+    collection_offer: Account<'info, CollectionOffer>,
 
-    mint: Account<'info, Mint>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCollectionOffer<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+
+    /// CHECK: SPL refund account checked by the token program, unused when the payment mint is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    marketplace: Account<'info, Marketplace>,
 
     #[account(
-    init,
-    token::mint = mint,
-    token::authority = escrow,
+    mut,
     seeds = [
     PREFIX.as_bytes(),
     marketplace.key().as_ref(),
-    marketplace_mint.as_ref(),
+    collection_offer.payment_mint.as_ref(),
     ESCROW.as_bytes()
     ],
     bump,
-    payer = payer,
     )]
     escrow: Account<'info, TokenAccount>,
 
+    #[account(
+    mut,
+    close = buyer,
+    has_one = marketplace,
+    constraint = collection_offer.authority == buyer.key(),
+    )]
+    collection_offer: Account<'info, CollectionOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteCollectionOffer<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+
+    #[account(mut)]
+    buyer: SystemAccount<'info>,
+
+    #[account(mut, constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(has_one = marketplace, constraint = payment_mint.mint == collection_offer.payment_mint)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: Token account or wallet depending on the payment mint, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or wallet depending on the payment mint, chosen by the seller
+    #[account(mut)]
+    seller_funds_dest_account: UncheckedAccount<'info>,
+    /// CHECK: Buyer wallet or buyer token account depending on the payment mint, checked in the instruction, gets back
+    /// the unused part of the escrowed maker fee and the maker rebate
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    #[account(constraint = nft_mint.key() == seller_nft_account.mint)]
+    nft_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    seller_nft_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+    init_if_needed,
+    payer = seller,
+    associated_token::mint = nft_mint,
+    associated_token::authority = buyer,
+    )]
+    buyer_nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    has_one = marketplace,
+    has_one = collection,
+    constraint = collection_offer.authority == buyer.key(),
+    )]
+    collection_offer: Account<'info, CollectionOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], price: u64)]
+pub struct CreateTraitOffer<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(has_one = marketplace)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SPL source checked by the token program, unused when the payment mint is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payer.key.as_ref(),
+    merkle_root.as_ref(),
+    price.to_string().as_bytes(),
+    TRAIT_OFFER.as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    space = 192,
+    )]
+    trait_offer: Account<'info, TraitOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveTraitOffer<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+
+    /// CHECK: SPL refund account checked by the token program, unused when the payment mint is native
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    trait_offer.payment_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    close = buyer,
+    has_one = marketplace,
+    constraint = trait_offer.authority == buyer.key(),
+    )]
+    trait_offer: Account<'info, TraitOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTraitOffer<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+
+    #[account(mut)]
+    buyer: SystemAccount<'info>,
+
+    #[account(mut, constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(has_one = marketplace, constraint = payment_mint.mint == trait_offer.payment_mint)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: Token account or wallet depending on the payment mint, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or wallet depending on the payment mint, chosen by the seller
+    #[account(mut)]
+    seller_funds_dest_account: UncheckedAccount<'info>,
+    /// CHECK: Buyer wallet or buyer token account depending on the payment mint, checked in the instruction, gets back
+    /// the unused part of the escrowed maker fee and the maker rebate
+    #[account(mut)]
+    buyer_paying_account: UncheckedAccount<'info>,
+
+    #[account(constraint = nft_mint.key() == seller_nft_account.mint)]
+    nft_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    seller_nft_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+    init_if_needed,
+    payer = seller,
+    associated_token::mint = nft_mint,
+    associated_token::authority = buyer,
+    )]
+    buyer_nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    has_one = marketplace,
+    has_one = collection,
+    constraint = trait_offer.authority == buyer.key(),
+    )]
+    trait_offer: Account<'info, TraitOffer>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    seller_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(mut, has_one = marketplace)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+
+    #[account(constraint = mint.key() == seller_nft_token_account.mint)]
+    mint: Account<'info, Mint>,
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payer.key.as_ref(),
+    seller_nft_token_account.mint.as_ref(),
+    AUCTION.as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    space = 272,
+    )]
+    auction: Account<'info, Auction>,
+
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = vault,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    auction.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    bidder: Signer<'info>,
+    /// CHECK: SPL source checked by the token program, unused when the payment mint is native
+    #[account(mut)]
+    bidder_paying_account: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet or token account of the previous bidder depending on the payment mint, refunded when outbid, only
+    /// checked against the auction if a bid was already placed
+    #[account(mut)]
+    previous_bidder_paying_account: UncheckedAccount<'info>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    auction.payment_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = marketplace, has_one = collection)]
+    auction: Account<'info, Auction>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(mut)]
+    authority: SystemAccount<'info>,
+    /// CHECK: This is not dangerous because it is checked against auction.highest_bidder
+    highest_bidder: UncheckedAccount<'info>,
+
+    // Ended auctions settle even while paused, the nft and the highest bid would be locked otherwise
+    #[account(mut)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(mut, has_one = marketplace, constraint = payment_mint.mint == auction.payment_mint)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: Token account or wallet depending on the payment mint, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or wallet depending on the payment mint, chosen by the seller
+    #[account(mut)]
+    seller_funds_dest_account: UncheckedAccount<'info>,
+    /// CHECK: Bidder wallet or bidder token account depending on the payment mint, gets back the unused part of the
+    /// escrowed maker fee and the maker rebate, only checked if a bid was placed
+    #[account(mut)]
+    highest_bidder_paying_account: UncheckedAccount<'info>,
+
+    mint: Box<Account<'info, Mint>>,
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = mint,
+    associated_token::authority = highest_bidder,
+    )]
+    winner_nft_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    mut,
+    close = authority,
+    has_one = marketplace,
+    has_one = collection,
+    has_one = authority,
+    has_one = mint,
+    constraint = auction.highest_bidder == highest_bidder.key(),
+    constraint = auction.destination == seller_funds_dest_account.key(),
+    )]
+    auction: Account<'info, Auction>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    auction.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump,
+    )]
+    vault: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, constraint = seller_nft_token_account.mint == auction.mint)]
+    seller_nft_token_account: Account<'info, TokenAccount>,
+
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, has_one = marketplace, constraint = payment_mint.mint == auction.payment_mint)]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    mut,
+    close = authority,
+    has_one = marketplace,
+    has_one = authority,
+    constraint = auction.highest_bid == 0 @ ErrorCode::ErrAuctionHasBids,
+    )]
+    auction: Account<'info, Auction>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    auction.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMint<'info> {
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, has_one = marketplace)]
+    payment_mint: Account<'info, PaymentMint>,
+}
+
+#[derive(Accounts)]
+pub struct RemovePaymentMint<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+    #[account(
+    mut,
+    close = authority,
+    has_one = marketplace,
+    constraint = payment_mint.mint != marketplace.mint @ ErrorCode::ErrCannotRemovePrimaryPaymentMint,
+    constraint = payment_mint.open_listings == 0 @ ErrorCode::ErrPaymentMintInUse,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLegacyBuyOffer<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    // Mint the legacy offer was escrowed in, attested by the authority since the legacy layout doesn't record it
+    mint: Account<'info, Mint>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Account<'info, TokenAccount>,
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = legacy_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    legacy_buy_offer.key().as_ref(),
+    LEGACY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    legacy_escrow: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because owner, discriminator, size and marketplace are checked in the instruction
+    legacy_buy_offer: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RefundLegacyBuyOffer<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+
+    // Gets back the rent of the legacy escrow, paid when the offer was recorded
+    #[account(mut)]
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(
+    mut,
+    constraint = buyer_paying_account.owner == buyer.key(),
+    constraint = buyer_paying_account.mint == legacy_escrow.mint,
+    )]
+    buyer_paying_account: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    legacy_buy_offer.key().as_ref(),
+    LEGACY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    legacy_escrow: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because owner, discriminator and size are checked in the instruction
+    #[account(mut)]
+    legacy_buy_offer: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteLegacyBuyOffer<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+
+    #[account(mut)]
+    buyer: SystemAccount<'info>,
+
+    #[account(mut, constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+
+    #[account(has_one = marketplace, constraint = payment_mint.mint == legacy_escrow.mint @ ErrorCode::ErrInvalidLegacyBuyOffer)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: Token account, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    legacy_buy_offer.key().as_ref(),
+    LEGACY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    legacy_escrow: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account chosen by the seller
+    #[account(mut)]
+    seller_funds_dest_account: UncheckedAccount<'info>,
+    // Legacy offers escrowed no maker fee, only a maker rebate can be paid back
+    #[account(
+    mut,
+    constraint = buyer_paying_account.owner == buyer.key(),
+    constraint = buyer_paying_account.mint == payment_mint.mint,
+    )]
+    buyer_paying_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    destination: Account<'info, TokenAccount>,
+    #[account(mut)]
+    seller_nft_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: This is not dangerous because owner, discriminator and size are checked in the instruction
+    #[account(mut)]
+    legacy_buy_offer: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    MULTISIG.as_bytes(),
+    ],
+    bump,
+    payer = authority,
+    space = 373,
+    )]
+    multisig: Account<'info, Multisig>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAdminProposal<'info> {
+    #[account(mut)]
+    proposer: Signer<'info>,
+    #[account(mut)]
+    multisig: Account<'info, Multisig>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    multisig.key().as_ref(),
+    multisig.proposal_count.to_string().as_bytes(),
+    ADMIN_PROPOSAL.as_bytes(),
+    ],
+    bump,
+    payer = proposer,
+    space = 730,
+    )]
+    proposal: Account<'info, AdminProposal>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminProposal<'info> {
+    approver: Signer<'info>,
+    multisig: Account<'info, Multisig>,
+    #[account(mut, has_one = multisig)]
+    proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminProposal<'info> {
+    canceller: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    multisig: Account<'info, Multisig>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMarketplaceProposal<'info> {
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(mut, has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(mut, constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_marketplace_mint: Pubkey)]
+pub struct ExecuteUpdateMarketplaceMintProposal<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Box<Account<'info, Multisig>>,
+    #[account(mut, constraint = marketplace.authority == multisig.key())]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Box<Account<'info, AdminProposal>>,
+
+    #[account(constraint = new_marketplace_mint == mint.key())]
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    init_if_needed,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_payment_mint: Pubkey)]
+pub struct ExecuteAddPaymentMintProposal<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Box<Account<'info, Multisig>>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Box<Account<'info, AdminProposal>>,
+
+    #[account(constraint = new_payment_mint == mint.key())]
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_payment_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_payment_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_payment_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutePaymentMintProposal<'info> {
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+
+    #[account(mut, has_one = marketplace)]
+    payment_mint: Account<'info, PaymentMint>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ExecuteCreateCollectionProposal<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    symbol.as_bytes(),
+    marketplace.key().as_ref(),
+    ],
+    bump,
+    payer = payer,
+    space = COLLECTION_SPACE,
+    )]
+    collection: Account<'info, Collection>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpdateCollectionProposal<'info> {
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
+    collection: Account<'info, Collection>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecordLegacyBuyOfferProposal<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+
+    mint: Account<'info, Mint>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    escrow: Account<'info, TokenAccount>,
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = legacy_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    legacy_buy_offer.key().as_ref(),
+    LEGACY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    legacy_escrow: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because owner, discriminator, size and marketplace are checked in the instruction
+    legacy_buy_offer: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMigrateCollectionProposal<'info> {
+    // Pays the extra rent of the migrated collection
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    proposer: SystemAccount<'info>,
+    #[account(has_one = marketplace)]
+    multisig: Account<'info, Multisig>,
+    #[account(constraint = marketplace.authority == multisig.key())]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, close = proposer, has_one = multisig, has_one = proposer)]
+    proposal: Account<'info, AdminProposal>,
+
+    /// CHECK: This is not dangerous because owner, discriminator, size and marketplace are checked in the instruction
+    #[account(mut)]
+    collection: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateReferrer<'info> {
+    #[account(mut)]
+    referrer: Signer<'info>,
+    marketplace: Account<'info, Marketplace>,
+    #[account(has_one = marketplace)]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    referrer.key().as_ref(),
+    REFERRER.as_bytes(),
+    ],
+    bump,
+    payer = referrer,
+    space = 152,
+    )]
+    referrer_stats: Account<'info, ReferrerStats>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTraderStats<'info> {
+    #[account(mut)]
+    trader: Signer<'info>,
+    marketplace: Account<'info, Marketplace>,
+    #[account(has_one = marketplace)]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    trader.key().as_ref(),
+    TRADER.as_bytes(),
+    ],
+    bump,
+    payer = trader,
+    space = 112,
+    )]
+    trader_stats: Account<'info, TraderStats>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRoyalties<'info> {
+    creator: Signer<'info>,
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(
+    mut,
+    has_one = marketplace,
+    has_one = creator,
+    has_one = rent_payer,
+    close = rent_payer,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    royalty_vault.payment_mint.as_ref(),
+    creator.key().as_ref(),
+    ROYALTY_VAULT.as_bytes(),
+    ],
+    bump,
+    )]
+    royalty_vault: Account<'info, RoyaltyVault>,
+    /// CHECK: Checked against the royalty vault, gets its rent back
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
+    /// CHECK: Payment mint royalty escrow holding SPL royalties, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    royalty_vault.payment_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account or wallet depending on the payment mint, chosen by the creator
+    #[account(mut)]
+    destination: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(marketplace_mint: Pubkey)]
+pub struct CreateMarketplace<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+    init,
+    seeds = [
+        PREFIX.as_bytes(),
+        payer.key.as_ref()
+    ],
+    bump,
+    payer = payer,
+    space = MARKETPLACE_SPACE,
+    )]
+    marketplace: Account<'info, Marketplace>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    marketplace_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    marketplace_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    marketplace_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = payer,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMarketplace<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// CHECK: This is not dangerous because owner, discriminator, size and authority are checked in the instruction
+    #[account(mut)]
+    marketplace: UncheckedAccount<'info>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    mint.key().as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCollection<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    /// CHECK: This is not dangerous because owner, discriminator, size and marketplace are checked in the instruction
+    #[account(mut)]
+    collection: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMarketplace<'info> {
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketplacePaused<'info> {
+    signer: Signer<'info>,
+    #[account(mut, constraint = marketplace.can_pause(&signer.key()) @ ErrorCode::ErrNotAuthorityOrGuardian)]
+    marketplace: Account<'info, Marketplace>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollectionPaused<'info> {
+    signer: Signer<'info>,
+    #[account(constraint = marketplace.can_pause(&signer.key()) @ ErrorCode::ErrNotAuthorityOrGuardian)]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
+    collection: Account<'info, Collection>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    new_authority: Signer<'info>,
+    #[account(
+    mut,
+    constraint = marketplace.pending_authority == Some(new_authority.key()) @ ErrorCode::ErrNotPendingAuthority,
+    )]
+    marketplace: Account<'info, Marketplace>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_marketplace_mint: Pubkey)]
+pub struct UpdateMarketplaceMint<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(constraint = new_marketplace_mint == mint.key())]
+    mint: Account<'info, Mint>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    escrow: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    token::mint = mint,
+    token::authority = royalty_escrow,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    )]
+    royalty_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    init_if_needed,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    new_marketplace_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    payer = authority,
+    space = PAYMENT_MINT_SPACE,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct CreateCollection<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    symbol.as_bytes(),
+    marketplace.key().as_ref(),
+    ],
+    bump,
+    payer = authority,
+    space = COLLECTION_SPACE,
+    )]
+    collection: Account<'info, Collection>,
+
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    authority: Signer<'info>,
+    #[account(has_one = authority)]
+    marketplace: Account<'info, Marketplace>,
+
+    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
+    collection: Account<'info, Collection>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateSellOrder<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut)]
+    seller_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(mut, has_one = marketplace)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+
+    #[account(constraint = mint.key() == seller_nft_token_account.mint)]
+    mint: Account<'info, Mint>,
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    init,
+    seeds = [
+    PREFIX.as_bytes(),
+    seller_nft_token_account.key().as_ref(),
+    nonce.to_string().as_bytes(),
+    SELL_ORDER.as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    space = 219,
+    )]
+    sell_order: Account<'info, SellOrder>,
+
+    #[account(
+    init,
+    token::mint = mint,
+    token::authority = vault,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    sell_order.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump,
+    payer = payer,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSellOrderPrice<'info> {
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    sell_order: Account<'info, SellOrder>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveSellOrder<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
+    seller_nft_token_account: Account<'info, TokenAccount>,
+    #[account(mut, has_one = authority, constraint = seller_nft_token_account.mint == sell_order.mint)]
+    sell_order: Account<'info, SellOrder>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    sell_order.marketplace.as_ref(),
+    sell_order.payment_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    sell_order.marketplace.as_ref(),
+    sell_order.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump = sell_order.vault_bump,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireSellOrder<'info> {
+    #[account(mut)]
+    authority: SystemAccount<'info>,
+    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
+    seller_nft_token_account: Account<'info, TokenAccount>,
+    #[account(
+    mut,
+    close = authority,
+    has_one = authority,
+    constraint = seller_nft_token_account.mint == sell_order.mint,
+    )]
+    sell_order: Account<'info, SellOrder>,
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    sell_order.marketplace.as_ref(),
+    sell_order.payment_mint.as_ref(),
+    PAYMENT_MINT.as_bytes()
+    ],
+    bump,
+    )]
+    payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    sell_order.marketplace.as_ref(),
+    sell_order.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump = sell_order.vault_bump,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateLegacySellOrder<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
+    seller_nft_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because owner, discriminator and size are checked in the instruction
+    #[account(mut)]
+    legacy_sell_order: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    "vault".as_bytes(),
+    seller_nft_token_account.mint.as_ref(),
+    ],
+    bump,
+    )]
+    legacy_vault: Account<'info, TokenAccount>,
+
+    token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SellOrderAddQuantity<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
+    seller_nft_token_account: Account<'info, TokenAccount>,
+    #[account(mut, has_one = authority, has_one = marketplace, constraint = seller_nft_token_account.mint == sell_order.mint)]
+    sell_order: Account<'info, SellOrder>,
+
+    #[account(constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Box<Account<'info, Marketplace>>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Box<Account<'info, Collection>>,
+    #[account(mut, has_one = marketplace, constraint = payment_mint.mint == sell_order.payment_mint)]
+    payment_mint: Box<Account<'info, PaymentMint>>,
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    sell_order.marketplace.as_ref(),
+    sell_order.key().as_ref(),
+    "vault".as_bytes(),
+    ],
+    bump = sell_order.vault_bump,
+    )]
+    vault: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+    #[account(mut)]
+    buyer_nft_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: SPL source checked by the token program, unused when the marketplace is native
+    #[account(mut)]
+    buyer_paying_token_account: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = !marketplace.paused @ ErrorCode::ErrMarketplacePaused)]
+    marketplace: Account<'info, Marketplace>,
+    #[account(mut, has_one = marketplace)]
+    payment_mint: Account<'info, PaymentMint>,
+    /// CHECK: Token account or wallet depending on the payment mint, key is checked against fees_destination
+    #[account(mut, constraint = marketplace_dest_account.key() == payment_mint.fees_destination)]
+    marketplace_dest_account: UncheckedAccount<'info>,
+    /// CHECK: Payment mint royalty escrow holding SPL royalties until creators claim them, address checked by seeds
+    #[account(
+    mut,
+    seeds = [
+    PREFIX.as_bytes(),
+    marketplace.key().as_ref(),
+    payment_mint.mint.as_ref(),
+    ROYALTY_ESCROW.as_bytes()
+    ],
+    bump,
+    )]
+    royalty_escrow: UncheckedAccount<'info>,
+    #[account(constraint = collection.marketplace_key == marketplace.key(), constraint = !collection.paused @ ErrorCode::ErrCollectionPaused)]
+    collection: Account<'info, Collection>,
+
+    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
+    metadata: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+#[account]
+pub struct Marketplace {
+    fees: i16, //Maker fee, a negative fee is paid back to the maker out of the taker fee
+    fees_destination: Pubkey,
+    authority: Pubkey,
+    mint: Pubkey,
+    pending_authority: Option<Pubkey>, //Set by propose_authority, becomes the authority once it signs accept_authority
+    fee_timelock: i64, //Seconds a fee increase waits before it applies, can only be raised
+    pending_fees: Option<PendingFees>,
+    guardian: Option<Pubkey>, //Can pause and unpause trading next to the authority
+    paused: bool, //Blocks new listings, offers and fills, withdrawals stay open
+    pause_reason: u8,
+    referral_fee: u16, //Basis points of the marketplace share paid to the referrer of a fill
+    taker_fees: u16, //Paid by the side filling a sell order or an offer
+    pending_taker_fees: Option<PendingFees>,
+    fee_discount: Option<FeeDiscount>,
+}
+
+#[account]
+pub struct SellOrder {
+    marketplace: Pubkey,
+    price: u64,
+    quantity: u64,
+    mint: Pubkey,
+    authority: Pubkey,
+    destination: Pubkey,
+    expiry: Option<i64>, //Unix timestamp after which the order can no longer be filled
+    dutch_auction: Option<DutchAuction>, //When set, price decays from start_price down to price
+    vault_bump: u8,
+    payment_mint: Pubkey, //Mint the price is denominated in, must be accepted by the marketplace
+}
+
+// Layout of marketplaces created before the fee, pause and referral settings, only read to migrate them
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyMarketplace {
+    fees: i16,
+    fees_destination: Pubkey,
+    authority: Pubkey,
+    mint: Pubkey,
+}
+
+// Layout of collections created before the fee, pause and royalty settings, only read to migrate them
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyCollection {
+    marketplace_key: Pubkey,
+    symbol: String,
+    required_verifier: Pubkey,
+    fees: Option<i16>,
+    ignore_creator_fee: bool,
+}
+
+// Layout of sell orders created before vaults were split per sell order, only read to migrate them
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacySellOrder {
+    marketplace: Pubkey,
+    price: u64,
+    quantity: u64,
+    mint: Pubkey,
+    authority: Pubkey,
+    destination: Pubkey,
+}
+
+// Layout of buy offers funded from the shared marketplace escrow, only read to refund them
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyBuyOffer {
+    marketplace: Pubkey,
+    mint: Pubkey,
+    proposed_price: u64,
+    authority: Pubkey,
+    destination: Pubkey,
+}
+
+// Fee increase queued by update_marketplace or update_collection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PendingFees {
+    fees: i16,
+    effective_at: i64,
+}
+
+// Share of the seller_fee_basis_points paid to the creators of a fill, shares are in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum RoyaltyPolicy {
+    Full,
+    Tip {
+        floor: u16, //Minimum share always paid, buyers can tip the rest of the royalties on top of the price
+    },
+    Cap {
+        max_fees: u16, //Basis points of the price
+    },
+}
+
+// Maker and taker fees of fills whose buyer holds the pass are capped at fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeDiscount {
+    pass: FeePass,
+    fees: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum FeePass {
+    Mint {
+        mint: Pubkey,
+    },
+    Collection {
+        collection: Pubkey, //Collection of this marketplace
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DutchAuction {
+    start_price: u64,
+    start_time: i64,
+    end_time: i64,
+}
+
+#[account]
+pub struct Collection {
+    marketplace_key: Pubkey,
+    symbol: String,
+    required_verifier: Pubkey,
+    fees: Option<i16>, //Takes priority over marketplace fees
+    ignore_creator_fee: bool,
+    pending_fees: Option<PendingFees>, //Waits for the marketplace fee_timelock
+    paused: bool, //Same as Marketplace.paused for this collection only
+    pause_reason: u8,
+    taker_fees: Option<u16>, //Takes priority over marketplace taker fees
+    pending_taker_fees: Option<PendingFees>,
+    royalty_policy: RoyaltyPolicy, //Applies unless ignore_creator_fee is set
+}
+
+#[account]
+pub struct BuyOffer {
+    marketplace: Pubkey,
+    mint: Pubkey,
+    proposed_price: u64,
+    authority: Pubkey,
+    destination: Pubkey,
+    expiry: Option<i64>, //Unix timestamp after which the offer can no longer be executed
+    payment_mint: Pubkey, //Mint the offer is escrowed in, kept so the offer outlives a marketplace mint change
+    maker_fee: u64, //Highest maker fee the buyer can be charged, escrowed on top of the price
+}
+
+#[account]
+pub struct CollectionOffer {
+    marketplace: Pubkey,
+    collection: Pubkey,
+    authority: Pubkey,
+    price: u64, //Price paid for each NFT
+    quantity: u64, //Number of NFTs still wanted
+    maker_fee: u64, //Highest maker fee charged for each NFT, escrowed on top of the price
+    payment_mint: Pubkey, //Mint the offer is escrowed in, kept so the offer outlives a marketplace mint change
+}
+
+#[account]
+pub struct TraitOffer {
+    marketplace: Pubkey,
+    collection: Pubkey,
+    authority: Pubkey,
+    merkle_root: [u8; 32], //Root of the keccak merkle tree of TRAIT_OFFER_LEAF prefixed eligible mint addresses
+    price: u64, //Price paid for each NFT
+    quantity: u64, //Number of NFTs still wanted
+    maker_fee: u64, //Highest maker fee charged for each NFT, escrowed on top of the price
+    payment_mint: Pubkey, //Mint the offer is escrowed in, kept so the offer outlives a marketplace mint change
+}
+
+#[account]
+pub struct Auction {
+    marketplace: Pubkey,
+    collection: Pubkey,
+    authority: Pubkey,
+    mint: Pubkey,
+    destination: Pubkey,
+    reserve_price: u64,
+    min_bid_increment: u64,
+    end_time: i64,
+    highest_bidder: Pubkey, //Seller until the first bid is placed
+    highest_bid: u64,
+    highest_bid_maker_fee: u64, //Highest maker fee the highest bidder can be charged, escrowed on top of the bid
+    payment_mint: Pubkey, //Mint the bids are escrowed in, kept so the auction outlives a marketplace mint change
+}
+
+// One per mint accepted by a marketplace, the escrow for the mint lives at the same seeds with ESCROW
+#[account]
+pub struct PaymentMint {
+    marketplace: Pubkey,
+    mint: Pubkey,
+    fees_destination: Pubkey,
+    open_listings: u64, //NFTs listed in sell orders and auctions priced in this mint, it can't be removed while some are
+    fee_splits: Vec<FeeSplit>, //Empty when fees_destination receives everything, otherwise its first entry is fees_destination
+    fee_tiers: Vec<FeeTier>, //Taker fees by filled volume in this mint, sorted by volume
+}
+
+// One per referrer and payment mint, referral shares are paid to destination
+#[account]
+pub struct ReferrerStats {
+    marketplace: Pubkey,
+    payment_mint: Pubkey,
+    referrer: Pubkey,
+    destination: Pubkey,
+    volume: u64, //Cumulative amount of the referred fills
+    fees_earned: u64,
+}
+
+// One per creator and payment mint, accrues the royalties of the fills until the creator claims them
+#[account]
+pub struct RoyaltyVault {
+    marketplace: Pubkey,
+    payment_mint: Pubkey,
+    creator: Pubkey,
+    amount: u64, //Royalties left to claim, native vaults hold them on top of their rent
+    rent_payer: Pubkey, //Marketplace or fill signer that funded the rent, refunded when the vault is closed on claim
+}
+
+// One per trader and payment mint, only fills passing it are counted
+#[account]
+pub struct TraderStats {
+    marketplace: Pubkey,
+    payment_mint: Pubkey,
+    trader: Pubkey,
+    volume: u64, //Cumulative amount of the fills taken by the trader
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeTier {
+    volume: u64, //Trader volume from which the tier applies
+    fees: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeSplit {
+    destination: Pubkey,
+    share: u16, //Basis points of the marketplace share
+}
+
+// M-of-N admin, once created it is the marketplace authority and only acts through approved AdminProposals
+#[account]
+pub struct Multisig {
+    marketplace: Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+    proposal_count: u64, //Seeds the next proposal
+}
+
+#[account]
+pub struct AdminProposal {
+    multisig: Pubkey,
+    proposer: Pubkey, //Paid for the proposal, refunded when it is executed
+    action: AdminAction,
+    approved_by: Vec<Pubkey>,
+    expires_at: i64, //Can't be approved nor executed from then on, anyone can cancel it, only the proposer before
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminAction {
+    UpdateMarketplace {
+        optional_fees: Option<i16>,
+        optional_fees_destination: Option<Pubkey>,
+        optional_fee_timelock: Option<i64>,
+        optional_taker_fees: Option<u16>,
+    },
+    ProposeAuthority {
+        new_authority: Pubkey,
+    },
+    SetGuardian {
+        guardian: Option<Pubkey>,
+    },
+    UpdateMarketplaceMint {
+        mint: Pubkey,
+        fees_destination: Pubkey,
+    },
+    CreateCollection {
+        symbol: String,
+        required_verifier: Pubkey,
+        fee: Option<i16>,
+        ignore_fee: bool,
+        royalty_policy: RoyaltyPolicy,
+    },
+    UpdateCollection {
+        collection: Pubkey,
+        optional_fee: Option<i16>,
+        optional_symbol: Option<String>,
+        optional_required_verifier: Option<Pubkey>,
+        optional_ignore_creator_fee: Option<bool>,
+        optional_taker_fee: Option<u16>,
+        optional_royalty_policy: Option<RoyaltyPolicy>,
+    },
+    CancelAuthorityTransfer,
+    SetReferralFee {
+        referral_fee: u16,
+    },
+    SetFeeDiscount {
+        fee_discount: Option<FeeDiscount>,
+    },
+    AddPaymentMint {
+        mint: Pubkey,
+        fees_destination: Pubkey,
+    },
+    UpdatePaymentMint {
+        mint: Pubkey,
+        fees_destination: Pubkey,
+    },
+    RemovePaymentMint {
+        mint: Pubkey,
+    },
+    SetFeeSplits {
+        mint: Pubkey,
+        fee_splits: Vec<FeeSplit>,
+    },
+    SetFeeTiers {
+        mint: Pubkey,
+        fee_tiers: Vec<FeeTier>,
+    },
+    SetSigners {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    RecordLegacyBuyOffer {
+        legacy_buy_offer: Pubkey,
+        mint: Pubkey,
+    },
+    MigrateCollection {
+        collection: Pubkey,
+    },
+}
+
+// Payment to one creator account, in the order the creators were passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatorShare {
+    destination: Pubkey,
+    amount: u64,
+}
+
+// Accounts used to accrue the creator shares of a fill into their royalty vaults
+pub struct RoyaltyAccounts<'info> {
+    payer: AccountInfo<'info>, //Marketplace funding the rent of the royalty vaults created by the fill
+    signer: AccountInfo<'info>, //Signer of the fill, funds the vault rent when the marketplace can't
+    escrow: AccountInfo<'info>, //Payment mint royalty escrow receiving SPL royalties
+    system_program: AccountInfo<'info>,
+}
+
+// Maker and taker parts of the marketplace fee of a fill, the maker rebate is capped by the taker share
+pub struct FillFees {
+    maker_share: u64,
+    taker_share: u64,
+    maker_rebate: u64,
+}
+
+// Split of an offer payment, returned by pay_offer_from_escrow so the caller can emit it
+#[derive(Default)]
+pub struct FillShares {
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+    referrer: Option<Pubkey>,
+    referral_share: u64,
+}
+
+#[event]
+pub struct SellOrderCreated {
+    sell_order: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    quantity: u64,
+}
+
+#[event]
+pub struct SellOrderUpdated {
+    sell_order: Pubkey,
+    price: u64,
+    quantity: u64,
+}
+
+#[event]
+pub struct SellOrderFilled {
+    sell_order: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+    buyer: Pubkey,
+    payment_mint: Pubkey,
+    quantity: u64,
+    unit_price: u64,
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+    referrer: Option<Pubkey>,
+    referral_share: u64,
+    remaining_quantity: u64,
+}
+
+#[event]
+pub struct SellOrderCancelled {
+    sell_order: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+    quantity: u64, //Quantity returned to the seller
+    remaining_quantity: u64,
+}
+
+#[event]
+pub struct OfferCreated {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    expiry: Option<i64>,
+}
+
+#[event]
+pub struct OfferExecuted {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    seller: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+    referrer: Option<Pubkey>,
+    referral_share: u64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    price: u64, //Refunded to the buyer
+    expired: bool,
+}
+
+// Full configuration after the change so indexers do not need to fetch the account
+#[event]
+pub struct MarketplaceUpdated {
+    marketplace: Pubkey,
+    authority: Pubkey,
+    pending_authority: Option<Pubkey>,
+    guardian: Option<Pubkey>,
+    mint: Pubkey,
+    fees: i16,
+    fees_destination: Pubkey,
+    fee_timelock: i64,
+    pending_fees: Option<PendingFees>,
+    paused: bool,
+    pause_reason: u8,
+    referral_fee: u16,
+    taker_fees: u16,
+    pending_taker_fees: Option<PendingFees>,
+    fee_discount: Option<FeeDiscount>,
+}
+
+#[event]
+pub struct CollectionUpdated {
+    collection: Pubkey,
+    marketplace: Pubkey,
+    symbol: String,
+    required_verifier: Pubkey,
+    fees: Option<i16>,
+    pending_fees: Option<PendingFees>,
+    ignore_creator_fee: bool,
+    paused: bool,
+    pause_reason: u8,
+    taker_fees: Option<u16>,
+    pending_taker_fees: Option<PendingFees>,
+    royalty_policy: RoyaltyPolicy,
+}
+
+// Full configuration after the change, removed is set when the payment mint is closed
+#[event]
+pub struct PaymentMintUpdated {
+    payment_mint: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    fees_destination: Pubkey,
+    fee_splits: Vec<FeeSplit>,
+    fee_tiers: Vec<FeeTier>,
+    removed: bool,
+}
+
+#[event]
+pub struct CollectionOfferCreated {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    buyer: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    quantity: u64,
+}
+
+#[event]
+pub struct CollectionOfferFilled {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    seller: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+    referrer: Option<Pubkey>,
+    referral_share: u64,
+    remaining_quantity: u64,
+}
+
+#[event]
+pub struct CollectionOfferCancelled {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    buyer: Pubkey,
+    price: u64,
+    quantity: u64, //Quantity refunded to the buyer
+}
+
+#[event]
+pub struct TraitOfferCreated {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    buyer: Pubkey,
+    payment_mint: Pubkey,
+    merkle_root: [u8; 32],
+    price: u64,
+    quantity: u64,
+}
+
+#[event]
+pub struct TraitOfferFilled {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    seller: Pubkey,
+    payment_mint: Pubkey,
+    price: u64,
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+    referrer: Option<Pubkey>,
+    referral_share: u64,
+    remaining_quantity: u64,
+}
+
+#[event]
+pub struct TraitOfferCancelled {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    buyer: Pubkey,
+    price: u64,
+    quantity: u64, //Quantity refunded to the buyer
+}
+
+#[event]
+pub struct AuctionCreated {
+    auction: Pubkey,
+    marketplace: Pubkey,
+    collection: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+    payment_mint: Pubkey,
+    reserve_price: u64,
+    min_bid_increment: u64,
+    end_time: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    auction: Pubkey,
+    marketplace: Pubkey,
+    bidder: Pubkey,
+    payment_mint: Pubkey,
+    amount: u64,
+    end_time: i64, //Pushed out when the bid lands in the extension window
+}
+
+#[event]
+pub struct AuctionSettled {
+    auction: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+    highest_bidder: Pubkey,
+    payment_mint: Pubkey,
+    highest_bid: u64, //Zero when nobody bid and the nft went back to the seller
+    marketplace_share: u64,
+    creator_shares: Vec<CreatorShare>,
+    seller_share: u64,
+    maker_rebate: u64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    auction: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    seller: Pubkey,
+}
+
+#[event]
+pub struct LegacyBuyOfferRecorded {
+    offer: Pubkey,
+    marketplace: Pubkey,
+    mint: Pubkey,
+    buyer: Pubkey,
+    payment_mint: Pubkey, //Mint the offer was escrowed in, its price now sits in the legacy escrow of the offer
+    price: u64,
+}
+
+#[event]
+pub struct RoyaltiesClaimed {
+    royalty_vault: Pubkey,
+    marketplace: Pubkey,
+    creator: Pubkey,
+    payment_mint: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+}
+
+#[event]
+pub struct ReferrerCreated {
+    referrer_stats: Pubkey,
+    marketplace: Pubkey,
+    payment_mint: Pubkey,
+    referrer: Pubkey,
+    destination: Pubkey,
+}
+
+#[event]
+pub struct TraderStatsCreated {
+    trader_stats: Pubkey,
+    marketplace: Pubkey,
+    payment_mint: Pubkey,
+    trader: Pubkey,
+}
+
+#[event]
+pub struct AdminProposalUpdated {
+    proposal: Pubkey,
+    multisig: Pubkey,
+    proposer: Pubkey,
+    action: AdminAction,
+    approved_by: Vec<Pubkey>,
+    expires_at: i64,
+    cancelled: bool, //Dropped before being executed, the account is closed
+}
+
+impl CollectionOffer {
+    pub fn is_native(&self) -> bool {
+        return self.payment_mint == spl_token::native_mint::id();
+    }
+
+    pub fn escrowed_amount(&self) -> u64 {
+        return self.price.checked_add(self.maker_fee).unwrap().checked_mul(self.quantity).unwrap();
+    }
+}
+
+impl TraitOffer {
+    pub fn is_native(&self) -> bool {
+        return self.payment_mint == spl_token::native_mint::id();
+    }
+
+    pub fn escrowed_amount(&self) -> u64 {
+        return self.price.checked_add(self.maker_fee).unwrap().checked_mul(self.quantity).unwrap();
+    }
+}
+
+impl FillFees {
+    pub fn marketplace_share(&self) -> u64 {
+        return self.maker_share.checked_add(self.taker_share).unwrap().checked_sub(self.maker_rebate).unwrap();
+    }
+}
+
+impl Collection {
+    pub fn init(
+        &mut self,
+        marketplace_key: Pubkey,
+        symbol: String,
+        required_verifier: Pubkey,
+        fee: Option<i16>,
+        ignore_fee: bool,
+        royalty_policy: RoyaltyPolicy,
+    ) -> Result<()> {
+        self.marketplace_key = marketplace_key;
+        self.required_verifier = required_verifier;
+        self.symbol = symbol;
+        self.fees = fee;
+        self.ignore_creator_fee = ignore_fee;
+        self.royalty_policy = royalty_policy;
+
+        self.validate()
+    }
+
+    pub fn update(
+        &mut self,
+        marketplace: &Marketplace,
+        optional_fee: Option<i16>,
+        optional_symbol: Option<String>,
+        optional_required_verifier: Option<Pubkey>,
+        optional_ignore_creator_fee: Option<bool>,
+        optional_taker_fee: Option<u16>,
+        optional_royalty_policy: Option<RoyaltyPolicy>,
+        now: i64,
+    ) -> Result<()> {
+        if let Some(pending_fees) = PendingFees::take_applied(&mut self.pending_fees, now) {
+            self.fees = Some(pending_fees);
+        }
+        if let Some(pending_taker_fees) = PendingFees::take_applied(&mut self.pending_taker_fees, now) {
+            self.taker_fees = Some(pending_taker_fees as u16);
+        }
+        if let Some(fee_share) = optional_fee {
+            let current_fees = self.effective_fees(marketplace, now);
+            self.pending_fees = PendingFees::schedule(current_fees, fee_share, marketplace.fee_timelock, now);
+            if self.pending_fees.is_none() {
+                self.fees = Some(fee_share);
+            }
+        }
+        if let Some(taker_fee_share) = optional_taker_fee {
+            if taker_fee_share > 10000 {
+                return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+            }
+            let current_taker_fees = self.effective_taker_fees(marketplace, now) as i16;
+            self.pending_taker_fees =
+                PendingFees::schedule(current_taker_fees, taker_fee_share as i16, marketplace.fee_timelock, now);
+            if self.pending_taker_fees.is_none() {
+                self.taker_fees = Some(taker_fee_share);
+            }
+        }
+        if let Some(symbol) = optional_symbol {
+            self.symbol = symbol;
+        }
+        if let Some(required_verifier) = optional_required_verifier {
+            self.required_verifier = required_verifier;
+        }
+        if let Some(ignore_creator_fee) = optional_ignore_creator_fee {
+            self.ignore_creator_fee = ignore_creator_fee;
+        }
+        if let Some(royalty_policy) = optional_royalty_policy {
+            self.royalty_policy = royalty_policy;
+        }
+
+        self.validate()?;
+        self.validate_total_fees(marketplace, now)
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
-}
+    pub fn is_part_of_collection(&self, metadata: &Metadata) -> bool {
+        return if let Some(creators) = metadata.data.creators.as_ref() {
+            metadata.data.symbol.starts_with(&self.symbol.to_string())
+                && creators.iter().any(|c| c.address == self.required_verifier && c.verified)
+        } else {
+            false
+        };
+    }
 
-#[derive(Accounts)]
-pub struct UpdateMarketplace<'info> {
-    authority: Signer<'info>,
-    #[account(mut, has_one = authority)]
-    marketplace: Account<'info, Marketplace>,
-}
+    pub fn effective_fees(&self, marketplace: &Marketplace, now: i64) -> i16 {
+        if let Some(pending_fees) = PendingFees::applied(self.pending_fees, now) {
+            return pending_fees;
+        }
+        return self.fees.unwrap_or_else(|| marketplace.effective_fees(now));
+    }
 
-#[derive(Accounts)]
-#[instruction(new_marketplace_mint: Pubkey)]
-pub struct UpdateMarketplaceMint<'info> {
-    #[account(mut)]
-    authority: Signer<'info>,
-    #[account(mut, has_one = authority)]
-    marketplace: Account<'info, Marketplace>,
+    pub fn effective_taker_fees(&self, marketplace: &Marketplace, now: i64) -> u16 {
+        if let Some(pending_taker_fees) = PendingFees::applied(self.pending_taker_fees, now) {
+            return pending_taker_fees as u16;
+        }
+        return self.taker_fees.unwrap_or_else(|| marketplace.effective_taker_fees(now));
+    }
 
-    #[account(constraint = new_marketplace_mint == mint.key())]
-    mint: Account<'info, Mint>,
+    // Highest maker fee a fill can be charged until the pending increases are applied
+    pub fn max_fees(&self, marketplace: &Marketplace, now: i64) -> i16 {
+        let fees = PendingFees::max(self.effective_fees(marketplace, now), self.pending_fees);
+        if self.fees.is_none() {
+            return PendingFees::max(fees, marketplace.pending_fees);
+        }
+        return fees;
+    }
 
-    #[account(
-    init_if_needed,
-    token::mint = mint,
-    token::authority = escrow,
-    seeds = [
-    PREFIX.as_bytes(),
-    marketplace.key().as_ref(),
-    new_marketplace_mint.as_ref(),
-    ESCROW.as_bytes()
-    ],
-    bump,
-    payer = authority,
-    )]
-    escrow: Account<'info, TokenAccount>,
+    pub fn max_taker_fees(&self, marketplace: &Marketplace, now: i64) -> u16 {
+        let taker_fees = PendingFees::max(self.effective_taker_fees(marketplace, now) as i16, self.pending_taker_fees);
+        if self.taker_fees.is_none() {
+            return PendingFees::max(taker_fees, marketplace.pending_taker_fees) as u16;
+        }
+        return taker_fees as u16;
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
-}
+    // Maker fee escrowed by a bidder on top of its price, the unused part is refunded on fill
+    pub fn offer_maker_fee(&self, marketplace: &Marketplace, price: u64, now: i64) -> u64 {
+        return calculate_fee(price, self.max_fees(marketplace, now).max(0) as u16, 10000);
+    }
 
-#[derive(Accounts)]
-#[instruction(symbol: String)]
-pub struct CreateCollection<'info> {
-    #[account(mut)]
-    authority: Signer<'info>,
-    #[account(mut, has_one = authority)]
-    marketplace: Account<'info, Marketplace>,
-    #[account(
-    init,
-    seeds = [
-    PREFIX.as_bytes(),
-    symbol.as_bytes(),
-    marketplace.key().as_ref(),
-    ],
-    bump,
-    payer = authority,
-    space = 90,
-    )]
-    collection: Account<'info, Collection>,
+    pub fn validate_total_fees(&self, marketplace: &Marketplace, now: i64) -> Result<()> {
+        validate_total_fees(self.max_fees(marketplace, now), self.max_taker_fees(marketplace, now))
+    }
 
-    system_program: Program<'info, System>,
-    rent: Sysvar<'info, Rent>,
+    pub fn validate(&self) -> Result<()> {
+        if let Some(fee) = self.fees {
+            validate_fees(fee, -10000)?;
+        }
+        if let Some(taker_fee) = self.taker_fees {
+            validate_fees(taker_fee as i16, 0)?;
+        }
+        self.royalty_policy.validate()?;
+        PendingFees::validate(self.pending_fees, -10000)?;
+        PendingFees::validate(self.pending_taker_fees, 0)
+    }
 }
 
-#[derive(Accounts)]
-pub struct UpdateCollection<'info> {
-    authority: Signer<'info>,
-    #[account(has_one = authority)]
-    marketplace: Account<'info, Marketplace>,
+impl PendingFees {
+    // Decreases apply right away and drop any queued increase, increases wait fee_timelock seconds
+    pub fn schedule(current_fees: i16, fees: i16, fee_timelock: i64, now: i64) -> Option<PendingFees> {
+        if fees <= current_fees || fee_timelock == 0 {
+            return None;
+        }
+        Some(PendingFees {
+            fees,
+            effective_at: now.checked_add(fee_timelock).unwrap(),
+        })
+    }
 
-    #[account(mut, constraint = collection.marketplace_key == marketplace.key())]
-    collection: Account<'info, Collection>,
-}
+    pub fn applied(pending_fees: Option<PendingFees>, now: i64) -> Option<i16> {
+        match pending_fees {
+            Some(pending_fees) if now >= pending_fees.effective_at => Some(pending_fees.fees),
+            _ => None,
+        }
+    }
 
-#[derive(Accounts)]
-#[instruction(price: u64)]
-pub struct CreateSellOrder<'info> {
-    #[account(mut)]
-    payer: Signer<'info>,
-    #[account(mut)]
-    seller_nft_token_account: Box<Account<'info, TokenAccount>>,
+    pub fn take_applied(pending_fees: &mut Option<PendingFees>, now: i64) -> Option<i16> {
+        let applied = PendingFees::applied(*pending_fees, now);
+        if applied.is_some() {
+            *pending_fees = None;
+        }
+        applied
+    }
 
-    marketplace: Box<Account<'info, Marketplace>>,
-    #[account(constraint = collection.marketplace_key == marketplace.key())]
-    collection: Box<Account<'info, Collection>>,
+    // Highest fee that can apply until the pending change is dropped
+    pub fn max(current_fees: i16, pending_fees: Option<PendingFees>) -> i16 {
+        return pending_fees.map_or(current_fees, |pending_fees| current_fees.max(pending_fees.fees));
+    }
 
-    #[account(constraint = mint.key() == seller_nft_token_account.mint)]
-    mint: Account<'info, Mint>,
-    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
-    metadata: UncheckedAccount<'info>,
+    pub fn validate(pending_fees: Option<PendingFees>, min_fees: i16) -> Result<()> {
+        if let Some(pending_fees) = pending_fees {
+            validate_fees(pending_fees.fees, min_fees)?;
+        }
+        Ok(())
+    }
+}
 
-    #[account(
-    init_if_needed,
-    token::mint = mint,
-    token::authority = vault,
-    seeds = [
-    PREFIX.as_bytes(),
-    "vault".as_bytes(),
-    seller_nft_token_account.mint.as_ref(),
-    ],
-    bump,
-    payer = payer,
-    )]
-    vault: Account<'info, TokenAccount>,
+impl SellOrder {
+    pub fn is_expired(&self, now: i64) -> bool {
+        has_expired(self.expiry, now)
+    }
 
-    #[account(
-    init,
-    seeds = [
-    PREFIX.as_bytes(),
-    seller_nft_token_account.key().as_ref(),
-    price.to_string().as_bytes(),
-    ],
-    bump,
-    payer = payer,
-    space = 152,
-    )]
-    sell_order: Account<'info, SellOrder>,
+    pub fn current_price(&self, now: i64) -> u64 {
+        let dutch_auction = match self.dutch_auction {
+            Some(dutch_auction) => dutch_auction,
+            None => return self.price,
+        };
+        if now <= dutch_auction.start_time {
+            return dutch_auction.start_price;
+        }
+        if now >= dutch_auction.end_time {
+            return self.price;
+        }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
+        let elapsed = (now - dutch_auction.start_time) as u128;
+        let duration = (dutch_auction.end_time - dutch_auction.start_time) as u128;
+        let price_drop = ((dutch_auction.start_price - self.price) as u128)
+            .checked_mul(elapsed)
+            .unwrap()
+            .checked_div(duration)
+            .unwrap();
+        return dutch_auction.start_price - price_drop as u64;
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(dutch_auction) = self.dutch_auction {
+            if dutch_auction.start_price < self.price || dutch_auction.end_time <= dutch_auction.start_time {
+                return Err(error!(ErrorCode::ErrInvalidDutchAuction));
+            }
+        }
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct RemoveSellOrder<'info> {
-    #[account(mut)]
-    authority: Signer<'info>,
-    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
-    seller_nft_token_account: Account<'info, TokenAccount>,
-    #[account(mut, has_one = authority, constraint = seller_nft_token_account.mint == sell_order.mint)]
-    sell_order: Account<'info, SellOrder>,
+impl BuyOffer {
+    pub fn is_expired(&self, now: i64) -> bool {
+        has_expired(self.expiry, now)
+    }
 
-    #[account(
-    mut,
-    seeds = [
-    PREFIX.as_bytes(),
-    "vault".as_bytes(),
-    seller_nft_token_account.mint.as_ref(),
-    ],
-    bump,
-    )]
-    vault: Account<'info, TokenAccount>,
+    pub fn is_native(&self) -> bool {
+        return self.payment_mint == spl_token::native_mint::id();
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
+    pub fn escrowed_amount(&self) -> u64 {
+        return self.proposed_price.checked_add(self.maker_fee).unwrap();
+    }
 }
 
-#[derive(Accounts)]
-pub struct SellOrderAddQuantity<'info> {
-    #[account(mut)]
-    authority: Signer<'info>,
-    #[account(mut, constraint = authority.key() == seller_nft_token_account.owner)]
-    seller_nft_token_account: Account<'info, TokenAccount>,
-    #[account(mut, has_one = authority, constraint = seller_nft_token_account.mint == sell_order.mint)]
-    sell_order: Account<'info, SellOrder>,
+impl Auction {
+    pub fn is_native(&self) -> bool {
+        return self.payment_mint == spl_token::native_mint::id();
+    }
 
-    #[account(
-    mut,
-    seeds = [
-    PREFIX.as_bytes(),
-    "vault".as_bytes(),
-    seller_nft_token_account.mint.as_ref(),
-    ],
-    bump,
-    )]
-    vault: Account<'info, TokenAccount>,
+    pub fn escrowed_amount(&self) -> u64 {
+        return self.highest_bid.checked_add(self.highest_bid_maker_fee).unwrap();
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-    rent: Sysvar<'info, Rent>,
+    pub fn minimum_bid(&self) -> u64 {
+        return if self.highest_bid == 0 {
+            self.reserve_price
+        } else {
+            self.highest_bid.checked_add(self.min_bid_increment).unwrap()
+        };
+    }
 }
 
-#[derive(Accounts)]
-pub struct Buy<'info> {
-    buyer: Signer<'info>,
-    #[account(mut)]
-    buyer_nft_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    buyer_paying_token_account: Account<'info, TokenAccount>,
+impl Marketplace {
+    pub fn update(
+        &mut self,
+        optional_fees: Option<i16>,
+        optional_fees_destination: Option<Pubkey>,
+        optional_fee_timelock: Option<i64>,
+        optional_taker_fees: Option<u16>,
+        now: i64,
+    ) -> Result<()> {
+        if let Some(pending_fees) = PendingFees::take_applied(&mut self.pending_fees, now) {
+            self.fees = pending_fees;
+        }
+        if let Some(pending_taker_fees) = PendingFees::take_applied(&mut self.pending_taker_fees, now) {
+            self.taker_fees = pending_taker_fees as u16;
+        }
+        if let Some(fee_timelock) = optional_fee_timelock {
+            //Lowering the delay would let the next increase skip it
+            if fee_timelock < self.fee_timelock {
+                return Err(error!(ErrorCode::ErrInvalidFeeTimelock));
+            }
+            self.fee_timelock = fee_timelock;
+        }
+        if let Some(fees) = optional_fees {
+            self.pending_fees = PendingFees::schedule(self.fees, fees, self.fee_timelock, now);
+            if self.pending_fees.is_none() {
+                self.fees = fees;
+            }
+        }
+        if let Some(taker_fees) = optional_taker_fees {
+            if taker_fees > 10000 {
+                return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+            }
+            self.pending_taker_fees =
+                PendingFees::schedule(self.taker_fees as i16, taker_fees as i16, self.fee_timelock, now);
+            if self.pending_taker_fees.is_none() {
+                self.taker_fees = taker_fees;
+            }
+        }
+        if let Some(fees_destination) = optional_fees_destination {
+            self.fees_destination = fees_destination;
+        }
+        self.validate()
+    }
 
-    marketplace: Account<'info, Marketplace>,
-    #[account(mut, constraint = marketplace_dest_account.key() == marketplace.fees_destination)]
-    marketplace_dest_account: Account<'info, TokenAccount>,
-    #[account(constraint = collection.marketplace_key == marketplace.key())]
-    collection: Account<'info, Collection>,
+    pub fn update_mint(&mut self, mint: Pubkey, fees_destination: Pubkey) -> Result<()> {
+        self.mint = mint;
+        self.fees_destination = fees_destination;
+        self.validate()
+    }
 
-    /// CHECK: This is not dangerous because check it all the time using the verify_metadata_and_derivation func
-    metadata: UncheckedAccount<'info>,
+    pub fn validate(&self) -> Result<()> {
+        validate_fees(self.fees, -10000)?;
+        validate_fees(self.taker_fees as i16, 0)?;
+        if let Some(fee_discount) = self.fee_discount {
+            validate_fees(fee_discount.fees as i16, 0)?;
+        }
+        if self.referral_fee > 10000 {
+            return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+        }
+        if self.fee_timelock < 0 || self.fee_timelock > MAX_FEE_TIMELOCK {
+            return Err(error!(ErrorCode::ErrInvalidFeeTimelock));
+        }
+        PendingFees::validate(self.pending_fees, -10000)?;
+        PendingFees::validate(self.pending_taker_fees, 0)?;
+        validate_total_fees(
+            PendingFees::max(self.fees, self.pending_fees),
+            PendingFees::max(self.taker_fees as i16, self.pending_taker_fees) as u16,
+        )
+    }
 
-    #[account(
-    mut,
-    seeds = [
-    PREFIX.as_bytes(),
-    "vault".as_bytes(),
-    buyer_nft_token_account.mint.as_ref()
-    ],
-    bump,
-    )]
-    vault: Box<Account<'info, TokenAccount>>,
+    pub fn effective_fees(&self, now: i64) -> i16 {
+        return PendingFees::applied(self.pending_fees, now).unwrap_or(self.fees);
+    }
+
+    pub fn effective_taker_fees(&self, now: i64) -> u16 {
+        return PendingFees::applied(self.pending_taker_fees, now).map_or(self.taker_fees, |fees| fees as u16);
+    }
 
-    system_program: Program<'info, System>,
-    token_program: Program<'info, Token>,
-}
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        return self.authority == *signer || self.guardian == Some(*signer);
+    }
 
-#[account]
-pub struct Marketplace {
-    fees: u16,
-    fees_destination: Pubkey,
-    authority: Pubkey,
-    mint: Pubkey,
+    pub fn is_native(&self) -> bool {
+        return self.mint == spl_token::native_mint::id();
+    }
 }
 
-#[account]
-pub struct SellOrder {
-    marketplace: Pubkey,
-    price: u64,
-    quantity: u64,
-    mint: Pubkey,
-    authority: Pubkey,
-    destination: Pubkey,
+impl Multisig {
+    pub fn validate(&self) -> Result<()> {
+        let mut signers = self.signers.clone();
+        signers.sort();
+        signers.dedup();
+        if self.signers.is_empty()
+            || self.signers.len() > MAX_MULTISIG_SIGNERS
+            || signers.len() != self.signers.len()
+            || self.threshold == 0
+            || self.threshold as usize > self.signers.len() {
+            return Err(error!(ErrorCode::ErrInvalidMultisig));
+        }
+        Ok(())
+    }
 }
 
-#[account]
-pub struct Collection {
-    marketplace_key: Pubkey,
-    symbol: String,
-    required_verifier: Pubkey,
-    fees: Option<u16>, //Takes priority over marketplace fees
-    ignore_creator_fee: bool,
+impl AdminProposal {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn verify_approved(&self, multisig: &Multisig, now: i64) -> Result<()> {
+        if self.is_expired(now) {
+            return Err(error!(ErrorCode::ErrProposalExpired));
+        }
+        let approvals = self.approved_by.iter().filter(|approver| multisig.signers.contains(*approver)).count();
+        if approvals < multisig.threshold as usize {
+            return Err(error!(ErrorCode::ErrNotEnoughApprovals));
+        }
+        Ok(())
+    }
 }
 
-#[account]
-pub struct BuyOffer {
-    marketplace: Pubkey,
-    mint: Pubkey,
-    proposed_price: u64,
-    authority: Pubkey,
-    destination: Pubkey,
+impl ReferrerStats {
+    pub fn record(&mut self, volume: u64, referral_share: u64) {
+        self.volume = self.volume.checked_add(volume).unwrap();
+        self.fees_earned = self.fees_earned.checked_add(referral_share).unwrap();
+    }
 }
 
-impl Collection {
-    pub fn is_part_of_collection(&self, metadata: &Metadata) -> bool {
-        return if let Some(creators) = metadata.data.creators.as_ref() {
-            metadata.data.symbol.starts_with(&self.symbol.to_string())
-                && creators.iter().any(|c| c.address == self.required_verifier && c.verified)
-        } else {
-            false
+impl RoyaltyPolicy {
+    // Returns the royalties taken from the price and the tip paid on top of it
+    pub fn royalties(&self, total_amount: u64, seller_fee_basis_points: u16, tip: u16) -> (u64, u64) {
+        let full_royalties = calculate_fee(total_amount, seller_fee_basis_points, 10000);
+        return match *self {
+            RoyaltyPolicy::Full => (full_royalties, 0),
+            RoyaltyPolicy::Tip { floor } => (
+                calculate_fee(full_royalties, floor, 10000),
+                calculate_fee(full_royalties, tip.min(10000 - floor), 10000),
+            ),
+            RoyaltyPolicy::Cap { max_fees } => (calculate_fee(total_amount, seller_fee_basis_points.min(max_fees), 10000), 0),
         };
     }
 
     pub fn validate(&self) -> Result<()> {
-        if let Some(fee) = self.fees {
-            if fee > 10000 {
-                return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
-            }
+        let share = match *self {
+            RoyaltyPolicy::Full => 0,
+            RoyaltyPolicy::Tip { floor } => floor,
+            RoyaltyPolicy::Cap { max_fees } => max_fees,
+        };
+        if share > 10000 {
+            return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
         }
         Ok(())
     }
 }
 
-impl Marketplace {
+impl FeePass {
+    pub fn accounts_len(&self) -> usize {
+        return match self {
+            FeePass::Mint { .. } => 1,
+            FeePass::Collection { .. } => 3,
+        };
+    }
+}
+
+impl RoyaltyVault {
+    pub fn is_native(&self) -> bool {
+        return self.payment_mint == spl_token::native_mint::id();
+    }
+}
+
+impl TraderStats {
+    pub fn record(&mut self, volume: u64) {
+        self.volume = self.volume.checked_add(volume).unwrap();
+    }
+}
+
+impl PaymentMint {
+    pub fn set(&mut self, marketplace: Pubkey, mint: Pubkey, fees_destination: Pubkey) {
+        self.marketplace = marketplace;
+        self.mint = mint;
+        self.set_fees_destination(fees_destination);
+    }
+
+    pub fn list(&mut self, quantity: u64) {
+        self.open_listings = self.open_listings.checked_add(quantity).unwrap();
+    }
+
+    pub fn unlist(&mut self, quantity: u64) {
+        self.open_listings = self.open_listings.checked_sub(quantity).unwrap();
+    }
+
+    pub fn set_fees_destination(&mut self, fees_destination: Pubkey) {
+        self.fees_destination = fees_destination;
+        if let Some(first_split) = self.fee_splits.first_mut() {
+            first_split.destination = fees_destination;
+        }
+    }
+
+    pub fn set_fee_splits(&mut self, fee_splits: Vec<FeeSplit>) -> Result<()> {
+        if let Some(first_split) = fee_splits.first() {
+            self.fees_destination = first_split.destination;
+        }
+        self.fee_splits = fee_splits;
+        self.validate()
+    }
+
     pub fn validate(&self) -> Result<()> {
-        if self.fees > 10000 {
-            return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+        if self.fee_tiers.len() > MAX_FEE_TIERS
+            || self.fee_tiers.iter().any(|fee_tier| fee_tier.fees > 10000)
+            || self.fee_tiers.windows(2).any(|fee_tiers| fee_tiers[0].volume >= fee_tiers[1].volume) {
+            return Err(error!(ErrorCode::ErrInvalidFeeTiers));
+        }
+        if self.fee_splits.is_empty() {
+            return Ok(());
+        }
+
+        let mut destinations: Vec<Pubkey> = self.fee_splits.iter().map(|fee_split| fee_split.destination).collect();
+        destinations.sort();
+        destinations.dedup();
+        let total_share: u64 = self.fee_splits.iter().map(|fee_split| fee_split.share as u64).sum();
+        if self.fee_splits.len() > MAX_FEE_SPLITS
+            || destinations.len() != self.fee_splits.len()
+            || self.fee_splits.iter().any(|fee_split| fee_split.share == 0)
+            || total_share != 10000 {
+            return Err(error!(ErrorCode::ErrInvalidFeeSplits));
         }
         Ok(())
     }
+
+    pub fn is_native(&self) -> bool {
+        return self.mint == spl_token::native_mint::id();
+    }
 }
 
 fn verify_metadata_and_derivation(unverified_metadata: &AccountInfo, nft_mint: &Pubkey, collection: &Collection) -> Result<Metadata> {
@@ -911,8 +5350,722 @@ pub mod constant {
     pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
     pub const PREFIX: &str = "MARKETPLACE";
     pub const ESCROW: &str = "ESCROW";
+    pub const SELL_ORDER: &str = "SELL_ORDER";
+    pub const LEGACY_SELL_ORDER_SPACE: usize = 152;
+    pub const LEGACY_BUY_OFFER_SPACE: usize = 144;
+    pub const MARKETPLACE_SPACE: usize = 250;
+    pub const LEGACY_MARKETPLACE_SPACE: usize = 112;
+    pub const COLLECTION_SPACE: usize = 120;
+    pub const LEGACY_COLLECTION_SPACE: usize = 90;
+    pub const ROYALTY_VAULT_SPACE: usize = 144;
+    pub const PAYMENT_MINT_SPACE: usize = 340;
+    pub const COLLECTION_OFFER: &str = "COLLECTION_OFFER";
+    pub const TRAIT_OFFER: &str = "TRAIT_OFFER";
+    pub const TRAIT_OFFER_LEAF: &str = "TRAIT_OFFER_LEAF"; //Prefixes the trait offer merkle leaves so a leaf can't be passed off as an inner node
+    pub const AUCTION: &str = "AUCTION";
+    pub const AUCTION_EXTENSION_WINDOW: i64 = 600; //Bids in the last 10 minutes push the end time out
+    pub const PAYMENT_MINT: &str = "PAYMENT_MINT";
+    pub const MULTISIG: &str = "MULTISIG";
+    pub const ADMIN_PROPOSAL: &str = "ADMIN_PROPOSAL";
+    pub const MAX_MULTISIG_SIGNERS: usize = 10;
+    pub const ADMIN_PROPOSAL_LIFETIME: i64 = 604800; //7 days
+    pub const MAX_FEE_TIMELOCK: i64 = 2592000; //30 days
+    pub const MAX_FEE_SPLITS: usize = 5;
+    pub const REFERRER: &str = "REFERRER";
+    pub const TRADER: &str = "TRADER";
+    pub const MAX_FEE_TIERS: usize = 5;
+    pub const ROYALTY_VAULT: &str = "ROYALTY_VAULT";
+    pub const ROYALTY_ESCROW: &str = "ROYALTY_ESCROW"; //Holds the SPL royalties of a payment mint apart from the offers escrow
+    pub const LEGACY_ESCROW: &str = "LEGACY_ESCROW"; //Holds the price of one legacy buy offer apart from the pooled escrow
+}
+
+
+#[allow(clippy::too_many_arguments)]
+fn pay_offer_from_escrow<'c, 'info>(
+    total_amount: u64,
+    metadata: Metadata,
+    collection: &Collection,
+    marketplace: &Marketplace,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    escrow: AccountInfo<'info>,
+    marketplace_dest_account: AccountInfo<'info>,
+    seller_funds_dest_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    royalty_accounts: RoyaltyAccounts<'info>,
+    payment_mint: Pubkey,
+    fee_splits: &[FeeSplit],
+    fee_tiers: &[FeeTier],
+    accept_referral: bool,
+    maker: Pubkey,
+    taker: Option<Pubkey>,
+    discount_holder: Option<Pubkey>,
+    escrowed_maker_fee: u64,
+    maker_rebate_account: AccountInfo<'info>,
+) -> Result<FillShares> {
+    let is_native = payment_mint == spl_token::native_mint::id();
+    let mut creators_count = 0;
+    let mut creators_distributions_option: Option<Vec<(&AccountInfo, u8, Pubkey, u8)>> = None;
+    if !collection.ignore_creator_fee {
+        if let Some(creators) = metadata.data.creators {
+            creators_count = creators.len();
+            let creators_distributions = verify_and_get_creators(creators, remaining_accounts, collection.marketplace_key, payment_mint)?;
+            creators_distributions_option = Some(creators_distributions);
+        }
+    }
+    let split_accounts = get_split_accounts(fee_splits, remaining_accounts, creators_count)?;
+    let mut index = creators_count + split_accounts.len();
+    let mut referral = None;
+    if accept_referral {
+        let traders = [Some(maker), taker];
+        referral = get_referral(collection.marketplace_key, payment_mint, remaining_accounts, index, &traders)?;
+        if referral.is_some() {
+            index = index + 2;
+        }
+    }
+    let mut trader_stats = None;
+    if let Some(taker) = taker {
+        trader_stats = get_trader_stats(collection.marketplace_key, payment_mint, taker, remaining_accounts, index)?;
+        if trader_stats.is_some() {
+            index = index + 1;
+        }
+    }
+    let mut discount_fees = None;
+    if let Some(discount_holder) = discount_holder {
+        discount_fees = get_discount_fees(marketplace.fee_discount, discount_holder, remaining_accounts, index)?;
+    }
+
+    //The bidder is the maker and pays its fee from the amount escrowed on top of the price, the seller pays the taker fee
+    let now = Clock::get()?.unix_timestamp;
+    let mut maker_fee = collection.effective_fees(marketplace, now);
+    let mut taker_fee = collection.effective_taker_fees(marketplace, now);
+    if let Some(trader_stats) = trader_stats.as_mut() {
+        taker_fee = get_tier_fees(fee_tiers, trader_stats.volume, taker_fee);
+        trader_stats.record(total_amount);
+        trader_stats.exit(&crate::ID)?;
+    }
+    //The pass only discounts the fee of the side its holder is on
+    if let Some(discount_fees) = discount_fees {
+        if discount_holder == Some(maker) {
+            maker_fee = maker_fee.min(discount_fees as i16);
+        } else {
+            taker_fee = taker_fee.min(discount_fees);
+        }
+    }
+    let mut fill_fees = get_fill_fees(total_amount, maker_fee, taker_fee);
+    //Fee increases after the offer was placed are capped by what the bidder escrowed
+    fill_fees.maker_share = fill_fees.maker_share.min(escrowed_maker_fee);
+    let maker_refund = escrowed_maker_fee
+        .checked_sub(fill_fees.maker_share)
+        .unwrap()
+        .checked_add(fill_fees.maker_rebate)
+        .unwrap();
+
+    //Offers are escrowed at their price so there is no room for a royalty tip
+    let mut creators_share = 0;
+    if !collection.ignore_creator_fee {
+        creators_share = collection.royalty_policy.royalties(total_amount, metadata.data.seller_fee_basis_points, 0).0;
+    }
+    let mut marketplace_share = fill_fees.marketplace_share();
+    let seller_share = total_amount
+        .checked_sub(creators_share)
+        .and_then(|share| share.checked_sub(fill_fees.taker_share))
+        .ok_or_else(|| error!(ErrorCode::ErrFeesExceedPrice))?;
+
+    if maker_refund > 0 {
+        pay_from_escrow(
+            is_native,
+            escrow.clone(),
+            maker_rebate_account,
+            token_program.clone(),
+            maker_refund,
+            signer,
+        )?;
+    }
+
+    let mut referral_share = 0;
+    if let Some((referrer_stats, referral_dest_account)) = referral.as_mut() {
+        referral_share = calculate_fee(marketplace_share, marketplace.referral_fee, 10000);
+        marketplace_share = marketplace_share - referral_share;
+        referrer_stats.record(total_amount, referral_share);
+        referrer_stats.exit(&crate::ID)?;
+        pay_from_escrow(
+            is_native,
+            escrow.clone(),
+            referral_dest_account.to_account_info(),
+            token_program.clone(),
+            referral_share,
+            signer,
+        )?;
+    }
+
+    let mut creator_shares = Vec::new();
+    if let Some(creators) = creators_distributions_option.as_ref() {
+        for creator in creators {
+            let creator_share = calculate_fee(creators_share, creator.1 as u16, 100);
+            accrue_royalties(&royalty_accounts, creator.0, creator.2, creator.3, collection.marketplace_key, payment_mint, creator_share)?;
+            pay_from_escrow(
+                is_native,
+                escrow.clone(),
+                get_royalty_destination(is_native, creator.0, &royalty_accounts),
+                token_program.clone(),
+                creator_share,
+                signer
+            )?;
+            creator_shares.push(CreatorShare { destination: creator.0.key(), amount: creator_share });
+        }
+    }
+
+    let fee_payments = get_fee_payments(fee_splits, marketplace_dest_account, split_accounts, marketplace_share)?;
+    for (fee_dest_account, fee_share) in fee_payments {
+        pay_from_escrow(
+            is_native,
+            escrow.clone(),
+            fee_dest_account,
+            token_program.clone(),
+            fee_share,
+            signer,
+        )?;
+    }
+
+    pay_from_escrow(
+        is_native,
+        escrow,
+        seller_funds_dest_account,
+        token_program,
+        seller_share,
+        signer,
+    )?;
+
+    Ok(FillShares {
+        marketplace_share,
+        creator_shares,
+        seller_share,
+        maker_rebate: fill_fees.maker_rebate,
+        referrer: referral.map(|(referrer_stats, _)| referrer_stats.referrer),
+        referral_share,
+    })
+}
+
+// Creates the royalty vault on the first fill of the creator, the marketplace funds its rent
+fn accrue_royalties<'info>(
+    royalty_accounts: &RoyaltyAccounts<'info>,
+    royalty_vault: &AccountInfo<'info>,
+    creator: Pubkey,
+    bump: u8,
+    marketplace_key: Pubkey,
+    payment_mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if royalty_vault.owner == &System::id() {
+        let seeds = &[
+            PREFIX.as_bytes(),
+            marketplace_key.as_ref(),
+            payment_mint.as_ref(),
+            creator.as_ref(),
+            ROYALTY_VAULT.as_bytes(),
+            &[bump], ];
+        let signer: &[&[&[u8]]] = &[&seeds[..]];
+
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(ROYALTY_VAULT_SPACE).saturating_sub(royalty_vault.lamports());
+        let mut rent_payer = royalty_accounts.payer.key();
+        if required_lamports > 0 {
+            //The marketplace funds the vault rent from the lamports it holds above its own rent, the signer of the fill
+            //covers it otherwise so fills never fail on creator setup, either way the rent goes back on claim
+            let payer = &royalty_accounts.payer;
+            let available_lamports = payer.lamports().saturating_sub(rent.minimum_balance(payer.data_len()));
+            if available_lamports >= required_lamports {
+                pay_native_from_escrow(payer, royalty_vault, required_lamports)?;
+            } else {
+                if royalty_accounts.signer.lamports() < required_lamports {
+                    return Err(error!(ErrorCode::ErrRoyaltyVaultUnfunded));
+                }
+                pay_native(
+                    royalty_accounts.signer.clone(),
+                    royalty_vault.clone(),
+                    royalty_accounts.system_program.clone(),
+                    required_lamports,
+                )?;
+                rent_payer = royalty_accounts.signer.key();
+            }
+        }
+        invoke_signed(
+            &system_instruction::allocate(royalty_vault.key, ROYALTY_VAULT_SPACE as u64),
+            &[royalty_vault.clone(), royalty_accounts.system_program.clone()],
+            signer,
+        )?;
+        invoke_signed(
+            &system_instruction::assign(royalty_vault.key, &crate::ID),
+            &[royalty_vault.clone(), royalty_accounts.system_program.clone()],
+            signer,
+        )?;
+
+        let new_royalty_vault = RoyaltyVault { marketplace: marketplace_key, payment_mint, creator, amount: 0, rent_payer };
+        let mut data = royalty_vault.try_borrow_mut_data()?;
+        let dst: &mut [u8] = &mut data;
+        new_royalty_vault.try_serialize(&mut std::io::Cursor::new(dst))?;
+    }
+
+    let mut royalty_vault = Account::<RoyaltyVault>::try_from(royalty_vault)?;
+    royalty_vault.amount = royalty_vault.amount.checked_add(amount).unwrap();
+    royalty_vault.exit(&crate::ID)
+}
+
+// Native royalties are held by the royalty vault itself, SPL royalties by the payment mint royalty escrow
+fn get_royalty_destination<'info>(
+    is_native: bool,
+    royalty_vault: &AccountInfo<'info>,
+    royalty_accounts: &RoyaltyAccounts<'info>,
+) -> AccountInfo<'info> {
+    if is_native {
+        royalty_vault.clone()
+    } else {
+        royalty_accounts.escrow.clone()
+    }
+}
+
+fn pay_from_buyer<'info>(
+    is_native: bool,
+    buyer: AccountInfo<'info>,
+    buyer_paying_account: AccountInfo<'info>,
+    dest: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if is_native {
+        pay_native(buyer, dest, system_program, amount)
+    } else {
+        pay(buyer_paying_account, dest, buyer, token_program, amount)
+    }
+}
+
+fn pay_from_escrow<'info>(
+    is_native: bool,
+    escrow: AccountInfo<'info>,
+    dest: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    if is_native {
+        pay_native_from_escrow(&escrow, &dest, amount)
+    } else {
+        pay_with_signer(escrow.clone(), dest, escrow, token_program, amount, signer)
+    }
+}
+
+// Native refunds go to the wallet itself, SPL refunds to a token account of the wallet in the payment mint
+fn is_refund_account(account: &AccountInfo, owner: Pubkey, payment_mint: Pubkey) -> bool {
+    if payment_mint == spl_token::native_mint::id() {
+        return account.key() == owner;
+    }
+    match Account::<TokenAccount>::try_from(account) {
+        Ok(token_account) => token_account.owner == owner && token_account.mint == payment_mint,
+        Err(_) => false,
+    }
+}
+
+fn get_escrow_balance(escrow: &AccountInfo, is_native: bool) -> Result<u64> {
+    if is_native {
+        //Native escrows keep their rent exempt reserve on top of the escrowed lamports
+        return Ok(escrow.lamports().checked_sub(Rent::get()?.minimum_balance(escrow.data_len())).unwrap());
+    }
+    Ok(Account::<TokenAccount>::try_from(escrow)?.amount)
+}
+
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed_hash = leaf;
+    for proof_element in proof.iter() {
+        if computed_hash <= *proof_element {
+            computed_hash = keccak::hashv(&[&computed_hash[..], &proof_element[..]]).0;
+        } else {
+            computed_hash = keccak::hashv(&[&proof_element[..], &computed_hash[..]]).0;
+        }
+    }
+    return computed_hash == root;
+}
+
+// Buy offers created before the upgrade have a smaller layout, they can't be loaded as BuyOffer
+fn load_legacy_buy_offer(legacy_buy_offer_info: &AccountInfo, marketplace: Pubkey) -> Result<LegacyBuyOffer> {
+    if legacy_buy_offer_info.owner != &crate::ID {
+        return Err(error!(ErrorCode::ErrInvalidLegacyBuyOffer));
+    }
+    let data = legacy_buy_offer_info.try_borrow_data()?;
+    if data.len() != LEGACY_BUY_OFFER_SPACE || data[..8] != BuyOffer::discriminator() {
+        return Err(error!(ErrorCode::ErrInvalidLegacyBuyOffer));
+    }
+    let legacy_buy_offer = LegacyBuyOffer::deserialize(&mut &data[8..])
+        .map_err(|_| error!(ErrorCode::ErrInvalidLegacyBuyOffer))?;
+    if legacy_buy_offer.marketplace != marketplace {
+        return Err(error!(ErrorCode::ErrInvalidLegacyBuyOffer));
+    }
+    Ok(legacy_buy_offer)
+}
+
+// Rewrites a collection created before the fee, pause and royalty settings with the defaults of a new one, the payer
+// funds the extra rent
+fn migrate_legacy_collection<'info>(
+    collection_info: &AccountInfo<'info>,
+    marketplace: Pubkey,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if collection_info.owner != &crate::ID {
+        return Err(error!(ErrorCode::ErrInvalidLegacyCollection));
+    }
+
+    let legacy_collection = {
+        let data = collection_info.try_borrow_data()?;
+        if data.len() != LEGACY_COLLECTION_SPACE || data[..8] != Collection::discriminator() {
+            return Err(error!(ErrorCode::ErrInvalidLegacyCollection));
+        }
+        LegacyCollection::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::ErrInvalidLegacyCollection))?
+    };
+    if legacy_collection.marketplace_key != marketplace {
+        return Err(error!(ErrorCode::ErrInvalidLegacyCollection));
+    }
+
+    let collection = Collection {
+        marketplace_key: legacy_collection.marketplace_key,
+        symbol: legacy_collection.symbol,
+        required_verifier: legacy_collection.required_verifier,
+        fees: legacy_collection.fees,
+        ignore_creator_fee: legacy_collection.ignore_creator_fee,
+        pending_fees: None,
+        paused: false,
+        pause_reason: 0,
+        taker_fees: None,
+        pending_taker_fees: None,
+        royalty_policy: RoyaltyPolicy::Full,
+    };
+    collection.validate()?;
+    resize_account(
+        collection_info,
+        payer,
+        system_program,
+        COLLECTION_SPACE,
+    )?;
+    collection.try_serialize(&mut &mut collection_info.try_borrow_mut_data()?[..])?;
+
+    emit_collection_updated(&Account::<Collection>::try_from(collection_info)?);
+    Ok(())
+}
+
+// The legacy layout doesn't record the mint an offer was escrowed in, once the authority attests it the price moves out
+// of the pooled escrow of that mint so refunds and fills of legacy offers never spend the deposits of newer offers
+fn escrow_legacy_buy_offer<'info>(
+    legacy_buy_offer_info: &AccountInfo<'info>,
+    marketplace: &AccountInfo<'info>,
+    mint: Pubkey,
+    escrow: AccountInfo<'info>,
+    escrow_bump: u8,
+    legacy_escrow: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let legacy_buy_offer = load_legacy_buy_offer(legacy_buy_offer_info, marketplace.key())?;
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        marketplace.key.as_ref(),
+        mint.as_ref(),
+        ESCROW.as_bytes(),
+        &[escrow_bump], ];
+    let signer: &[&[&[u8]]] = &[&seeds[..]];
+    pay_with_signer(
+        escrow.clone(),
+        legacy_escrow,
+        escrow,
+        token_program,
+        legacy_buy_offer.proposed_price,
+        signer,
+    )?;
+
+    emit!(LegacyBuyOfferRecorded {
+        offer: legacy_buy_offer_info.key(),
+        marketplace: legacy_buy_offer.marketplace,
+        mint: legacy_buy_offer.mint,
+        buyer: legacy_buy_offer.authority,
+        payment_mint: mint,
+        price: legacy_buy_offer.proposed_price,
+    });
+    Ok(())
+}
+
+fn close_legacy_buy_offer(legacy_buy_offer_info: &AccountInfo, buyer: &AccountInfo) -> Result<()> {
+    let refunded_lamports = buyer.lamports()
+        .checked_add(legacy_buy_offer_info.lamports())
+        .unwrap();
+    **buyer.lamports.borrow_mut() = refunded_lamports;
+    **legacy_buy_offer_info.lamports.borrow_mut() = 0;
+    legacy_buy_offer_info.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+// solana-program 1.8 has no AccountInfo::realloc, this grows the account the same way: the runtime reads the new
+// length written right before the data and accepts up to MAX_PERMITTED_DATA_INCREASE more bytes per instruction
+fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let original_len = account.data_len();
+    if new_len <= original_len || new_len - original_len > MAX_PERMITTED_DATA_INCREASE {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    let rent_shortfall = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        pay_native(payer, account.clone(), system_program, rent_shortfall)?;
+    }
+
+    unsafe {
+        let data_ptr = account.try_borrow_mut_data()?.as_mut_ptr();
+        *(data_ptr.offset(-8) as *mut u64) = new_len as u64;
+        let slice_len_ptr = (account.data.as_ptr() as *mut u64).offset(1);
+        *slice_len_ptr = new_len as u64;
+    }
+    account.try_borrow_mut_data()?[original_len..].fill(0);
+    Ok(())
+}
+
+fn has_expired(expiry: Option<i64>, now: i64) -> bool {
+    return if let Some(expiry) = expiry {
+        now >= expiry
+    } else {
+        false
+    };
+}
+
+fn emit_marketplace_updated(marketplace: &Account<Marketplace>) {
+    emit!(MarketplaceUpdated {
+        marketplace: marketplace.key(),
+        authority: marketplace.authority,
+        pending_authority: marketplace.pending_authority,
+        guardian: marketplace.guardian,
+        mint: marketplace.mint,
+        fees: marketplace.fees,
+        fees_destination: marketplace.fees_destination,
+        fee_timelock: marketplace.fee_timelock,
+        pending_fees: marketplace.pending_fees,
+        paused: marketplace.paused,
+        pause_reason: marketplace.pause_reason,
+        referral_fee: marketplace.referral_fee,
+        taker_fees: marketplace.taker_fees,
+        pending_taker_fees: marketplace.pending_taker_fees,
+        fee_discount: marketplace.fee_discount,
+    });
+}
+
+fn emit_collection_updated(collection: &Account<Collection>) {
+    emit!(CollectionUpdated {
+        collection: collection.key(),
+        marketplace: collection.marketplace_key,
+        symbol: collection.symbol.clone(),
+        required_verifier: collection.required_verifier,
+        fees: collection.fees,
+        pending_fees: collection.pending_fees,
+        ignore_creator_fee: collection.ignore_creator_fee,
+        paused: collection.paused,
+        pause_reason: collection.pause_reason,
+        taker_fees: collection.taker_fees,
+        pending_taker_fees: collection.pending_taker_fees,
+        royalty_policy: collection.royalty_policy,
+    });
+}
+
+fn emit_payment_mint_updated(payment_mint: &Account<PaymentMint>, removed: bool) {
+    emit!(PaymentMintUpdated {
+        payment_mint: payment_mint.key(),
+        marketplace: payment_mint.marketplace,
+        mint: payment_mint.mint,
+        fees_destination: payment_mint.fees_destination,
+        fee_splits: payment_mint.fee_splits.clone(),
+        fee_tiers: payment_mint.fee_tiers.clone(),
+        removed,
+    });
+}
+
+fn emit_admin_proposal_updated(proposal: &Account<AdminProposal>, cancelled: bool) {
+    emit!(AdminProposalUpdated {
+        proposal: proposal.key(),
+        multisig: proposal.multisig,
+        proposer: proposal.proposer,
+        action: proposal.action.clone(),
+        approved_by: proposal.approved_by.clone(),
+        expires_at: proposal.expires_at,
+        cancelled,
+    });
+}
+
+fn emit_offer_cancelled(buy_offer: &Account<BuyOffer>, expired: bool) {
+    emit!(OfferCancelled {
+        offer: buy_offer.key(),
+        marketplace: buy_offer.marketplace,
+        mint: buy_offer.mint,
+        buyer: buy_offer.authority,
+        price: buy_offer.proposed_price,
+        expired,
+    });
+}
+
+// Splits the marketplace share between the fee splits, the first destination receives the rounding dust
+fn get_fee_payments<'info>(
+    fee_splits: &[FeeSplit],
+    marketplace_dest_account: AccountInfo<'info>,
+    split_accounts: &[AccountInfo<'info>],
+    marketplace_share: u64,
+) -> Result<Vec<(AccountInfo<'info>, u64)>> {
+    if fee_splits.is_empty() {
+        return Ok(vec![(marketplace_dest_account, marketplace_share)]);
+    }
+    if split_accounts.len() != fee_splits.len() - 1 {
+        return Err(error!(ErrorCode::ErrInvalidFeeSplitAccounts));
+    }
+
+    let mut fee_payments = vec![(marketplace_dest_account, 0)];
+    let mut split_total: u64 = 0;
+    for (fee_split, split_account) in fee_splits[1..].iter().zip(split_accounts.iter()) {
+        if split_account.key() != fee_split.destination {
+            return Err(error!(ErrorCode::ErrInvalidFeeSplitAccounts));
+        }
+        let split_share = calculate_fee(marketplace_share, fee_split.share, 10000);
+        split_total = split_total.checked_add(split_share).unwrap();
+        fee_payments.push((split_account.clone(), split_share));
+    }
+    fee_payments[0].1 = marketplace_share.checked_sub(split_total).unwrap();
+    Ok(fee_payments)
+}
+
+// A referral is passed as the ReferrerStats account followed by its destination, right after the fee split destinations
+fn get_referral<'c, 'info>(
+    marketplace_key: Pubkey,
+    payment_mint: Pubkey,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    start: usize,
+    traders: &[Option<Pubkey>],
+) -> Result<Option<(Account<'info, ReferrerStats>, &'c AccountInfo<'info>)>> {
+    let referrer_stats = match remaining_accounts.get(start).map(Account::<'info, ReferrerStats>::try_from) {
+        Some(Ok(referrer_stats)) => referrer_stats,
+        _ => return Ok(None),
+    };
+    let referral_dest_account = remaining_accounts.get(start + 1)
+        .ok_or_else(|| error!(ErrorCode::ErrInvalidReferral))?;
+    if referrer_stats.marketplace != marketplace_key
+        || referrer_stats.payment_mint != payment_mint
+        || referral_dest_account.key() != referrer_stats.destination {
+        return Err(error!(ErrorCode::ErrInvalidReferral));
+    }
+    if traders.contains(&Some(referrer_stats.referrer)) {
+        return Err(error!(ErrorCode::ErrSelfReferral));
+    }
+    Ok(Some((referrer_stats, referral_dest_account)))
+}
+
+// The taker passes its TraderStats right after the referral accounts to get the fee of its volume tier
+fn get_trader_stats<'info>(
+    marketplace_key: Pubkey,
+    payment_mint: Pubkey,
+    trader: Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    start: usize,
+) -> Result<Option<Account<'info, TraderStats>>> {
+    let trader_stats = match remaining_accounts.get(start).map(Account::<'info, TraderStats>::try_from) {
+        Some(Ok(trader_stats)) => trader_stats,
+        _ => return Ok(None),
+    };
+    if trader_stats.marketplace != marketplace_key
+        || trader_stats.payment_mint != payment_mint
+        || trader_stats.trader != trader {
+        return Err(error!(ErrorCode::ErrInvalidTraderStats));
+    }
+    Ok(Some(trader_stats))
+}
+
+// The buyer proves it holds the pass with its token account, followed by the metadata and the collection for a
+// collection pass, right after the trader stats
+fn get_discount_fees(
+    fee_discount: Option<FeeDiscount>,
+    holder: Pubkey,
+    remaining_accounts: &[AccountInfo],
+    start: usize,
+) -> Result<Option<u16>> {
+    let fee_discount = match fee_discount {
+        Some(fee_discount) => fee_discount,
+        None => return Ok(None),
+    };
+    let pass_account = match remaining_accounts.get(start).map(Account::<TokenAccount>::try_from) {
+        Some(Ok(pass_account)) => pass_account,
+        _ => return Ok(None),
+    };
+    if pass_account.owner != holder || pass_account.amount == 0 {
+        return Err(error!(ErrorCode::ErrInvalidFeePass));
+    }
+    match fee_discount.pass {
+        FeePass::Mint { mint } => {
+            if pass_account.mint != mint {
+                return Err(error!(ErrorCode::ErrInvalidFeePass));
+            }
+        }
+        FeePass::Collection { collection } => {
+            let (metadata, pass_collection) = match remaining_accounts.get(start + 1..start + 3) {
+                Some([metadata, pass_collection]) if pass_collection.key() == collection => (metadata, pass_collection),
+                _ => return Err(error!(ErrorCode::ErrInvalidFeePass)),
+            };
+            let pass_collection = Account::<Collection>::try_from(pass_collection)?;
+            verify_metadata_and_derivation(metadata, &pass_account.mint, &pass_collection)?;
+        }
+    }
+    Ok(Some(fee_discount.fees))
+}
+
+// Fee of the highest tier reached by the volume, tiers only lower the fee
+fn get_tier_fees(fee_tiers: &[FeeTier], volume: u64, fees: u16) -> u16 {
+    return match fee_tiers.iter().rev().find(|fee_tier| volume >= fee_tier.volume) {
+        Some(fee_tier) => fee_tier.fees.min(fees),
+        None => fees,
+    };
 }
 
+// Fee split destinations after the first one are passed right after the creators in the remaining accounts
+fn get_split_accounts<'c, 'info>(
+    fee_splits: &[FeeSplit],
+    remaining_accounts: &'c [AccountInfo<'info>],
+    start: usize,
+) -> Result<&'c [AccountInfo<'info>]> {
+    let count = fee_splits.len().saturating_sub(1);
+    remaining_accounts.get(start..start + count).ok_or_else(|| error!(ErrorCode::ErrInvalidFeeSplitAccounts))
+}
+
+fn get_fill_fees(total_amount: u64, maker_fee: i16, taker_fee: u16) -> FillFees {
+    let taker_share = calculate_fee(total_amount, taker_fee, 10000);
+    let maker_share_abs = calculate_fee(total_amount, maker_fee.unsigned_abs(), 10000);
+    if maker_fee >= 0 {
+        return FillFees { maker_share: maker_share_abs, taker_share, maker_rebate: 0 };
+    }
+    FillFees { maker_share: 0, taker_share, maker_rebate: maker_share_abs.min(taker_share) }
+}
+
+fn validate_fees(fees: i16, min_fees: i16) -> Result<()> {
+    if fees > 10000 {
+        return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+    }
+    if fees < min_fees {
+        return Err(error!(ErrorCode::ErrFeeBelowMinimum));
+    }
+    Ok(())
+}
+
+// A rebate only gives back part of the taker fee, so only a positive maker fee adds up with the taker fee
+fn validate_total_fees(maker_fees: i16, taker_fees: u16) -> Result<()> {
+    if maker_fees.max(0) as u32 + taker_fees as u32 > 10000 {
+        return Err(error!(ErrorCode::ErrFeeShouldLowerOrEqualThan10000));
+    }
+    Ok(())
+}
 
 fn calculate_fee(amount: u64, fee_share: u16, basis: u64) -> u64 {
     let fee = amount
@@ -924,27 +6077,30 @@ fn calculate_fee(amount: u64, fee_share: u16, basis: u64) -> u64 {
     return fee;
 }
 
-fn verify_and_get_creators<'a, 'b, 'c, 'info>(creators: Vec<Creator>, remaining_accounts: &'c [AccountInfo<'info>], marketplace_mint: Pubkey) -> Vec<(&'c AccountInfo<'info>, u8)> {
-    let is_native = marketplace_mint == spl_token::native_mint::id();
+fn verify_and_get_creators<'c, 'info>(
+    creators: Vec<Creator>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    marketplace_key: Pubkey,
+    payment_mint: Pubkey,
+) -> Result<Vec<(&'c AccountInfo<'info>, u8, Pubkey, u8)>> {
     let mut creators_distributions = Vec::new();
     for i in 0..creators.len() {
-        let remaining_account_creator = &remaining_accounts[i];
-        if is_native {
-            assert_eq!(remaining_account_creator.key(), creators[i].address);
-            creators_distributions.push((remaining_account_creator, creators[i].share));
-        } else {
-            let ata_seeds: &[&[u8]] = &[
+        let remaining_account_creator = remaining_accounts.get(i)
+            .ok_or(error!(ErrorCode::ErrInvalidRoyaltyVault))?;
+        let (royalty_vault_key, bump) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                marketplace_key.as_ref(),
+                payment_mint.as_ref(),
                 creators[i].address.as_ref(),
-                spl_token::ID.as_ref(),
-                marketplace_mint.as_ref(),
-            ];
-            let atp = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM).unwrap();
-            let creator_associated_token_addr = Pubkey::find_program_address(&ata_seeds, &atp);
-            assert_eq!(remaining_account_creator.key(), creator_associated_token_addr.0);
-            creators_distributions.push((remaining_account_creator, creators[i].share));
-        }
+                ROYALTY_VAULT.as_bytes(),
+            ],
+            &crate::ID,
+        );
+        require!(remaining_account_creator.key() == royalty_vault_key, ErrorCode::ErrInvalidRoyaltyVault);
+        creators_distributions.push((remaining_account_creator, creators[i].share, creators[i].address, bump));
     }
-    return creators_distributions;
+    return Ok(creators_distributions);
 }
 
 
@@ -963,5 +6119,109 @@ pub enum ErrorCode {
     #[msg("Derived key invalid")]
     DerivedKeyInvalid,
     #[msg("AccountNotInitialized")]
-    NotInitialized
+    NotInitialized,
+    #[msg("Expiry should be in the future")]
+    ErrExpiryShouldBeInTheFuture,
+    #[msg("Sell order is not expired")]
+    ErrSellOrderNotExpired,
+    #[msg("Buy offer is not expired")]
+    ErrBuyOfferNotExpired,
+    #[msg("Buy offer is expired")]
+    ErrBuyOfferExpired,
+    #[msg("Quantity should be greater than 0")]
+    ErrQuantityShouldBeGreaterThanZero,
+    #[msg("nft not part of trait offer")]
+    ErrNftNotPartOfTraitOffer,
+    #[msg("Auction has ended")]
+    ErrAuctionEnded,
+    #[msg("Auction has not ended")]
+    ErrAuctionNotEnded,
+    #[msg("Bid is too low")]
+    ErrBidTooLow,
+    #[msg("Previous bidder account does not match the highest bidder")]
+    ErrInvalidPreviousBidderAccount,
+    #[msg("Dutch auction should start above the floor price and end after it starts")]
+    ErrInvalidDutchAuction,
+    #[msg("Sell order unit price is above the maximum unit price")]
+    ErrUnitPriceAboveMaximum,
+    #[msg("Total price is above the maximum total price")]
+    ErrTotalPriceAboveMaximum,
+    #[msg("Legacy sell order is invalid")]
+    ErrInvalidLegacySellOrder,
+    #[msg("Buy offer does not belong to the reconciled escrow or is duplicated")]
+    ErrInvalidBuyOfferForReconciliation,
+    #[msg("Escrow balance does not match open buy offers")]
+    ErrEscrowOutOfBalance,
+    #[msg("Refund account does not belong to the buyer")]
+    ErrInvalidRefundAccount,
+    #[msg("Native mint is not supported for this instruction")]
+    ErrNativeMintNotSupported,
+    #[msg("The marketplace mint cannot be removed from the payment mints")]
+    ErrCannotRemovePrimaryPaymentMint,
+    #[msg("Invalid legacy buy offer")]
+    ErrInvalidLegacyBuyOffer,
+    #[msg("Signer is not the pending authority")]
+    ErrNotPendingAuthority,
+    #[msg("Multisig needs 1 to 10 distinct signers and a threshold between 1 and the number of signers")]
+    ErrInvalidMultisig,
+    #[msg("Signer is not part of the multisig")]
+    ErrNotMultisigSigner,
+    #[msg("Proposal already approved by this signer")]
+    ErrProposalAlreadyApproved,
+    #[msg("Proposal does not have enough approvals")]
+    ErrNotEnoughApprovals,
+    #[msg("Proposal action does not match this instruction")]
+    ErrInvalidProposalAction,
+    #[msg("Fee timelock can only be raised, up to 30 days")]
+    ErrInvalidFeeTimelock,
+    #[msg("Marketplace is paused")]
+    ErrMarketplacePaused,
+    #[msg("Collection is paused")]
+    ErrCollectionPaused,
+    #[msg("Signer is neither the marketplace authority nor its guardian")]
+    ErrNotAuthorityOrGuardian,
+    #[msg("Fee splits should have at most 5 unique destinations with non zero shares summing to 10000")]
+    ErrInvalidFeeSplits,
+    #[msg("Fee split destinations do not match the payment mint fee splits")]
+    ErrInvalidFeeSplitAccounts,
+    #[msg("Referrer stats do not belong to this marketplace and payment mint or the destination does not match")]
+    ErrInvalidReferral,
+    #[msg("Fee is below the minimum allowed")]
+    ErrFeeBelowMinimum,
+    #[msg("Maker rebate account should belong to the buyer and hold the payment mint")]
+    ErrInvalidMakerRebateAccount,
+    #[msg("Fee tiers should be sorted by volume, at most 5 and lower or equal than 10000")]
+    ErrInvalidFeeTiers,
+    #[msg("Trader stats do not belong to the taker of this marketplace and payment mint")]
+    ErrInvalidTraderStats,
+    #[msg("Fee pass account should be a token account of the pass held by the buyer")]
+    ErrInvalidFeePass,
+    #[msg("Auction reserve price and minimum bid increment should be greater than zero")]
+    ErrInvalidAuction,
+    #[msg("Auction already has bids")]
+    ErrAuctionHasBids,
+    #[msg("Account is not a legacy marketplace of this authority and mint")]
+    ErrInvalidLegacyMarketplace,
+    #[msg("Account is not a legacy collection of this marketplace")]
+    ErrInvalidLegacyCollection,
+    #[msg("Admin proposal expired")]
+    ErrProposalExpired,
+    #[msg("Referrer can't be the buyer or the seller of the fill")]
+    ErrSelfReferral,
+    #[msg("Fees and royalties can't exceed the price")]
+    ErrFeesExceedPrice,
+    #[msg("Neither the marketplace nor the signer of the fill hold enough lamports to fund the royalty vault")]
+    ErrRoyaltyVaultUnfunded,
+    #[msg("The payment mint still has open sell orders or auctions")]
+    ErrPaymentMintInUse,
+    #[msg("Only the proposer can cancel a proposal before it expires")]
+    ErrNotProposer,
+    #[msg("Sell order does not belong to this marketplace, nft mint and payment mint")]
+    ErrInvalidSellOrder,
+    #[msg("Vault does not belong to the sell order")]
+    ErrInvalidSellOrderVault,
+    #[msg("Seller account does not match the sell order destination")]
+    ErrInvalidSellerDestination,
+    #[msg("Royalty vault does not belong to the creator, marketplace and payment mint")]
+    ErrInvalidRoyaltyVault,
 }
\ No newline at end of file
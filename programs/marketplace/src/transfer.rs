@@ -1,6 +1,7 @@
 use anchor_lang::prelude::{AccountInfo, CpiContext, Result};
+use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_spl::token;
-use anchor_spl::token::Transfer;
+use anchor_spl::token::{CloseAccount, Transfer};
 
 
 pub fn pay<'info>(
@@ -34,4 +35,46 @@ pub fn pay_with_signer<'info>(
     };
     let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
     token::transfer(cpi_ctx, amount)
-}
\ No newline at end of file
+}
+
+pub fn close_with_signer<'info>(
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]]
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    token::close_account(cpi_ctx)
+}
+
+pub fn pay_native<'info>(
+    payer: AccountInfo<'info>,
+    dest: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    invoke(
+        &system_instruction::transfer(payer.key, dest.key, amount),
+        &[payer, dest, system_program],
+    )?;
+    Ok(())
+}
+
+//The escrow is owned by the program so lamports can be moved without a system transfer
+pub fn pay_native_from_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let escrow_lamports = escrow.lamports().checked_sub(amount).unwrap();
+    let dest_lamports = dest.lamports().checked_add(amount).unwrap();
+    **escrow.try_borrow_mut_lamports()? = escrow_lamports;
+    **dest.try_borrow_mut_lamports()? = dest_lamports;
+    Ok(())
+}
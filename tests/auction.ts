@@ -0,0 +1,239 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, fundRoyaltyVaults, nextEvent } from "./utils/utils";
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getAuctionPDA, getAuctionVaultPDA, getCollectionPDA, getEscrowPDA, getMarketplacePDA, getPaymentMintPDA, getRoyaltyEscrowPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+
+describe('auctions', () => {
+    let admin: web3.Keypair;
+    let adminTokenAccount: splToken.AccountInfo;
+    let bidder: web3.Keypair;
+    let bidderTokenAccount: splToken.AccountInfo;
+    let otherBidder: web3.Keypair;
+    let otherBidderTokenAccount: splToken.AccountInfo;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let sellerNftAccount: web3.PublicKey;
+    let marketplacePDA: web3.PublicKey;
+    let marketplaceMint: splToken.Token;
+    let escrowPDA: web3.PublicKey;
+    let collectionPDA: web3.PublicKey;
+    let collection: Collection;
+    let nftMint: splToken.Token;
+    let auctionPDA: web3.PublicKey;
+    let fee = 200;
+    let collectionName = "AURY"
+
+    let now = () => new anchor.BN(Math.floor(Date.now() / 1000))
+
+    let setCollectionPaused = (paused: boolean) => program.methods.setCollectionPaused(paused, 1).accounts({
+        signer: admin.publicKey,
+        marketplace: marketplacePDA,
+        collection: collectionPDA,
+    }).signers([admin]).rpc()
+
+    it('Prepare tests variables', async () => {
+        admin = anchor.web3.Keypair.generate()
+        creator = anchor.web3.Keypair.generate()
+        seller = anchor.web3.Keypair.generate()
+        bidder = anchor.web3.Keypair.generate()
+        otherBidder = anchor.web3.Keypair.generate()
+        for (let wallet of [admin, creator, seller, bidder, otherBidder]) {
+            let fromAirdropSignature = await provider.connection.requestAirdrop(
+                wallet.publicKey,
+                anchor.web3.LAMPORTS_PER_SOL,
+            );
+            await provider.connection.confirmTransaction(fromAirdropSignature);
+        }
+
+        marketplacePDA = await getMarketplacePDA(admin.publicKey)
+        marketplaceMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            splToken.TOKEN_PROGRAM_ID,
+        );
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey);
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+        bidderTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(bidder.publicKey);
+        otherBidderTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(otherBidder.publicKey);
+        await marketplaceMint.mintTo(bidderTokenAccount.address, admin, [], 1000);
+        await marketplaceMint.mintTo(otherBidderTokenAccount.address, admin, [], 1000);
+
+        escrowPDA = await getEscrowPDA(marketplacePDA, marketplaceMint.publicKey)
+        collectionPDA = await getCollectionPDA(marketplacePDA, collectionName)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(
+            creator.publicKey,
+            seller.publicKey,
+            lamports,
+            nft_data(creator.publicKey),
+            nft_json_url,
+        );
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, admin)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        auctionPDA = await getAuctionPDA(marketplacePDA, seller.publicKey, nftMint.publicKey)
+
+        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey, new anchor.BN(0)).accounts(
+            {
+                payer: admin.publicKey,
+                marketplace: marketplacePDA,
+                mint: marketplaceMint.publicKey,
+                escrow: escrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+                paymentMint: await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc();
+
+        // fills pay the rent of new royalty vaults from the marketplace lamports
+        await fundRoyaltyVaults(provider.connection, admin, marketplacePDA)
+
+        await program.methods.createCollection(collectionName, creator.publicKey, null, false, { full: {} }).accounts(
+            {
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+                collection: collectionPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc()
+
+        collection = new Collection(provider, marketplacePDA, collectionPDA)
+    });
+
+    it('reject an auction without reserve price or bid increment', async () => {
+        let endTime = now().addn(3600)
+        await assert.rejects(collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(0), new anchor.BN(10), endTime, seller,
+        ))
+        await assert.rejects(collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(100), new anchor.BN(0), endTime, seller,
+        ))
+    });
+
+    it('cancel an auction without bids', async () => {
+        let endTime = now().addn(3600)
+        let createdEvent = nextEvent(program, "AuctionCreated")
+        await collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(100), new anchor.BN(10), endTime, seller,
+        )
+        let sellerNft = await nftMint.getAccountInfo(sellerNftAccount)
+        assert.equal(sellerNft.amount.toNumber(), 4);
+
+        let event = await createdEvent
+        assert.equal(event.auction.toString(), auctionPDA.toString());
+        assert.equal(event.mint.toString(), nftMint.publicKey.toString());
+        assert.equal(event.seller.toString(), seller.publicKey.toString());
+        assert.equal(event.paymentMint.toString(), marketplaceMint.publicKey.toString());
+        assert.equal(event.reservePrice.toNumber(), 100);
+        assert.equal(event.endTime.toNumber(), endTime.toNumber());
+
+        let cancelledEvent = nextEvent(program, "AuctionCancelled")
+        await collection.cancelAuction(auctionPDA, sellerNftAccount, seller)
+        event = await cancelledEvent
+        assert.equal(event.auction.toString(), auctionPDA.toString());
+        assert.equal(event.seller.toString(), seller.publicKey.toString());
+
+        sellerNft = await nftMint.getAccountInfo(sellerNftAccount)
+        assert.equal(sellerNft.amount.toNumber(), 5);
+        assert.equal(await provider.connection.getAccountInfo(auctionPDA), null);
+        assert.equal(await provider.connection.getAccountInfo(await getAuctionVaultPDA(marketplacePDA, auctionPDA)), null);
+    });
+
+    it('settle an auction without bids', async () => {
+        await collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(100), new anchor.BN(10), now().addn(2), seller,
+        )
+
+        await assert.rejects(collection.settleAuction(auctionPDA, seller))
+        await new Promise(resolve => setTimeout(resolve, 4000))
+
+        // ended auctions settle even while the collection is paused
+        await setCollectionPaused(true)
+        let settledEvent = nextEvent(program, "AuctionSettled")
+        await collection.settleAuction(auctionPDA, seller)
+        await setCollectionPaused(false)
+
+        let event = await settledEvent
+        assert.equal(event.auction.toString(), auctionPDA.toString());
+        assert.equal(event.highestBidder.toString(), seller.publicKey.toString());
+        assert.equal(event.highestBid.toNumber(), 0);
+        assert.equal(event.sellerShare.toNumber(), 0);
+
+        // the nft goes back to the seller and nothing is paid
+        let sellerNft = await nftMint.getAccountInfo(sellerNftAccount)
+        assert.equal(sellerNft.amount.toNumber(), 5);
+        let updatedSellerAccount = await marketplaceMint.getAccountInfo(sellerTokenAccount.address)
+        assert.equal(updatedSellerAccount.amount.toNumber(), 0);
+        assert.equal(await provider.connection.getAccountInfo(auctionPDA), null);
+    });
+
+    it('place and outbid bids', async () => {
+        await collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(100), new anchor.BN(10), now().addn(3600), seller,
+        )
+
+        await assert.rejects(collection.placeBid(auctionPDA, new anchor.BN(99), bidderTokenAccount.address, bidder))
+        let bidEvent = nextEvent(program, "BidPlaced")
+        await collection.placeBid(auctionPDA, new anchor.BN(100), bidderTokenAccount.address, bidder)
+
+        let event = await bidEvent
+        assert.equal(event.auction.toString(), auctionPDA.toString());
+        assert.equal(event.bidder.toString(), bidder.publicKey.toString());
+        assert.equal(event.amount.toNumber(), 100);
+
+        // the 2% maker fee is escrowed on top of the bid
+        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 102);
+
+        // a bid has to beat the highest bid by the minimum increment
+        await assert.rejects(collection.placeBid(auctionPDA, new anchor.BN(109), otherBidderTokenAccount.address, otherBidder))
+        await collection.placeBid(auctionPDA, new anchor.BN(110), otherBidderTokenAccount.address, otherBidder)
+
+        let auction = await program.account.auction.fetch(auctionPDA)
+        assert.equal(auction.highestBidder.toString(), otherBidder.publicKey.toString());
+        assert.equal(auction.highestBid.toNumber(), 110);
+        assert.equal(auction.highestBidMakerFee.toNumber(), 2);
+
+        let updatedBidderAccount = await marketplaceMint.getAccountInfo(bidderTokenAccount.address)
+        assert.equal(updatedBidderAccount.amount.toNumber(), 1000);
+        escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 112);
+
+        // the seller can't pull the nft once somebody bid
+        await assert.rejects(collection.cancelAuction(auctionPDA, sellerNftAccount, seller))
+    });
+
+    it('reject bids while the collection is paused', async () => {
+        await setCollectionPaused(true)
+        await assert.rejects(collection.placeBid(auctionPDA, new anchor.BN(200), bidderTokenAccount.address, bidder))
+        await setCollectionPaused(false)
+
+        await collection.placeBid(auctionPDA, new anchor.BN(200), bidderTokenAccount.address, bidder)
+        let auction = await program.account.auction.fetch(auctionPDA)
+        assert.equal(auction.highestBid.toNumber(), 200);
+    });
+})
+    ;
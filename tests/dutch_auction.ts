@@ -0,0 +1,145 @@
+import * as anchor from '@project-serum/anchor';
+import { web3 } from '@project-serum/anchor';
+import { NATIVE_MINT, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getCollectionPDA, getSellOrderPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const airdrop = async (to: web3.PublicKey) => {
+    let signature = await provider.connection.requestAirdrop(to, 2 * web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature);
+}
+
+// prices are computed with the cluster clock, which can drift from the local one
+const clusterTime = async (): Promise<number> => {
+    return await provider.connection.getBlockTime(await provider.connection.getSlot())
+}
+
+describe('dutch auction sell orders', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let nftMint: Token;
+    let sellerNftAssociatedTokenAccount: web3.PublicKey;
+    let buyerNftATA: web3.PublicKey;
+
+    let collection: Collection;
+
+    let startPrice = new anchor.BN(2_000_000)
+    let floorPrice = new anchor.BN(1_000_000)
+
+    // lists one nft with its price dropping from startPrice to floorPrice between startTime and endTime
+    const listAndBuy = async (nonce: number, startTime: number, endTime: number): Promise<number> => {
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAssociatedTokenAccount,
+            seller.publicKey,
+            new anchor.BN(nonce),
+            floorPrice,
+            new anchor.BN(1),
+            seller,
+            null,
+            { startPrice: startPrice, startTime: new anchor.BN(startTime), endTime: new anchor.BN(endTime) },
+        )
+
+        // no fees nor royalties so the seller receives the price the buyer paid
+        let sellerBalanceBefore = await provider.connection.getBalance(seller.publicKey)
+        await collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(nonce))],
+            buyerNftATA,
+            buyer.publicKey,
+            new anchor.BN(1),
+            startPrice,
+            buyer,
+        )
+        return await provider.connection.getBalance(seller.publicKey) - sellerBalanceBefore
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            await airdrop(account.publicKey)
+        }
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(
+            creator.publicKey,
+            seller.publicKey,
+            lamports,
+            nft_data(creator.publicKey),
+            nft_json_url
+        );
+        await provider.send(tx, [mint, creator]);
+
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAssociatedTokenAccount = (await nftMint.getOrCreateAssociatedAccountInfo(seller.publicKey)).address
+        buyerNftATA = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+
+        let marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, NATIVE_MINT, 0, admin.publicKey, new anchor.BN(0))
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", true)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('sells at the start price before the auction starts', async () => {
+        let now = await clusterTime()
+        let paid = await listAndBuy(0, now + 1000, now + 2000)
+        assert.equal(paid, startPrice.toNumber())
+    });
+
+    it('sells at the interpolated price midway', async () => {
+        let now = await clusterTime()
+        let paid = await listAndBuy(1, now - 1000, now + 1000)
+
+        // the price drops by 500 lamports a second, allow for the time the transactions took
+        let midPrice = (startPrice.toNumber() + floorPrice.toNumber()) / 2
+        assert.ok(paid <= midPrice, `${paid} should be at most ${midPrice}`)
+        assert.ok(paid >= midPrice - 500 * 30, `${paid} should be close to ${midPrice}`)
+    });
+
+    it('sells at the floor price once the auction ended', async () => {
+        let now = await clusterTime()
+        let paid = await listAndBuy(2, now - 2000, now - 1000)
+        assert.equal(paid, floorPrice.toNumber())
+    });
+
+    it('rejects a fill above the maximum unit price', async () => {
+        let now = await clusterTime()
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAssociatedTokenAccount,
+            seller.publicKey,
+            new anchor.BN(3),
+            floorPrice,
+            new anchor.BN(1),
+            seller,
+            null,
+            { startPrice: startPrice, startTime: new anchor.BN(now + 1000), endTime: new anchor.BN(now + 2000) },
+        )
+
+        await assert.rejects(collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(3))],
+            buyerNftATA,
+            buyer.publicKey,
+            new anchor.BN(1),
+            startPrice,
+            buyer,
+            floorPrice,
+        ))
+    });
+})
+    ;
@@ -0,0 +1,150 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getSellOrderPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('fee discount', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+    let buyerNftAccount: web3.PublicKey;
+    let passMint: Token;
+    let passNftMint: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 5% maker fee and 3% taker fee, pass holders get a 1% fee
+    let price = new anchor.BN(1000)
+    let royalties = 100
+    let makerShare = 50
+    let discountedShare = 10
+
+    const balance = async (account: web3.PublicKey): Promise<number> =>
+        (await marketplaceMint.getAccountInfo(account)).amount.toNumber()
+
+    const list = (nonce: number) => collection.sellAsset(
+        nftMint.publicKey,
+        sellerNftAccount,
+        sellerTokenAccount.address,
+        new anchor.BN(nonce),
+        price,
+        new anchor.BN(1),
+        seller,
+    )
+
+    const buyInstruction = async (nonce: number, feePassMint: web3.PublicKey) => collection.buyInstruction(
+        nftMint.publicKey,
+        [await getSellOrderPDA(sellerNftAccount, new anchor.BN(nonce))],
+        buyerNftAccount,
+        buyerTokenAccount.address,
+        new anchor.BN(1),
+        price.addn(discountedShare),
+        buyer.publicKey,
+        null,
+        null,
+        feePassMint,
+    )
+
+    const assertDiscountedBuy = async (nonce: number, feePassMint: web3.PublicKey) => {
+        let buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await provider.send(new web3.Transaction().add(await buyInstruction(nonce, feePassMint)), [buyer])
+
+        // the pass of the buyer only lowers its taker fee, the seller still pays the 5% maker fee
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1000 - discountedShare)
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties - makerShare)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + makerShare + discountedShare)
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        buyerNftAccount = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+
+        // the buyer holds both a pass mint token and an nft of the collection
+        passMint = await Token.createMint(provider.connection, admin, admin.publicKey, null, 0, TOKEN_PROGRAM_ID)
+        await passMint.mintTo(await passMint.createAssociatedTokenAccount(buyer.publicKey), admin, [], 1)
+        const [passNft, , passTx] = await createMint(creator.publicKey, buyer.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(passTx, [passNft, creator]);
+        passNftMint = passNft.publicKey
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await program.methods.updateMarketplace(null, null, null, 300).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplace.marketplacePDA,
+        }).signers([admin]).rpc()
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('reject the pass of another owner', async () => {
+        await marketplace.setFeeDiscount(admin, { pass: { mint: { mint: passMint.publicKey } }, fees: 100 })
+        await list(0)
+
+        // the seller holds a pass too, but the buyer can't use it
+        let sellerPassAccount = await passMint.createAssociatedTokenAccount(seller.publicKey)
+        await passMint.mintTo(sellerPassAccount, admin, [], 1)
+        let ix = await buyInstruction(0, passMint.publicKey)
+        let buyerPassAccount = await getAssociatedTokenAddress(buyer.publicKey, passMint.publicKey)
+        for (let key of ix.keys) {
+            if (key.pubkey.equals(buyerPassAccount)) {
+                key.pubkey = sellerPassAccount
+            }
+        }
+        await assert.rejects(provider.send(new web3.Transaction().add(ix), [buyer]))
+    });
+
+    it('discount the taker fee of mint pass holders', async () => {
+        await assertDiscountedBuy(0, passMint.publicKey)
+    });
+
+    it('discount the taker fee of collection pass holders', async () => {
+        await marketplace.setFeeDiscount(admin, { pass: { collection: { collection: collection.collectionPDA } }, fees: 100 })
+        await list(1)
+
+        await assertDiscountedBuy(1, passNftMint)
+    });
+});
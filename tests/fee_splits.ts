@@ -0,0 +1,137 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, nextEvent } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getPaymentMintPDA, getSellOrderPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('fee splits', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let partner: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let partnerTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 5% marketplace fee on a 1000 fill, split 70/30
+    let price = new anchor.BN(1000)
+    let adminShare = 35
+    let partnerShare = 15
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        partner = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        partnerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(partner.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('set fee splits', async () => {
+        // shares have to add up to the whole marketplace share
+        await assert.rejects(
+            marketplace.setFeeSplits(admin, marketplaceMint.publicKey, [
+                { destination: adminTokenAccount.address, share: 7000 },
+                { destination: partnerTokenAccount.address, share: 2000 },
+            ])
+        )
+        let updatedEvent = nextEvent(program, "PaymentMintUpdated")
+        await marketplace.setFeeSplits(admin, marketplaceMint.publicKey, [
+            { destination: adminTokenAccount.address, share: 7000 },
+            { destination: partnerTokenAccount.address, share: 3000 },
+        ])
+
+        let paymentMint = await program.account.paymentMint.fetch(await getPaymentMintPDA(marketplace.marketplacePDA, marketplaceMint.publicKey))
+        assert.equal(paymentMint.feeSplits.length, 2)
+        assert.equal(paymentMint.feesDestination.toString(), adminTokenAccount.address.toString())
+
+        let event = await updatedEvent
+        assert.equal(event.mint.toString(), marketplaceMint.publicKey.toString())
+        assert.equal(event.feeSplits.length, 2)
+        assert.equal(event.feeSplits[1].destination.toString(), partnerTokenAccount.address.toString())
+    });
+
+    it('split the fee of a buy', async () => {
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            new anchor.BN(0),
+            price,
+            new anchor.BN(1),
+            seller,
+        )
+        await collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAccount, new anchor.BN(0))],
+            await nftMint.createAssociatedTokenAccount(buyer.publicKey),
+            buyerTokenAccount.address,
+            new anchor.BN(1),
+            price,
+            buyer,
+        )
+
+        assert.equal((await marketplaceMint.getAccountInfo(adminTokenAccount.address)).amount.toNumber(), adminShare)
+        assert.equal((await marketplaceMint.getAccountInfo(partnerTokenAccount.address)).amount.toNumber(), partnerShare)
+    });
+
+    it('split the fee of a trait offer', async () => {
+        let traitMints = [nftMint.publicKey]
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(1), buyerTokenAccount.address, buyer)
+        await collection.executeTraitOffer(
+            await getTraitOfferPDA(marketplace.marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price),
+            traitMints,
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            seller,
+        )
+
+        assert.equal((await marketplaceMint.getAccountInfo(adminTokenAccount.address)).amount.toNumber(), 2 * adminShare)
+        assert.equal((await marketplaceMint.getAccountInfo(partnerTokenAccount.address)).amount.toNumber(), 2 * partnerShare)
+    });
+});
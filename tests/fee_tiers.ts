@@ -0,0 +1,177 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, nextEvent } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getSellOrderPDA, getTraderStatsPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('fee tiers', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+    let buyerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // no maker fee, a 3% taker fee lowered to 1% from no volume and to 0.5% from a 1000 volume
+    let price = new anchor.BN(1000)
+    let royalties = 100
+    let firstTierShare = 10
+    let secondTierShare = 5
+
+    const balance = async (account: web3.PublicKey): Promise<number> =>
+        (await marketplaceMint.getAccountInfo(account)).amount.toNumber()
+
+    const traderVolume = async (trader: web3.PublicKey): Promise<number> =>
+        (await program.account.traderStats.fetch(
+            await getTraderStatsPDA(marketplace.marketplacePDA, marketplaceMint.publicKey, trader)
+        )).volume.toNumber()
+
+    const listAndBuy = async (nonce: number) => {
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            new anchor.BN(nonce),
+            price,
+            new anchor.BN(1),
+            seller,
+        )
+        return collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAccount, new anchor.BN(nonce))],
+            buyerNftAccount,
+            buyerTokenAccount.address,
+            new anchor.BN(1),
+            price.addn(firstTierShare),
+            buyer,
+        )
+    }
+
+    const fillTraitOffer = async () => {
+        let traitMints = [nftMint.publicKey]
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(1), buyerTokenAccount.address, buyer)
+        return collection.executeTraitOffer(
+            await getTraitOfferPDA(marketplace.marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price),
+            traitMints,
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            seller,
+        )
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        buyerNftAccount = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 0, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await program.methods.updateMarketplace(null, null, null, 300).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplace.marketplacePDA,
+        }).signers([admin]).rpc()
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('set fee tiers', async () => {
+        // tiers have to be sorted by volume
+        await assert.rejects(marketplace.setFeeTiers(admin, marketplaceMint.publicKey, [
+            { volume: new anchor.BN(1000), fees: 50 },
+            { volume: new anchor.BN(0), fees: 100 },
+        ]))
+        let updatedEvent = nextEvent(program, "PaymentMintUpdated")
+        await marketplace.setFeeTiers(admin, marketplaceMint.publicKey, [
+            { volume: new anchor.BN(0), fees: 100 },
+            { volume: new anchor.BN(1000), fees: 50 },
+        ])
+        let event = await updatedEvent
+        assert.equal(event.feeTiers.length, 2)
+        assert.equal(event.feeTiers[1].volume.toNumber(), 1000)
+        assert.equal(event.feeTiers[1].fees, 50)
+        let createdEvent = nextEvent(program, "TraderStatsCreated")
+        await marketplace.createTraderStats(buyer, marketplaceMint.publicKey)
+        let traderStatsEvent = await createdEvent
+        assert.equal(traderStatsEvent.trader.toString(), buyer.publicKey.toString());
+        assert.equal(traderStatsEvent.paymentMint.toString(), marketplaceMint.publicKey.toString());
+        await marketplace.createTraderStats(seller, marketplaceMint.publicKey)
+    });
+
+    it('lower the taker fee of high volume buyers', async () => {
+        let buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await listAndBuy(0)
+
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1000 - firstTierShare)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + firstTierShare)
+        assert.equal(await traderVolume(buyer.publicKey), 1000)
+
+        buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        adminBalanceBefore = await balance(adminTokenAccount.address)
+        await listAndBuy(1)
+
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1000 - secondTierShare)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + secondTierShare)
+        assert.equal(await traderVolume(buyer.publicKey), 2000)
+    });
+
+    it('lower the taker fee of high volume sellers taking offers', async () => {
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await fillTraitOffer()
+
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties - firstTierShare)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + firstTierShare)
+        assert.equal(await traderVolume(seller.publicKey), 1000)
+
+        sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        adminBalanceBefore = await balance(adminTokenAccount.address)
+        await fillTraitOffer()
+
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties - secondTierShare)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + secondTierShare)
+        assert.equal(await traderVolume(seller.publicKey), 2000)
+    });
+});
@@ -0,0 +1,125 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { getCollectionPDA, getEscrowPDA, getMarketplacePDA, getPaymentMintPDA, getRoyaltyEscrowPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+describe('fee timelock', () => {
+    let admin: web3.Keypair;
+    let marketplacePDA: web3.PublicKey;
+    let collectionPDA: web3.PublicKey;
+
+    const updateMarketplace = (fees: number | null, feeTimelock: anchor.BN | null, takerFees: number | null = null) => {
+        return program.methods.updateMarketplace(fees, null, feeTimelock, takerFees).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+    }
+
+    const updateCollection = (fee: number) => {
+        return program.methods.updateCollection(fee, null, null, null, null, null).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+        }).signers([admin]).rpc()
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        let signature = await provider.connection.requestAirdrop(admin.publicKey, web3.LAMPORTS_PER_SOL)
+        await provider.connection.confirmTransaction(signature)
+
+        let marketplaceMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            TOKEN_PROGRAM_ID,
+        );
+        let adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+
+        marketplacePDA = await getMarketplacePDA(admin.publicKey)
+        await program.methods.createMarketplace(marketplaceMint.publicKey, 500, adminTokenAccount.address, admin.publicKey, new anchor.BN(600)).accounts({
+            payer: admin.publicKey,
+            marketplace: marketplacePDA,
+            mint: marketplaceMint.publicKey,
+            escrow: await getEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            paymentMint: await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey),
+            systemProgram: web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([admin]).rpc()
+
+        collectionPDA = await getCollectionPDA(marketplacePDA, "AURY")
+        await program.methods.createCollection("AURY", admin.publicKey, 300, false, { full: {} }).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([admin]).rpc()
+    });
+
+    it('fee increase is queued', async () => {
+        let createdMarketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(createdMarketplace.feeTimelock.toNumber(), 600);
+
+        await updateMarketplace(null, new anchor.BN(3600))
+        await assert.rejects(updateMarketplace(null, new anchor.BN(60)))
+
+        await updateMarketplace(1000, null)
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.fees, 500);
+        assert.equal(marketplace.feeTimelock.toNumber(), 3600);
+        assert.equal(marketplace.pendingFees.fees, 1000);
+    });
+
+    it('fee decrease applies immediately', async () => {
+        await updateMarketplace(100, null)
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.fees, 100);
+        assert.equal(marketplace.pendingFees, null);
+    });
+
+    it('taker fee increase is queued separately', async () => {
+        await updateMarketplace(null, null, 200)
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.takerFees, 0);
+        assert.equal(marketplace.pendingTakerFees.fees, 200);
+        assert.equal(marketplace.pendingFees, null);
+
+        // a negative maker fee is a rebate, it cannot go below -100%
+        await assert.rejects(updateMarketplace(-10001, null))
+        await updateMarketplace(-50, null)
+
+        marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.fees, -50);
+        assert.equal(marketplace.pendingTakerFees.fees, 200);
+    });
+
+    it('collection fee increase is queued', async () => {
+        await updateCollection(2000)
+
+        let collection = await program.account.collection.fetch(collectionPDA)
+        assert.equal(collection.fees, 300);
+        assert.equal(collection.pendingFees.fees, 2000);
+
+        await updateCollection(200)
+
+        collection = await program.account.collection.fetch(collectionPDA)
+        assert.equal(collection.fees, 200);
+        assert.equal(collection.pendingFees, null);
+    });
+});
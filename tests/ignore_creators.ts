@@ -98,7 +98,7 @@ describe('ignore creators tests', () => {
         sellerNftAssociatedTokenAccount = (await nftMint.getOrCreateAssociatedAccountInfo(seller.publicKey)).address
 
         marketplace = new Marketplace(provider)
-        await marketplace.createMarketplace(seller, marketplaceMint.publicKey, 5, sellerTokenAccount)
+        await marketplace.createMarketplace(seller, marketplaceMint.publicKey, 5, sellerTokenAccount, new anchor.BN(0))
 
         await marketplace.createCollection(seller, "AURY", creator.publicKey, "AURY", true)
 
@@ -111,6 +111,7 @@ describe('ignore creators tests', () => {
             nftMint.publicKey,
             sellerNftAssociatedTokenAccount,
             sellerTokenAccount,
+            new anchor.BN(0),
             new anchor.BN(2000),
             new anchor.BN(2),
             seller
@@ -162,11 +163,12 @@ describe('ignore creators tests', () => {
         await collection.buy(
             nftMint.publicKey,
             [
-                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(2000)),
+                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(0)),
             ],
             buyerNftATA,
             buyerTokenATA,
             new anchor.BN(2),
+            new anchor.BN(4000),
             buyer,
         )
 
@@ -0,0 +1,153 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getSellOrderPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('maker and taker fees', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 10% creator royalties on a 1000 fill
+    let price = new anchor.BN(1000)
+    let royalties = 100
+    let traitMints: web3.PublicKey[];
+
+    const balance = async (account: web3.PublicKey): Promise<number> =>
+        (await marketplaceMint.getAccountInfo(account)).amount.toNumber()
+
+    const updateMarketplace = (fees: number | null, takerFees: number | null) =>
+        program.methods.updateMarketplace(fees, null, null, takerFees).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplace.marketplacePDA,
+        }).signers([admin]).rpc()
+
+    const fillTraitOffer = async () => {
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(1), buyerTokenAccount.address, buyer)
+        await collection.executeTraitOffer(
+            await getTraitOfferPDA(marketplace.marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price),
+            traitMints,
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            seller,
+        )
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        traitMints = [nftMint.publicKey]
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('reject maker and taker fees above the price', async () => {
+        await assert.rejects(updateMarketplace(10000, 10000))
+        await assert.rejects(updateMarketplace(9800, 300))
+    });
+
+    it('charge the maker fee of an offer to the bidder', async () => {
+        let buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await fillTraitOffer()
+
+        // the 5% maker fee is escrowed on top of the price, the seller only pays royalties
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1050)
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + 50)
+    });
+
+    it('charge the taker fee to the buyer and rebate the maker', async () => {
+        // 1% maker rebate and 3% taker fee
+        await updateMarketplace(-100, 300)
+
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            new anchor.BN(0),
+            price,
+            new anchor.BN(1),
+            seller,
+        )
+        let buy = async (maxTotalPrice: number) => collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAccount, new anchor.BN(0))],
+            await getAssociatedTokenAddress(buyer.publicKey, nftMint.publicKey),
+            buyerTokenAccount.address,
+            new anchor.BN(1),
+            new anchor.BN(maxTotalPrice),
+            buyer,
+        )
+        // the taker fee counts toward the maximum total price
+        await assert.rejects(buy(1000))
+
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await buy(1030)
+
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties + 10)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + 30 - 10)
+    });
+
+    it('rebate the bidder of an offer', async () => {
+        let buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await fillTraitOffer()
+
+        // the seller takes the offer and pays the 3% taker fee, 1% of it goes back to the bidder
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1000 + 10)
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties - 30)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + 30 - 10)
+    });
+});
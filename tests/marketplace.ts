@@ -6,7 +6,8 @@ import { PublicKey } from "@solana/web3.js";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import assert from "assert";
 import { nft_data, nft_json_url } from "./data";
-import { createMint } from "./utils/utils";
+import { createMint, fundRoyaltyVaults, nextEvent } from "./utils/utils";
+import { getPaymentMintPDA, getRoyaltyEscrowPDA, getRoyaltyVaultPDA, getSellOrderPDA, getSellOrderVaultPDA } from "../js/getPDAs";
 
 let provider = anchor.Provider.env()
 anchor.setProvider(provider);
@@ -33,6 +34,7 @@ describe('marketplace with mint', () => {
     let programNftVaultPDA: PublicKey;
     let sellOrderPDA: PublicKey;
     let escrowPDA: PublicKey;
+    let paymentMintPDA: PublicKey;
 
     it('Prepare tests variables', async () => {
         // admin = anchor.web3.Keypair.generate()
@@ -96,6 +98,7 @@ describe('marketplace with mint', () => {
             ],
             program.programId,
         );
+        paymentMintPDA = await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey);
 
         adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(
             admin.publicKey,
@@ -141,27 +144,30 @@ describe('marketplace with mint', () => {
             seller.publicKey
         );
 
-        [programNftVaultPDA] = await anchor.web3.PublicKey.findProgramAddress(
-            [Buffer.from('MARKETPLACE'), Buffer.from("vault"), nftMint.publicKey.toBuffer()],
-            program.programId,
-        );
         [sellOrderPDA] = await anchor.web3.PublicKey.findProgramAddress(
             [
                 Buffer.from("MARKETPLACE"),
                 sellerNftAssociatedTokenAccount.toBuffer(),
-                Buffer.from("1000") //Sell order price
+                Buffer.from("0"), //Sell order nonce
+                Buffer.from("SELL_ORDER"),
             ],
             program.programId,
         );
+        [programNftVaultPDA] = await anchor.web3.PublicKey.findProgramAddress(
+            [Buffer.from('MARKETPLACE'), marketplacePDA.toBuffer(), sellOrderPDA.toBuffer(), Buffer.from("vault")],
+            program.programId,
+        );
     });
 
     it('create marketplace', async () => {
-        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey)
+        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey, new anchor.BN(0))
             .accounts({
                 payer: admin.publicKey,
                 marketplace: marketplacePDA,
                 mint: marketplaceMint.publicKey,
                 escrow: escrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+                paymentMint: paymentMintPDA,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -199,12 +205,14 @@ describe('marketplace with mint', () => {
             program.programId,
         );
         await assert.rejects(
-            program.methods.createMarketplace(marketplaceMint.publicKey, feeAbove100, tmpTokenAccount.address, tmpAuthority.publicKey).accounts(
+            program.methods.createMarketplace(marketplaceMint.publicKey, feeAbove100, tmpTokenAccount.address, tmpAuthority.publicKey, new anchor.BN(0)).accounts(
                 {
                     payer: tmpAuthority.publicKey,
                     marketplace: failedMarketplacePDA,
                     mint: marketplaceMint.publicKey,
                     escrow: escrowPDA,
+                    royaltyEscrow: await getRoyaltyEscrowPDA(failedMarketplacePDA, marketplaceMint.publicKey),
+                    paymentMint: await getPaymentMintPDA(failedMarketplacePDA, marketplaceMint.publicKey),
                     systemProgram: anchor.web3.SystemProgram.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -224,7 +232,7 @@ describe('marketplace with mint', () => {
             tmpAuthority.publicKey,
         );
 
-        await program.methods.updateMarketplace(tmpFee, tmpTokenAccount.address, tmpAuthority.publicKey).accounts(
+        await program.methods.updateMarketplace(tmpFee, tmpTokenAccount.address, null, null).accounts(
             {
                 authority: admin.publicKey,
                 marketplace: marketplacePDA,
@@ -232,15 +240,76 @@ describe('marketplace with mint', () => {
 
         let updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
         assert.equal(updatedMarketplace.fees.toString(), tmpFee.toString());
-        assert.equal(updatedMarketplace.authority.toString(), tmpAuthority.publicKey.toString());
         assert.equal(updatedMarketplace.feesDestination.toString(), tmpTokenAccount.address.toString());
 
         //revert
-        await program.methods.updateMarketplace(fee, adminTokenAccount.address, admin.publicKey).accounts(
+        await program.methods.updateMarketplace(fee, adminTokenAccount.address, null, null).accounts(
             {
-                authority: tmpAuthority.publicKey,
+                authority: admin.publicKey,
                 marketplace: marketplacePDA,
-            }).signers([tmpAuthority]).rpc();
+            }).signers([admin]).rpc();
+    });
+
+    it('transfer marketplace authority', async () => {
+        let tmpAuthority = anchor.web3.Keypair.generate()
+
+        await program.methods.proposeAuthority(tmpAuthority.publicKey).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+
+        let updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(updatedMarketplace.authority.toString(), admin.publicKey.toString());
+        assert.equal(updatedMarketplace.pendingAuthority.toString(), tmpAuthority.publicKey.toString());
+
+        // only the proposed key can accept
+        await assert.rejects(
+            program.methods.acceptAuthority().accounts({
+                newAuthority: admin.publicKey,
+                marketplace: marketplacePDA,
+            }).signers([admin]).rpc()
+        )
+
+        await program.methods.acceptAuthority().accounts({
+            newAuthority: tmpAuthority.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([tmpAuthority]).rpc()
+
+        updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(updatedMarketplace.authority.toString(), tmpAuthority.publicKey.toString());
+        assert.equal(updatedMarketplace.pendingAuthority, null);
+
+        //revert
+        await program.methods.proposeAuthority(admin.publicKey).accounts({
+            authority: tmpAuthority.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([tmpAuthority]).rpc()
+        await program.methods.acceptAuthority().accounts({
+            newAuthority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+    });
+
+    it('cancel marketplace authority transfer', async () => {
+        let tmpAuthority = anchor.web3.Keypair.generate()
+
+        await program.methods.proposeAuthority(tmpAuthority.publicKey).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+        await program.methods.cancelAuthorityTransfer().accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+
+        let updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(updatedMarketplace.pendingAuthority, null);
+        await assert.rejects(
+            program.methods.acceptAuthority().accounts({
+                newAuthority: tmpAuthority.publicKey,
+                marketplace: marketplacePDA,
+            }).signers([tmpAuthority]).rpc()
+        )
     });
 
     it('update marketplace mint', async () => {
@@ -273,6 +342,8 @@ describe('marketplace with mint', () => {
                 marketplace: marketplacePDA,
                 mint: newMarketplaceMint.publicKey,
                 escrow: newEscrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, newMarketplaceMint.publicKey),
+                paymentMint: await getPaymentMintPDA(marketplacePDA, newMarketplaceMint.publicKey),
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -280,6 +351,8 @@ describe('marketplace with mint', () => {
         let updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
         assert.equal(updatedMarketplace.feesDestination.toString(), newAdminTokenAccount.address.toString());
         assert.equal(updatedMarketplace.mint.toString(), newMarketplaceMint.publicKey.toString());
+        let newPaymentMint = await program.account.paymentMint.fetch(await getPaymentMintPDA(marketplacePDA, newMarketplaceMint.publicKey))
+        assert.equal(newPaymentMint.feesDestination.toString(), newAdminTokenAccount.address.toString());
 
         //revert
         await program.methods.updateMarketplaceMint(marketplaceMint.publicKey, adminTokenAccount.address).accounts({
@@ -287,14 +360,78 @@ describe('marketplace with mint', () => {
             marketplace: marketplacePDA,
             mint: marketplaceMint.publicKey,
             escrow: escrowPDA,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            paymentMint: paymentMintPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).signers([admin]).rpc()
+    });
+
+    it('add and remove payment mint', async () => {
+        let otherMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            splToken.TOKEN_PROGRAM_ID,
+        );
+        let otherAdminTokenAccount = await otherMint.getOrCreateAssociatedAccountInfo(admin.publicKey);
+        let otherPaymentMintPDA = await getPaymentMintPDA(marketplacePDA, otherMint.publicKey);
+        let [otherEscrowPDA] = await anchor.web3.PublicKey.findProgramAddress(
+            [
+                Buffer.from("MARKETPLACE"),
+                marketplacePDA.toBuffer(),
+                otherMint.publicKey.toBuffer(),
+                Buffer.from("ESCROW"),
+            ],
+            program.programId,
+        );
+
+        let addedEvent = nextEvent(program, "PaymentMintUpdated")
+        await program.methods.addPaymentMint(otherAdminTokenAccount.address).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+            mint: otherMint.publicKey,
+            escrow: otherEscrowPDA,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, otherMint.publicKey),
+            paymentMint: otherPaymentMintPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         }).signers([admin]).rpc()
+
+        let paymentMint = await program.account.paymentMint.fetch(otherPaymentMintPDA)
+        assert.equal(paymentMint.mint.toString(), otherMint.publicKey.toString());
+        assert.equal(paymentMint.feesDestination.toString(), otherAdminTokenAccount.address.toString());
+        let event = await addedEvent
+        assert.equal(event.paymentMint.toString(), otherPaymentMintPDA.toString());
+        assert.equal(event.mint.toString(), otherMint.publicKey.toString());
+        assert.equal(event.removed, false);
+
+        await assert.rejects(
+            program.methods.removePaymentMint().accounts({
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+                paymentMint: paymentMintPDA,
+            }).signers([admin]).rpc()
+        )
+
+        let removedEvent = nextEvent(program, "PaymentMintUpdated")
+        await program.methods.removePaymentMint().accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+            paymentMint: otherPaymentMintPDA,
+        }).signers([admin]).rpc()
+        assert.equal(await provider.connection.getAccountInfo(otherPaymentMintPDA), null);
+        event = await removedEvent
+        assert.equal(event.paymentMint.toString(), otherPaymentMintPDA.toString());
+        assert.equal(event.removed, true);
     });
 
     it('create collection', async () => {
-        await program.methods.createCollection(collectionName, creator.publicKey, collectionFee, false).accounts(
+        await program.methods.createCollection(collectionName, creator.publicKey, collectionFee, false, { full: {} }).accounts(
             {
                 authority: admin.publicKey,
                 marketplace: marketplacePDA,
@@ -321,7 +458,7 @@ describe('marketplace with mint', () => {
             program.programId,
         );
         await assert.rejects(
-            program.methods.createCollection(collectionName + "fail", creator.publicKey, feeAbove100, false).accounts({
+            program.methods.createCollection(collectionName + "fail", creator.publicKey, feeAbove100, false, { full: {} }).accounts({
                 authority: admin.publicKey,
                 marketplace: marketplacePDA,
                 collection: failcollectionPDA,
@@ -336,7 +473,7 @@ describe('marketplace with mint', () => {
         let tmpName = "some name"
         let tmpRequiredVerifier = anchor.web3.Keypair.generate().publicKey
 
-        await program.methods.updateCollection(tmpFee, tmpName, tmpRequiredVerifier, false).accounts({
+        await program.methods.updateCollection(tmpFee, tmpName, tmpRequiredVerifier, false, null, null).accounts({
             authority: admin.publicKey,
             marketplace: marketplacePDA,
             collection: collectionPDA,
@@ -349,7 +486,7 @@ describe('marketplace with mint', () => {
         assert.equal(updatedCollection.ignoreCreatorFee, false);
 
         // reset
-        await program.methods.updateCollection(collectionFee, collectionName, creator.publicKey, false).accounts({
+        await program.methods.updateCollection(collectionFee, collectionName, creator.publicKey, false, null, null).accounts({
             authority: admin.publicKey,
             marketplace: marketplacePDA,
             collection: collectionPDA,
@@ -361,12 +498,13 @@ describe('marketplace with mint', () => {
         let price = new anchor.BN(1000);
         let quantity = new anchor.BN(4);
 
-        await program.methods.createSellOrder(price, quantity, sellerTokenAccount.address).accounts(
+        await program.methods.createSellOrder(new anchor.BN(0), price, quantity, sellerTokenAccount.address, null, null).accounts(
             {
                 payer: seller.publicKey,
                 sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
                 marketplace: marketplacePDA,
                 collection: collectionPDA,
+                paymentMint: paymentMintPDA,
                 mint: nftMint.publicKey,
                 metadata: metadataPDA,
                 vault: programNftVaultPDA,
@@ -383,10 +521,29 @@ describe('marketplace with mint', () => {
         assert.equal(sellOrder.mint.toString(), nftMint.publicKey.toString());
         assert.equal(sellOrder.authority.toString(), seller.publicKey.toString());
         assert.equal(sellOrder.destination.toString(), sellerTokenAccount.address.toString());
+        assert.equal(sellOrder.paymentMint.toString(), marketplaceMint.publicKey.toString());
         let accountAfterSellOrderCreate = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
         assert.equal(accountAfterSellOrderCreate.amount, 1);
     });
 
+    it('update sell order price', async () => {
+        let newPrice = new anchor.BN(1200);
+
+        await program.methods.updateSellOrderPrice(newPrice).accounts({
+            authority: seller.publicKey,
+            sellOrder: sellOrderPDA,
+        }).signers([seller]).rpc()
+
+        let sellOrder = await program.account.sellOrder.fetch(sellOrderPDA)
+        assert.equal(sellOrder.price.toString(), newPrice.toString());
+
+        //revert
+        await program.methods.updateSellOrderPrice(new anchor.BN(1000)).accounts({
+            authority: seller.publicKey,
+            sellOrder: sellOrderPDA,
+        }).signers([seller]).rpc()
+    });
+
     it('remove one item from sell order', async () => {
         let quantity = new anchor.BN(1);
 
@@ -395,6 +552,7 @@ describe('marketplace with mint', () => {
             sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
             vault: programNftVaultPDA,
             sellOrder: sellOrderPDA,
+            paymentMint: paymentMintPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY
@@ -414,6 +572,10 @@ describe('marketplace with mint', () => {
             sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
             vault: programNftVaultPDA,
             sellOrder: sellOrderPDA,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+            paymentMint: paymentMintPDA,
+            metadata: metadataPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY
@@ -437,39 +599,198 @@ describe('marketplace with mint', () => {
         await marketplaceMint.mintTo(buyerMarketplaceAta.address, admin, [], 1000)
 
         let quantity_to_buy = new anchor.BN(1)
-        await program.methods.buy(quantity_to_buy).accounts({
+        let buyAccounts = {
             buyer: buyer.publicKey,
             buyerNftTokenAccount: buyerNftAta.address,
             buyerPayingTokenAccount: buyerMarketplaceAta.address,
             marketplace: marketplacePDA,
+            paymentMint: paymentMintPDA,
             marketplaceDestAccount: adminTokenAccount.address,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
             collection: collectionPDA,
             metadata: metadataPDA,
-            vault: programNftVaultPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
-        }).remainingAccounts([
-            { pubkey: creatorTokenAccount.address, isWritable: true, isSigner: false },
+        }
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplacePDA, marketplaceMint.publicKey, creator.publicKey)
+        let buyRemainingAccounts = [
+            { pubkey: royaltyVaultPDA, isWritable: true, isSigner: false },
             { pubkey: sellOrderPDA, isWritable: true, isSigner: false },
+            { pubkey: programNftVaultPDA, isWritable: true, isSigner: false },
             { pubkey: sellerTokenAccount.address, isWritable: true, isSigner: false },
-        ]).signers([buyer]).rpc()
+        ]
+
+        // the marketplace doesn't hold the lamports to create the royalty vault of the creator yet
+        await assert.rejects(
+            program.methods.buy(quantity_to_buy, new anchor.BN(1000), null, 0).accounts(buyAccounts)
+                .remainingAccounts(buyRemainingAccounts).signers([buyer]).rpc()
+        )
+        await fundRoyaltyVaults(provider.connection, admin, marketplacePDA)
+
+        await assert.rejects(
+            program.methods.buy(quantity_to_buy, new anchor.BN(999), null, 0).accounts(buyAccounts)
+                .remainingAccounts(buyRemainingAccounts).signers([buyer]).rpc()
+        )
+
+        // the seller account should be the sell order destination
+        await assert.rejects(
+            program.methods.buy(quantity_to_buy, new anchor.BN(1000), null, 0).accounts(buyAccounts)
+                .remainingAccounts([...buyRemainingAccounts.slice(0, 3),
+                    { pubkey: buyerMarketplaceAta.address, isWritable: true, isSigner: false }])
+                .signers([buyer]).rpc()
+        )
+        // the royalty vault should belong to the creator
+        await assert.rejects(
+            program.methods.buy(quantity_to_buy, new anchor.BN(1000), null, 0).accounts(buyAccounts)
+                .remainingAccounts([{ pubkey: marketplacePDA, isWritable: true, isSigner: false },
+                    ...buyRemainingAccounts.slice(1)])
+                .signers([buyer]).rpc()
+        )
+
+        let filledEvent = nextEvent(program, "SellOrderFilled")
+
+        let marketplaceLamportsBefore = await provider.connection.getBalance(marketplacePDA)
+        await program.methods.buy(quantity_to_buy, new anchor.BN(1000), null, 0).accounts(buyAccounts)
+            .remainingAccounts(buyRemainingAccounts).signers([buyer]).rpc()
+
+        // the marketplace paid the royalty vault rent, not the buyer
+        let royaltyVaultRent = await provider.connection.getMinimumBalanceForRentExemption(144)
+        assert.equal(await provider.connection.getBalance(marketplacePDA), marketplaceLamportsBefore - royaltyVaultRent);
 
         let sellOrder = await program.account.sellOrder.fetch(sellOrderPDA)
         assert.equal(sellOrder.quantity.toNumber(), 3);
 
+        let event = await filledEvent
+        assert.equal(event.sellOrder.toString(), sellOrderPDA.toString());
+        assert.equal(event.buyer.toString(), buyer.publicKey.toString());
+        assert.equal(event.quantity.toNumber(), 1);
+        assert.equal(event.unitPrice.toNumber(), 1000);
+        assert.equal(event.marketplaceShare.toNumber(), 50);
+        assert.equal(event.creatorShares[0].amount.toNumber(), 100);
+        assert.equal(event.sellerShare.toNumber(), 850);
+
         let updatedAdminTokenAccount = await marketplaceMint.getAccountInfo(adminTokenAccount.address)
         assert.equal(updatedAdminTokenAccount.amount.toNumber(), 50);
 
         let updatedSellerTokenAccount = await marketplaceMint.getAccountInfo(sellerTokenAccount.address)
         assert.equal(updatedSellerTokenAccount.amount.toNumber(), 850);
 
+        let royaltyVault = await program.account.royaltyVault.fetch(royaltyVaultPDA)
+        assert.equal(royaltyVault.creator.toString(), creator.publicKey.toString());
+        assert.equal(royaltyVault.amount.toNumber(), 100);
+        assert.equal(royaltyVault.rentPayer.toString(), marketplacePDA.toString());
+
+        marketplaceLamportsBefore = await provider.connection.getBalance(marketplacePDA)
+        await program.methods.claimRoyalties().accounts({
+            creator: creator.publicKey,
+            marketplace: marketplacePDA,
+            royaltyVault: royaltyVaultPDA,
+            rentPayer: marketplacePDA,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            destination: creatorTokenAccount.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).signers([creator]).rpc()
+
         let updatedCreatorTokenAccount = await marketplaceMint.getAccountInfo(creatorTokenAccount.address)
         assert.equal(updatedCreatorTokenAccount.amount.toNumber(), 100);
 
+        // claiming closes the vault and gives its rent back to the marketplace
+        assert.equal(await provider.connection.getAccountInfo(royaltyVaultPDA), null);
+        assert.equal(await provider.connection.getBalance(marketplacePDA), marketplaceLamportsBefore + royaltyVaultRent);
+
         let buyerNftAtaAfterSell = await nftMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
         assert.equal(buyerNftAtaAfterSell.amount.toNumber(), 1);
 
         let updatedBuyerTokenAccount = await marketplaceMint.getAccountInfo(buyerMarketplaceAta.address)
         assert.equal(updatedBuyerTokenAccount.amount.toNumber(), 0);
     });
+
+    it('pause trading', async () => {
+        let guardian = anchor.web3.Keypair.generate()
+        await program.methods.setGuardian(guardian.publicKey).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+        }).signers([admin]).rpc()
+
+        let setMarketplacePaused = (signer: anchor.web3.Keypair, paused: boolean, reason: number) =>
+            program.methods.setMarketplacePaused(paused, reason).accounts({
+                signer: signer.publicKey,
+                marketplace: marketplacePDA,
+            }).signers([signer]).rpc()
+
+        await assert.rejects(setMarketplacePaused(seller, true, 1))
+        await setMarketplacePaused(guardian, true, 1)
+
+        let updatedMarketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(updatedMarketplace.paused, true);
+        assert.equal(updatedMarketplace.pauseReason, 1);
+
+        let nonce = new anchor.BN(1)
+        let newSellOrderPDA = await getSellOrderPDA(sellerNftAssociatedTokenAccount, nonce)
+        let newVaultPDA = await getSellOrderVaultPDA(marketplacePDA, newSellOrderPDA)
+        let createSellOrder = () => program.methods.createSellOrder(nonce, new anchor.BN(1000), new anchor.BN(1), sellerTokenAccount.address, null, null).accounts(
+            {
+                payer: seller.publicKey,
+                sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
+                marketplace: marketplacePDA,
+                collection: collectionPDA,
+                paymentMint: paymentMintPDA,
+                mint: nftMint.publicKey,
+                metadata: metadataPDA,
+                vault: newVaultPDA,
+                sellOrder: newSellOrderPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }
+        ).signers([seller]).rpc()
+        await assert.rejects(createSellOrder())
+        let addQuantityToSellOrder = () => program.methods.addQuantityToSellOrder(new anchor.BN(1)).accounts({
+            authority: seller.publicKey,
+            sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
+            vault: programNftVaultPDA,
+            sellOrder: sellOrderPDA,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+            paymentMint: paymentMintPDA,
+            metadata: metadataPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY
+        }).signers([seller]).rpc()
+        await assert.rejects(addQuantityToSellOrder())
+
+        // withdrawals stay open while paused
+        await program.methods.removeSellOrder(new anchor.BN(1)).accounts({
+            authority: seller.publicKey,
+            sellerNftTokenAccount: sellerNftAssociatedTokenAccount,
+            vault: programNftVaultPDA,
+            sellOrder: sellOrderPDA,
+            paymentMint: paymentMintPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY
+        }).signers([seller]).rpc()
+        let sellOrder = await program.account.sellOrder.fetch(sellOrderPDA)
+        assert.equal(sellOrder.quantity.toNumber(), 2);
+
+        await setMarketplacePaused(admin, false, 0)
+
+        await program.methods.setCollectionPaused(true, 2).accounts({
+            signer: guardian.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+        }).signers([guardian]).rpc()
+        await assert.rejects(createSellOrder())
+        await assert.rejects(addQuantityToSellOrder())
+
+        await program.methods.setCollectionPaused(false, 0).accounts({
+            signer: guardian.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+        }).signers([guardian]).rpc()
+        let updatedCollection = await program.account.collection.fetch(collectionPDA)
+        assert.equal(updatedCollection.paused, false);
+        assert.equal(updatedCollection.pauseReason, 0);
+    });
 });
@@ -0,0 +1,280 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { getCollectionPDA, getEscrowPDA, getMarketplacePDA, getPaymentMintPDA, getRoyaltyEscrowPDA } from "../js/getPDAs";
+import { nextEvent } from "./utils/utils";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+describe('multisig admin', () => {
+    let admin: web3.Keypair;
+    let signers: web3.Keypair[];
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let marketplacePDA: web3.PublicKey;
+    let multisigPDA: web3.PublicKey;
+
+    const getProposalPDA = async (index: number): Promise<web3.PublicKey> => {
+        return (await web3.PublicKey.findProgramAddress(
+            [Buffer.from("MARKETPLACE"), multisigPDA.toBuffer(), Buffer.from(index.toString()), Buffer.from("ADMIN_PROPOSAL")],
+            program.programId,
+        ))[0]
+    }
+
+    const propose = async (proposer: web3.Keypair, action: any): Promise<web3.PublicKey> => {
+        let multisig = await program.account.multisig.fetch(multisigPDA)
+        let proposalPDA = await getProposalPDA(multisig.proposalCount.toNumber())
+        await program.methods.createAdminProposal(action).accounts({
+            proposer: proposer.publicKey,
+            multisig: multisigPDA,
+            proposal: proposalPDA,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([proposer]).rpc()
+        return proposalPDA
+    }
+
+    const approve = (approver: web3.Keypair, proposalPDA: web3.PublicKey) => {
+        return program.methods.approveAdminProposal().accounts({
+            approver: approver.publicKey,
+            multisig: multisigPDA,
+            proposal: proposalPDA,
+        }).signers([approver]).rpc()
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        signers = [web3.Keypair.generate(), web3.Keypair.generate(), web3.Keypair.generate()]
+        for (let account of [admin, ...signers]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            TOKEN_PROGRAM_ID,
+        );
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+
+        marketplacePDA = await getMarketplacePDA(admin.publicKey)
+        await program.methods.createMarketplace(marketplaceMint.publicKey, 5, adminTokenAccount.address, admin.publicKey, new anchor.BN(0)).accounts({
+            payer: admin.publicKey,
+            marketplace: marketplacePDA,
+            mint: marketplaceMint.publicKey,
+            escrow: await getEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            paymentMint: await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey),
+            systemProgram: web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([admin]).rpc()
+
+        multisigPDA = (await web3.PublicKey.findProgramAddress(
+            [Buffer.from("MARKETPLACE"), marketplacePDA.toBuffer(), Buffer.from("MULTISIG")],
+            program.programId,
+        ))[0]
+    });
+
+    it('create multisig', async () => {
+        await assert.rejects(
+            program.methods.createMultisig(signers.map(s => s.publicKey), 4).accounts({
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+                multisig: multisigPDA,
+                systemProgram: web3.SystemProgram.programId,
+                rent: web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc()
+        )
+
+        await program.methods.createMultisig(signers.map(s => s.publicKey), 2).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplacePDA,
+            multisig: multisigPDA,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([admin]).rpc()
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.authority.toString(), multisigPDA.toString());
+
+        // the previous authority can no longer change the configuration
+        await assert.rejects(
+            program.methods.updateMarketplace(10, null, null, null).accounts({
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+            }).signers([admin]).rpc()
+        )
+    });
+
+    it('update marketplace through a proposal', async () => {
+        let createdEvent = nextEvent(program, "AdminProposalUpdated")
+        let proposalPDA = await propose(signers[0], { updateMarketplace: { optionalFees: 10, optionalFeesDestination: null, optionalFeeTimelock: null, optionalTakerFees: null } })
+        let event = await createdEvent
+        assert.equal(event.proposal.toString(), proposalPDA.toString());
+        assert.equal(event.proposer.toString(), signers[0].publicKey.toString());
+        assert.equal(event.action.updateMarketplace.optionalFees, 10);
+        assert.equal(event.approvedBy.length, 1);
+        assert.equal(event.cancelled, false);
+
+        let execute = () => program.methods.executeMarketplaceProposal().accounts({
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+        }).rpc()
+
+        await assert.rejects(execute())
+        await assert.rejects(approve(signers[0], proposalPDA))
+        let approvedEvent = nextEvent(program, "AdminProposalUpdated")
+        await approve(signers[1], proposalPDA)
+        event = await approvedEvent
+        assert.deepEqual(event.approvedBy.map(s => s.toString()), [signers[0].publicKey.toString(), signers[1].publicKey.toString()]);
+        await execute()
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.fees, 10);
+        assert.equal(await provider.connection.getAccountInfo(proposalPDA), null);
+    });
+
+    it('create collection through a proposal', async () => {
+        let collectionPDA = await getCollectionPDA(marketplacePDA, "AURY")
+        let proposalPDA = await propose(signers[1], {
+            createCollection: { symbol: "AURY", requiredVerifier: admin.publicKey, fee: null, ignoreFee: false, royaltyPolicy: { full: {} } },
+        })
+        await approve(signers[2], proposalPDA)
+
+        let execute = (symbol: string) => program.methods.executeCreateCollectionProposal(symbol).accounts({
+            payer: signers[2].publicKey,
+            proposer: signers[1].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+            collection: collectionPDA,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([signers[2]]).rpc()
+
+        // the executed action has to match the approved one
+        await assert.rejects(execute("OTHER"))
+        await execute("AURY")
+
+        let collection = await program.account.collection.fetch(collectionPDA)
+        assert.equal(collection.symbol, "AURY");
+        assert.equal(collection.requiredVerifier.toString(), admin.publicKey.toString());
+    });
+
+    it('only migrate legacy collections through a proposal', async () => {
+        let collectionPDA = await getCollectionPDA(marketplacePDA, "AURY")
+        let proposalPDA = await propose(signers[0], { migrateCollection: { collection: collectionPDA } })
+        await approve(signers[1], proposalPDA)
+
+        // the collection was created with the current layout so there is nothing to migrate
+        await assert.rejects(program.methods.executeMigrateCollectionProposal().accounts({
+            payer: signers[1].publicKey,
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+            collection: collectionPDA,
+            systemProgram: web3.SystemProgram.programId,
+        }).signers([signers[1]]).rpc())
+        let collection = await program.account.collection.fetch(collectionPDA)
+        assert.equal(collection.symbol, "AURY");
+
+        await program.methods.cancelAdminProposal().accounts({
+            canceller: signers[0].publicKey,
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            proposal: proposalPDA,
+        }).signers([signers[0]]).rpc()
+    });
+
+    it('set fee splits and referral fee through proposals', async () => {
+        let paymentMintPDA = await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey)
+        let feeSplits = [
+            { destination: adminTokenAccount.address, share: 7000 },
+            { destination: signers[0].publicKey, share: 3000 },
+        ]
+        let proposalPDA = await propose(signers[0], { setFeeSplits: { mint: marketplaceMint.publicKey, feeSplits: feeSplits } })
+        await approve(signers[2], proposalPDA)
+        let updatedEvent = nextEvent(program, "PaymentMintUpdated")
+        await program.methods.executePaymentMintProposal().accounts({
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+            paymentMint: paymentMintPDA,
+        }).rpc()
+
+        let paymentMint = await program.account.paymentMint.fetch(paymentMintPDA)
+        assert.equal(paymentMint.feeSplits.length, 2);
+        assert.equal(paymentMint.feeSplits[1].share, 3000);
+        let event = await updatedEvent
+        assert.equal(event.paymentMint.toString(), paymentMintPDA.toString());
+        assert.equal(event.feeSplits[1].share, 3000);
+
+        proposalPDA = await propose(signers[0], { setReferralFee: { referralFee: 1000 } })
+        await approve(signers[1], proposalPDA)
+        await program.methods.executeMarketplaceProposal().accounts({
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+        }).rpc()
+
+        let marketplace = await program.account.marketplace.fetch(marketplacePDA)
+        assert.equal(marketplace.referralFee, 1000);
+    });
+
+    it('cancel a proposal', async () => {
+        let proposalPDA = await propose(signers[0], { setGuardian: { guardian: admin.publicKey } })
+        let cancel = (canceller: web3.Keypair) => program.methods.cancelAdminProposal().accounts({
+            canceller: canceller.publicKey,
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            proposal: proposalPDA,
+        }).signers([canceller]).rpc()
+
+        // only the proposer can withdraw a proposal before it expires, other signers can't veto it
+        await approve(signers[1], proposalPDA)
+        await assert.rejects(cancel(admin))
+        await assert.rejects(cancel(signers[2]))
+        let cancelledEvent = nextEvent(program, "AdminProposalUpdated")
+        await cancel(signers[0])
+        assert.equal(await provider.connection.getAccountInfo(proposalPDA), null);
+
+        let event = await cancelledEvent
+        assert.equal(event.proposal.toString(), proposalPDA.toString());
+        assert.equal(event.cancelled, true);
+    });
+
+    it('rotate the signers', async () => {
+        let newSigner = web3.Keypair.generate()
+        let newSigners = [signers[0].publicKey, newSigner.publicKey]
+        let proposalPDA = await propose(signers[0], { setSigners: { signers: newSigners, threshold: 2 } })
+        await approve(signers[1], proposalPDA)
+        await program.methods.executeMarketplaceProposal().accounts({
+            proposer: signers[0].publicKey,
+            multisig: multisigPDA,
+            marketplace: marketplacePDA,
+            proposal: proposalPDA,
+        }).rpc()
+
+        let multisig = await program.account.multisig.fetch(multisigPDA)
+        assert.deepEqual(multisig.signers.map(s => s.toString()), newSigners.map(s => s.toString()));
+        assert.equal(multisig.threshold, 2);
+
+        // removed signers can no longer propose
+        await assert.rejects(propose(signers[1], { cancelAuthorityTransfer: {} }))
+    });
+});
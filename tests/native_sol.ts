@@ -0,0 +1,236 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getAuctionPDA, getBuyerEscrowPDA, getCollectionPDA, getPaymentMintPDA, getRoyaltyVaultPDA, getSellOrderPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+const airdrop = async (to: web3.PublicKey) => {
+    let signature = await provider.connection.requestAirdrop(to, 2 * web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature);
+}
+
+describe('native sol marketplace', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let nftMint: Token;
+    let metadataPDA: web3.PublicKey;
+    let sellerNftAssociatedTokenAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // royalties accrued for the creator and not claimed yet
+    const accruedRoyalties = async (): Promise<number> => {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplace.marketplacePDA, NATIVE_MINT, creator.publicKey)
+        if (await provider.connection.getAccountInfo(royaltyVaultPDA) === null) {
+            return 0
+        }
+        return (await program.account.royaltyVault.fetch(royaltyVaultPDA)).amount.toNumber()
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            await airdrop(account.publicKey)
+        }
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, metadataAddr, tx] = await createMint(
+            creator.publicKey,
+            seller.publicKey,
+            lamports,
+            nft_data(creator.publicKey),
+            nft_json_url
+        );
+        await provider.send(tx, [mint, creator]);
+
+        metadataPDA = metadataAddr
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAssociatedTokenAccount = (await nftMint.getOrCreateAssociatedAccountInfo(seller.publicKey)).address
+
+        // fees are paid straight to the admin wallet
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, NATIVE_MINT, 500, admin.publicKey, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('buy with lamports', async () => {
+        let price = new anchor.BN(web3.LAMPORTS_PER_SOL / 10)
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAssociatedTokenAccount,
+            seller.publicKey,
+            new anchor.BN(0),
+            price,
+            new anchor.BN(1),
+            seller
+        )
+
+        let buyerNftATA = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+        let sellerBalanceBefore = await provider.connection.getBalance(seller.publicKey)
+        let royaltiesBefore = await accruedRoyalties()
+        let adminBalanceBefore = await provider.connection.getBalance(admin.publicKey)
+
+        await collection.buy(
+            nftMint.publicKey,
+            [
+                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(0)),
+            ],
+            buyerNftATA,
+            buyer.publicKey,
+            new anchor.BN(1),
+            price,
+            buyer,
+        )
+
+        let buyerNftAccount = await nftMint.getAccountInfo(buyerNftATA)
+        assert.equal(buyerNftAccount.amount.toNumber(), 1)
+
+        // 10% creator royalties and 5% marketplace fee
+        let total = price.toNumber()
+        assert.equal(await accruedRoyalties(), royaltiesBefore + total / 10)
+        assert.equal(await provider.connection.getBalance(admin.publicKey), adminBalanceBefore + total / 20)
+        assert.equal(await provider.connection.getBalance(seller.publicKey), sellerBalanceBefore + total - total / 10 - total / 20)
+    });
+
+    it('nft offer escrows lamports', async () => {
+        let price = new anchor.BN(web3.LAMPORTS_PER_SOL / 10)
+        let buyerEscrowPDA = await getBuyerEscrowPDA(marketplace.marketplacePDA, NATIVE_MINT, buyer.publicKey)
+        let [buyOfferPDA] = await web3.PublicKey.findProgramAddress(
+            [
+                Buffer.from("MARKETPLACE"),
+                marketplace.marketplacePDA.toBuffer(),
+                buyer.publicKey.toBuffer(),
+                nftMint.publicKey.toBuffer(),
+                Buffer.from(price.toString()),
+                Buffer.from("ESCROW"),
+            ],
+            program.programId,
+        );
+        let buyerNftATA = await Token.getAssociatedTokenAddress(ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, nftMint.publicKey, buyer.publicKey)
+
+        await program.methods.createBuyOffer(price, null).accounts({
+            payer: buyer.publicKey,
+            nftMint: nftMint.publicKey,
+            metadata: metadataPDA,
+            marketplace: marketplace.marketplacePDA,
+            collection: collection.collectionPDA,
+            paymentMint: await getPaymentMintPDA(marketplace.marketplacePDA, NATIVE_MINT),
+            marketplaceMint: NATIVE_MINT,
+            buyerEscrow: buyerEscrowPDA,
+            buyerPayingAccount: buyer.publicKey,
+            buyerNftAccount: buyerNftATA,
+            buyOffer: buyOfferPDA,
+            systemProgram: web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([buyer]).rpc()
+
+        let escrowRent = await provider.connection.getMinimumBalanceForRentExemption(0)
+        let escrowAccount = await provider.connection.getAccountInfo(buyerEscrowPDA)
+        assert.ok(escrowAccount.owner.equals(program.programId))
+        // the 5% maker fee is escrowed on top of the price
+        assert.equal(escrowAccount.lamports, escrowRent + price.toNumber() + price.toNumber() / 20)
+
+        await program.methods.reconcileBuyerEscrow(NATIVE_MINT).accounts({
+            buyer: buyer.publicKey,
+            marketplace: marketplace.marketplacePDA,
+            buyerEscrow: buyerEscrowPDA,
+        }).remainingAccounts([
+            { pubkey: buyOfferPDA, isWritable: false, isSigner: false },
+        ]).rpc()
+
+        await program.methods.removeBuyOffer().accounts({
+            buyer: buyer.publicKey,
+            buyerPayingAccount: buyer.publicKey,
+            marketplace: marketplace.marketplacePDA,
+            buyerEscrow: buyerEscrowPDA,
+            buyOffer: buyOfferPDA,
+            systemProgram: web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+        }).signers([buyer]).rpc()
+
+        escrowAccount = await provider.connection.getAccountInfo(buyerEscrowPDA)
+        assert.equal(escrowAccount.lamports, escrowRent)
+    });
+
+    it('trait offer escrows lamports', async () => {
+        // the buyer sells the nft back to the seller through a trait offer
+        let price = new anchor.BN(web3.LAMPORTS_PER_SOL / 10)
+        let traitMints = [nftMint.publicKey]
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(1), seller.publicKey, seller, NATIVE_MINT)
+
+        // the 5% maker fee is escrowed on top of the price, in the offer itself
+        let traitOfferPDA = await getTraitOfferPDA(marketplace.marketplacePDA, seller.publicKey, getMerkleRoot(traitMints), price)
+        let traitOfferRent = await provider.connection.getMinimumBalanceForRentExemption(192)
+        let traitOfferAccount = await provider.connection.getAccountInfo(traitOfferPDA)
+        assert.ok(traitOfferAccount.owner.equals(program.programId))
+        assert.equal(traitOfferAccount.lamports, traitOfferRent + price.toNumber() + price.toNumber() / 20)
+
+        let royaltiesBefore = await accruedRoyalties()
+        let adminBalanceBefore = await provider.connection.getBalance(admin.publicKey)
+        await collection.executeTraitOffer(
+            traitOfferPDA,
+            traitMints,
+            nftMint.publicKey,
+            await getAssociatedTokenAddress(buyer.publicKey, nftMint.publicKey),
+            buyer.publicKey,
+            buyer,
+        )
+
+        let sellerNftAccount = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
+        assert.equal(sellerNftAccount.amount.toNumber(), 1)
+
+        // the filled offer is closed and its rent goes back to the seller who made it
+        let total = price.toNumber()
+        assert.equal(await provider.connection.getAccountInfo(traitOfferPDA), null)
+        assert.equal(await accruedRoyalties(), royaltiesBefore + total / 10)
+        assert.equal(await provider.connection.getBalance(admin.publicKey), adminBalanceBefore + total / 20)
+    });
+
+    it('auction escrows lamports', async () => {
+        let reservePrice = new anchor.BN(web3.LAMPORTS_PER_SOL / 10)
+        let endTime = new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+        await collection.createAuction(
+            nftMint.publicKey, sellerNftAssociatedTokenAccount, seller.publicKey,
+            reservePrice, new anchor.BN(web3.LAMPORTS_PER_SOL / 100), endTime, seller, NATIVE_MINT,
+        )
+        let auctionPDA = await getAuctionPDA(marketplace.marketplacePDA, seller.publicKey, nftMint.publicKey)
+        let auctionRent = await provider.connection.getMinimumBalanceForRentExemption(272)
+
+        // the bid and its 5% maker fee are held by the auction itself
+        let buyerBalanceBefore = await provider.connection.getBalance(buyer.publicKey)
+        await collection.placeBid(auctionPDA, reservePrice, buyer.publicKey, buyer)
+        let bid = reservePrice.toNumber()
+        assert.equal(await provider.connection.getBalance(buyer.publicKey), buyerBalanceBefore - bid - bid / 20)
+        assert.equal(await provider.connection.getBalance(auctionPDA), auctionRent + bid + bid / 20)
+
+        // the outbid buyer gets its lamports back
+        let higherBid = new anchor.BN(web3.LAMPORTS_PER_SOL / 5)
+        await collection.placeBid(auctionPDA, higherBid, creator.publicKey, creator)
+        assert.equal(await provider.connection.getBalance(buyer.publicKey), buyerBalanceBefore)
+        assert.equal(await provider.connection.getBalance(auctionPDA), auctionRent + higherBid.toNumber() + higherBid.toNumber() / 20)
+    });
+});
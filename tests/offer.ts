@@ -6,7 +6,8 @@ import { PublicKey } from "@solana/web3.js";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import assert from "assert";
 import { nft_data, nft_json_url } from "./data";
-import { createMint } from "./utils/utils";
+import { createMint, fundRoyaltyVaults, nextEvent } from "./utils/utils";
+import { getCollectionOfferPDA, getPaymentMintPDA, getRoyaltyEscrowPDA, getRoyaltyVaultPDA } from "../js/getPDAs";
 
 let provider = anchor.Provider.env()
 anchor.setProvider(provider);
@@ -36,6 +37,8 @@ describe('marketplace with mint', () => {
     let nftMint: splToken.Token;
     let metadataPDA: web3.PublicKey;
     let escrowPDA: web3.PublicKey;
+    let buyerEscrowPDA: web3.PublicKey;
+    let paymentMintPDA: web3.PublicKey;
     let escrowDump: number;
     let buyOfferPDA: web3.PublicKey;
     let buyOfferDump: number;
@@ -97,7 +100,7 @@ describe('marketplace with mint', () => {
             buyer.publicKey,
         );
 
-        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 1000);
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 1050);
 
         [collectionPDA, collectionDump] = await anchor.web3.PublicKey.findProgramAddress(
             [Buffer.from("MARKETPLACE"), Buffer.from(collectionName), marketplacePDA.toBuffer()],
@@ -114,6 +117,19 @@ describe('marketplace with mint', () => {
             program.programId,
         );
 
+        paymentMintPDA = await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey);
+
+        [buyerEscrowPDA] = await anchor.web3.PublicKey.findProgramAddress(
+            [
+                Buffer.from("MARKETPLACE"),
+                marketplacePDA.toBuffer(),
+                marketplaceMint.publicKey.toBuffer(),
+                buyer.publicKey.toBuffer(),
+                Buffer.from("ESCROW"),
+            ],
+            program.programId,
+        );
+
         const data = nft_data(creator.publicKey);
         const json_url = nft_json_url;
         const lamports = await Token.getMinBalanceRentForExemptMint(
@@ -158,18 +174,23 @@ describe('marketplace with mint', () => {
             program.programId,
         );
 
-        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey).accounts(
+        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey, new anchor.BN(0)).accounts(
             {
                 payer: admin.publicKey,
                 marketplace: marketplacePDA,
                 mint: marketplaceMint.publicKey,
                 escrow: escrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+                paymentMint: paymentMintPDA,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             }).signers([admin]).rpc();
 
-        await program.methods.createCollection(collectionName, creator.publicKey, collectionFee, false).accounts(
+        // fills pay the rent of new royalty vaults from the marketplace lamports
+        await fundRoyaltyVaults(provider.connection, admin, marketplacePDA)
+
+        await program.methods.createCollection(collectionName, creator.publicKey, collectionFee, false, { full: {} }).accounts(
             {
                 authority: admin.publicKey,
                 marketplace: marketplacePDA,
@@ -180,14 +201,16 @@ describe('marketplace with mint', () => {
     });
 
     it('remove nft offer', async () => {
-        await program.methods.createBuyOffer(new anchor.BN(1000)).accounts(
+        await program.methods.createBuyOffer(new anchor.BN(1000), null).accounts(
             {
                 payer: buyer.publicKey,
                 nftMint: nftMint.publicKey,
                 metadata: metadataPDA,
                 marketplace: marketplacePDA,
                 collection: collectionPDA,
-                escrow: escrowPDA,
+                paymentMint: paymentMintPDA,
+                marketplaceMint: marketplaceMint.publicKey,
+                buyerEscrow: buyerEscrowPDA,
                 buyerPayingAccount: buyerTokenAccount.address,
                 buyerNftAccount: buyerNftTokenAccount,
                 buyOffer: buyOfferPDA,
@@ -201,30 +224,147 @@ describe('marketplace with mint', () => {
             buyer: buyer.publicKey,
             buyerPayingAccount: buyerTokenAccount.address,
             marketplace: marketplacePDA,
-            escrow: escrowPDA,
+            buyerEscrow: buyerEscrowPDA,
             buyOffer: buyOfferPDA,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         }).signers([buyer]).rpc()
 
-        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        let escrowAccount = await marketplaceMint.getAccountInfo(buyerEscrowPDA)
         assert.equal(escrowAccount.amount, 0);
         let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
-        assert.equal(updatedBuyerAccount.amount, 1000);
+        assert.equal(updatedBuyerAccount.amount, 1050);
 
         let closedBuyOffer = await provider.connection.getAccountInfo(buyOfferPDA);
         assert.equal(closedBuyOffer, null);
     });
 
+    it('expire nft offer', async () => {
+        let expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 2)
+        await program.methods.createBuyOffer(new anchor.BN(1000), expiry).accounts(
+            {
+                payer: buyer.publicKey,
+                nftMint: nftMint.publicKey,
+                metadata: metadataPDA,
+                marketplace: marketplacePDA,
+                collection: collectionPDA,
+                paymentMint: paymentMintPDA,
+                marketplaceMint: marketplaceMint.publicKey,
+                buyerEscrow: buyerEscrowPDA,
+                buyerPayingAccount: buyerTokenAccount.address,
+                buyerNftAccount: buyerNftTokenAccount,
+                buyOffer: buyOfferPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([buyer]).rpc()
+
+        let expireBuyOffer = () => program.methods.expireBuyOffer().accounts({
+            buyer: buyer.publicKey,
+            buyerPayingAccount: buyerTokenAccount.address,
+            marketplace: marketplacePDA,
+            buyerEscrow: buyerEscrowPDA,
+            buyOffer: buyOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }).rpc()
+
+        await assert.rejects(expireBuyOffer())
+        await new Promise(resolve => setTimeout(resolve, 4000))
+        await expireBuyOffer()
+
+        let escrowAccount = await marketplaceMint.getAccountInfo(buyerEscrowPDA)
+        assert.equal(escrowAccount.amount, 0);
+        let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
+        assert.equal(updatedBuyerAccount.amount, 1050);
+
+        let closedBuyOffer = await provider.connection.getAccountInfo(buyOfferPDA);
+        assert.equal(closedBuyOffer, null);
+    });
+
+    it('remove nft offer after a marketplace mint change', async () => {
+        await program.methods.createBuyOffer(new anchor.BN(1000), null).accounts(
+            {
+                payer: buyer.publicKey,
+                nftMint: nftMint.publicKey,
+                metadata: metadataPDA,
+                marketplace: marketplacePDA,
+                collection: collectionPDA,
+                paymentMint: paymentMintPDA,
+                marketplaceMint: marketplaceMint.publicKey,
+                buyerEscrow: buyerEscrowPDA,
+                buyerPayingAccount: buyerTokenAccount.address,
+                buyerNftAccount: buyerNftTokenAccount,
+                buyOffer: buyOfferPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([buyer]).rpc()
+
+        let newMarketplaceMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            splToken.TOKEN_PROGRAM_ID,
+        );
+        let newAdminTokenAccount = await newMarketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey);
+        let [newEscrowPDA] = await anchor.web3.PublicKey.findProgramAddress(
+            [
+                Buffer.from("MARKETPLACE"),
+                marketplacePDA.toBuffer(),
+                newMarketplaceMint.publicKey.toBuffer(),
+                Buffer.from("ESCROW"),
+            ],
+            program.programId,
+        );
+        let updateMarketplaceMint = async (mint: web3.PublicKey, feesDestination: web3.PublicKey, escrow: web3.PublicKey) =>
+            program.methods.updateMarketplaceMint(mint, feesDestination).accounts({
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+                mint: mint,
+                escrow: escrow,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, mint),
+                paymentMint: await getPaymentMintPDA(marketplacePDA, mint),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc()
+        await updateMarketplaceMint(newMarketplaceMint.publicKey, newAdminTokenAccount.address, newEscrowPDA)
+
+        // the offer still points at the escrow it was funded from
+        await program.methods.removeBuyOffer().accounts({
+            buyer: buyer.publicKey,
+            buyerPayingAccount: buyerTokenAccount.address,
+            marketplace: marketplacePDA,
+            buyerEscrow: buyerEscrowPDA,
+            buyOffer: buyOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).signers([buyer]).rpc()
+
+        let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
+        assert.equal(updatedBuyerAccount.amount, 1050);
+
+        //revert
+        await updateMarketplaceMint(marketplaceMint.publicKey, adminTokenAccount.address, escrowPDA)
+    });
+
     it('create nft offer', async () => {
-        await program.methods.createBuyOffer(new anchor.BN(1000)).accounts({
+        await program.methods.createBuyOffer(new anchor.BN(1000), null).accounts({
             payer: buyer.publicKey,
             nftMint: nftMint.publicKey,
             metadata: metadataPDA,
             marketplace: marketplacePDA,
             collection: collectionPDA,
-            escrow: escrowPDA,
+            paymentMint: paymentMintPDA,
+            marketplaceMint: marketplaceMint.publicKey,
+            buyerEscrow: buyerEscrowPDA,
             buyerPayingAccount: buyerTokenAccount.address,
             buyerNftAccount: buyerNftTokenAccount,
             buyOffer: buyOfferPDA,
@@ -240,23 +380,36 @@ describe('marketplace with mint', () => {
         assert.equal(buyOffer.proposedPrice.toString(), "1000");
         assert.equal(buyOffer.authority.toString(), buyer.publicKey.toString());
         assert.equal(buyOffer.destination.toString(), buyerNftTokenAccount.toString());
+        assert.equal(buyOffer.paymentMint.toString(), marketplaceMint.publicKey.toString());
 
-        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
-        assert.equal(escrowAccount.amount, 1000);
+        // the 5% collection maker fee is escrowed on top of the price
+        assert.equal(buyOffer.makerFee.toNumber(), 50);
+        let escrowAccount = await marketplaceMint.getAccountInfo(buyerEscrowPDA)
+        assert.equal(escrowAccount.amount, 1050);
 
         let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
         assert.equal(updatedBuyerAccount.amount, 0);
+
+        await program.methods.reconcileBuyerEscrow(marketplaceMint.publicKey).accounts({
+            buyer: buyer.publicKey,
+            marketplace: marketplacePDA,
+            buyerEscrow: buyerEscrowPDA,
+        }).remainingAccounts([{ pubkey: buyOfferPDA, isWritable: false, isSigner: false }]).rpc()
     });
 
     it('execute nft offer', async () => {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplacePDA, marketplaceMint.publicKey, creator.publicKey)
         await program.methods.executeOffer().accounts({
             seller: seller.publicKey,
             buyer: buyer.publicKey,
             marketplace: marketplacePDA,
             collection: collectionPDA,
+            paymentMint: paymentMintPDA,
             marketplaceDestAccount: adminTokenAccount.address,
-            escrow: escrowPDA,
+            buyerEscrow: buyerEscrowPDA,
             sellerFundsDestAccount: sellerTokenAccount.address,
+            makerRebateAccount: buyerTokenAccount.address,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
             destination: buyerNftTokenAccount,
             sellerNftAccount: sellerNftAssociatedTokenAccount,
             buyOffer: buyOfferPDA,
@@ -264,10 +417,10 @@ describe('marketplace with mint', () => {
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        }).remainingAccounts([{ pubkey: creatorTokenAccount.address, isWritable: true, isSigner: false }])
+        }).remainingAccounts([{ pubkey: royaltyVaultPDA, isWritable: true, isSigner: false }])
             .signers([seller]).rpc()
 
-        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        let escrowAccount = await marketplaceMint.getAccountInfo(buyerEscrowPDA)
         assert.equal(escrowAccount.amount, 0);
 
         let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
@@ -277,7 +430,7 @@ describe('marketplace with mint', () => {
         assert.equal(updatedBuyerNftAccount.amount, 1);
 
         let updatedSellerAccount = await marketplaceMint.getAccountInfo(sellerTokenAccount.address)
-        assert.equal(updatedSellerAccount.amount, 850);
+        assert.equal(updatedSellerAccount.amount, 900);
 
         let updatedSellerNftAccount = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
         assert.equal(updatedSellerNftAccount.amount.toNumber(), 4);
@@ -285,11 +438,142 @@ describe('marketplace with mint', () => {
         let updatedMarketplaceDestinationAccount = await marketplaceMint.getAccountInfo(adminTokenAccount.address)
         assert.equal(updatedMarketplaceDestinationAccount.amount.toNumber(), 50);
 
+        let royaltyVault = await program.account.royaltyVault.fetch(royaltyVaultPDA)
+        assert.equal(royaltyVault.amount.toNumber(), 100);
+
+        // royalties are held apart from the offers escrow until claimed
+        let royaltyEscrowPDA = await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey)
+        let royaltyEscrowAccount = await marketplaceMint.getAccountInfo(royaltyEscrowPDA)
+        assert.equal(royaltyEscrowAccount.amount.toNumber(), 100);
+
+        let claimRoyaltiesAccounts = {
+            creator: creator.publicKey,
+            marketplace: marketplacePDA,
+            royaltyVault: royaltyVaultPDA,
+            rentPayer: marketplacePDA,
+            royaltyEscrow: royaltyEscrowPDA,
+            destination: creatorTokenAccount.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        }
+        await program.methods.claimRoyalties().accounts(claimRoyaltiesAccounts).signers([creator]).rpc()
+
         let updatedCreator = await marketplaceMint.getAccountInfo(creatorTokenAccount.address)
         assert.equal(updatedCreator.amount.toNumber(), 100);
 
+        assert.equal(await provider.connection.getAccountInfo(royaltyVaultPDA), null);
+
         let closedBuyOffer = await provider.connection.getAccountInfo(buyOfferPDA);
         assert.equal(closedBuyOffer, null);
     });
+
+    it('execute collection offer', async () => {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplacePDA, marketplaceMint.publicKey, creator.publicKey)
+        let price = new anchor.BN(500)
+        let collectionOfferPDA = await getCollectionOfferPDA(marketplacePDA, buyer.publicKey, collectionPDA, price)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 1050);
+
+        let createdEvent = nextEvent(program, "CollectionOfferCreated")
+        await program.methods.createCollectionOffer(price, new anchor.BN(2)).accounts({
+            payer: buyer.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+            paymentMint: paymentMintPDA,
+            escrow: escrowPDA,
+            buyerPayingAccount: buyerTokenAccount.address,
+            collectionOffer: collectionOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).signers([buyer]).rpc()
+
+        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 1050);
+        let createdCollectionOffer = await program.account.collectionOffer.fetch(collectionOfferPDA)
+        assert.equal(createdCollectionOffer.paymentMint.toString(), marketplaceMint.publicKey.toString());
+        let event = await createdEvent
+        assert.equal(event.offer.toString(), collectionOfferPDA.toString());
+        assert.equal(event.collection.toString(), collectionPDA.toString());
+        assert.equal(event.buyer.toString(), buyer.publicKey.toString());
+        assert.equal(event.price.toNumber(), 500);
+        assert.equal(event.quantity.toNumber(), 2);
+
+        let filledEvent = nextEvent(program, "CollectionOfferFilled")
+        await program.methods.executeCollectionOffer().accounts({
+            seller: seller.publicKey,
+            buyer: buyer.publicKey,
+            marketplace: marketplacePDA,
+            collection: collectionPDA,
+            paymentMint: paymentMintPDA,
+            marketplaceDestAccount: adminTokenAccount.address,
+            escrow: escrowPDA,
+            royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+            sellerFundsDestAccount: sellerTokenAccount.address,
+            buyerPayingAccount: buyerTokenAccount.address,
+            nftMint: nftMint.publicKey,
+            sellerNftAccount: sellerNftAssociatedTokenAccount,
+            buyerNftAccount: buyerNftTokenAccount,
+            metadata: metadataPDA,
+            collectionOffer: collectionOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).remainingAccounts([{ pubkey: royaltyVaultPDA, isWritable: true, isSigner: false }])
+            .signers([seller]).rpc()
+
+        let collectionOffer = await program.account.collectionOffer.fetch(collectionOfferPDA)
+        assert.equal(collectionOffer.quantity.toNumber(), 1);
+
+        event = await filledEvent
+        assert.equal(event.offer.toString(), collectionOfferPDA.toString());
+        assert.equal(event.mint.toString(), nftMint.publicKey.toString());
+        assert.equal(event.seller.toString(), seller.publicKey.toString());
+        assert.equal(event.paymentMint.toString(), marketplaceMint.publicKey.toString());
+        assert.equal(event.marketplaceShare.toNumber(), 25);
+        assert.equal(event.creatorShares[0].amount.toNumber(), 50);
+        assert.equal(event.sellerShare.toNumber(), 450);
+        assert.equal(event.remainingQuantity.toNumber(), 1);
+
+        let updatedBuyerNftAccount = await nftMint.getAccountInfo(buyerNftTokenAccount)
+        assert.equal(updatedBuyerNftAccount.amount.toNumber(), 2);
+
+        let updatedSellerAccount = await marketplaceMint.getAccountInfo(sellerTokenAccount.address)
+        assert.equal(updatedSellerAccount.amount.toNumber(), 1350);
+
+        // the offers escrow only keeps the unfilled offer, the royalties moved to the royalty escrow
+        escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 525);
+        let royaltyEscrowAccount = await marketplaceMint.getAccountInfo(await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey))
+        assert.equal(royaltyEscrowAccount.amount.toNumber(), 50);
+
+        let cancelledEvent = nextEvent(program, "CollectionOfferCancelled")
+        await program.methods.removeCollectionOffer().accounts({
+            buyer: buyer.publicKey,
+            buyerPayingAccount: buyerTokenAccount.address,
+            marketplace: marketplacePDA,
+            escrow: escrowPDA,
+            collectionOffer: collectionOfferPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).signers([buyer]).rpc()
+
+        event = await cancelledEvent
+        assert.equal(event.offer.toString(), collectionOfferPDA.toString());
+        assert.equal(event.quantity.toNumber(), 1);
+
+        // the royalties stay in the royalty escrow until the creator claims them
+        escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 0);
+
+        let royaltyVault = await program.account.royaltyVault.fetch(royaltyVaultPDA)
+        assert.equal(royaltyVault.amount.toNumber(), 50);
+
+        let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
+        assert.equal(updatedBuyerAccount.amount.toNumber(), 525);
+
+        let closedCollectionOffer = await provider.connection.getAccountInfo(collectionOfferPDA);
+        assert.equal(closedCollectionOffer, null);
+    });
 })
     ;
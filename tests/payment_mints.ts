@@ -0,0 +1,150 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getAuctionPDA, getCollectionPDA, getEscrowPDA, getPaymentMintPDA, getRoyaltyEscrowPDA, getSellOrderPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('payment mints', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let otherMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 5% maker fee on a 1000 fill of an nft with 10% royalties
+    let price = new anchor.BN(1000)
+    let royalties = 100
+    let makerShare = 50
+
+    let now = () => new anchor.BN(Math.floor(Date.now() / 1000))
+
+    const balance = async (account: web3.PublicKey): Promise<number> =>
+        (await otherMint.getAccountInfo(account)).amount.toNumber()
+
+    const openListings = async (): Promise<number> =>
+        (await program.account.paymentMint.fetch(
+            await getPaymentMintPDA(marketplace.marketplacePDA, otherMint.publicKey)
+        )).openListings.toNumber()
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        // offers and listings below are all priced in the other mint
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        otherMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await otherMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        sellerTokenAccount = await otherMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await otherMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await otherMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+
+        marketplace = new Marketplace(provider)
+        let marketplaceTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, marketplaceTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.addPaymentMint(admin, otherMint.publicKey, adminTokenAccount.address)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('reject removing a payment mint with open sell orders', async () => {
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            new anchor.BN(0),
+            price,
+            new anchor.BN(2),
+            seller,
+            null,
+            null,
+            otherMint.publicKey,
+        )
+        assert.equal(await openListings(), 2)
+        await assert.rejects(marketplace.removePaymentMint(admin, otherMint.publicKey))
+
+        let sellOrderPDA = await getSellOrderPDA(sellerNftAccount, new anchor.BN(0))
+        await collection.removeSellOrder(nftMint.publicKey, sellerNftAccount, sellOrderPDA, new anchor.BN(1), seller)
+        assert.equal(await openListings(), 1)
+        await assert.rejects(marketplace.removePaymentMint(admin, otherMint.publicKey))
+
+        await collection.removeSellOrder(nftMint.publicKey, sellerNftAccount, sellOrderPDA, new anchor.BN(1), seller)
+        assert.equal(await openListings(), 0)
+    });
+
+    it('reject removing a payment mint with open auctions', async () => {
+        await collection.createAuction(
+            nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address,
+            new anchor.BN(100), new anchor.BN(10), now().addn(3600), seller, otherMint.publicKey,
+        )
+        let auctionPDA = await getAuctionPDA(marketplace.marketplacePDA, seller.publicKey, nftMint.publicKey)
+        let auction = await program.account.auction.fetch(auctionPDA)
+        assert.equal(auction.paymentMint.toString(), otherMint.publicKey.toString())
+        assert.equal(await openListings(), 1)
+        await assert.rejects(marketplace.removePaymentMint(admin, otherMint.publicKey))
+
+        await collection.cancelAuction(auctionPDA, sellerNftAccount, seller)
+        assert.equal(await openListings(), 0)
+    });
+
+    it('fill trait offers in their own payment mint', async () => {
+        let traitMints = [nftMint.publicKey]
+        let traitOfferPDA = await getTraitOfferPDA(marketplace.marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price)
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(1), buyerTokenAccount.address, buyer, otherMint.publicKey)
+
+        // the offer is escrowed in the other mint escrow, not the marketplace mint one
+        let traitOffer = await program.account.traitOffer.fetch(traitOfferPDA)
+        assert.equal(traitOffer.paymentMint.toString(), otherMint.publicKey.toString())
+        let escrowPDA = await getEscrowPDA(marketplace.marketplacePDA, otherMint.publicKey)
+        assert.equal(await balance(escrowPDA), 1000 + makerShare)
+
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let adminBalanceBefore = await balance(adminTokenAccount.address)
+        await collection.executeTraitOffer(traitOfferPDA, traitMints, nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address, seller)
+
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties)
+        assert.equal(await balance(adminTokenAccount.address), adminBalanceBefore + makerShare)
+        assert.equal(await balance(escrowPDA), 0)
+        assert.equal(await balance(await getRoyaltyEscrowPDA(marketplace.marketplacePDA, otherMint.publicKey)), royalties)
+    });
+
+    it('remove a payment mint once its listings are closed', async () => {
+        await marketplace.removePaymentMint(admin, otherMint.publicKey)
+        assert.equal(await provider.connection.getAccountInfo(await getPaymentMintPDA(marketplace.marketplacePDA, otherMint.publicKey)), null)
+    });
+});
@@ -0,0 +1,154 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, nextEvent } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getReferrerStatsPDA, getSellOrderPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('referrals', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let referrer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let referrerTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+    let buyerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 20% of the 5% marketplace fee of a 1000 fill
+    let price = new anchor.BN(1000)
+    let marketplaceShare = 50
+    let referralShare = 10
+
+    const listAndBuy = async (nonce: number, referrer: web3.PublicKey) => {
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            new anchor.BN(nonce),
+            price,
+            new anchor.BN(1),
+            seller,
+        )
+        return collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAccount, new anchor.BN(nonce))],
+            buyerNftAccount,
+            buyerTokenAccount.address,
+            new anchor.BN(1),
+            price,
+            buyer,
+            null,
+            referrer,
+        )
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        referrer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer, referrer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        referrerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(referrer.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        buyerNftAccount = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+        await marketplace.setReferralFee(admin, 2000)
+        let createdEvent = nextEvent(program, "ReferrerCreated")
+        await marketplace.createReferrer(referrer, marketplaceMint.publicKey, referrerTokenAccount.address)
+        let event = await createdEvent
+        assert.equal(event.referrer.toString(), referrer.publicKey.toString());
+        assert.equal(event.destination.toString(), referrerTokenAccount.address.toString());
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('pay referrer out of the marketplace share', async () => {
+        await listAndBuy(0, referrer.publicKey)
+
+        assert.equal((await marketplaceMint.getAccountInfo(referrerTokenAccount.address)).amount.toNumber(), referralShare)
+        assert.equal((await marketplaceMint.getAccountInfo(adminTokenAccount.address)).amount.toNumber(), marketplaceShare - referralShare)
+
+        let referrerStats = await program.account.referrerStats.fetch(
+            await getReferrerStatsPDA(marketplace.marketplacePDA, marketplaceMint.publicKey, referrer.publicKey)
+        )
+        assert.equal(referrerStats.volume.toNumber(), price.toNumber())
+        assert.equal(referrerStats.feesEarned.toNumber(), referralShare)
+    });
+
+    it('reject the buyer or the seller as referrer', async () => {
+        await marketplace.createReferrer(buyer, marketplaceMint.publicKey, buyerTokenAccount.address)
+        await marketplace.createReferrer(seller, marketplaceMint.publicKey, sellerTokenAccount.address)
+
+        await assert.rejects(listAndBuy(1, buyer.publicKey))
+        await assert.rejects(collection.buy(
+            nftMint.publicKey,
+            [await getSellOrderPDA(sellerNftAccount, new anchor.BN(1))],
+            buyerNftAccount,
+            buyerTokenAccount.address,
+            new anchor.BN(1),
+            price,
+            buyer,
+            null,
+            seller.publicKey,
+        ))
+    });
+
+    it('pay referrer on trait offers', async () => {
+        let traitMints = [nftMint.publicKey]
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(2), buyerTokenAccount.address, buyer)
+        let traitOfferPDA = await getTraitOfferPDA(marketplace.marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price)
+
+        await assert.rejects(collection.executeTraitOffer(
+            traitOfferPDA, traitMints, nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address, seller, seller.publicKey,
+        ))
+        await collection.executeTraitOffer(
+            traitOfferPDA, traitMints, nftMint.publicKey, sellerNftAccount, sellerTokenAccount.address, seller, referrer.publicKey,
+        )
+
+        assert.equal((await marketplaceMint.getAccountInfo(referrerTokenAccount.address)).amount.toNumber(), 2 * referralShare)
+        let referrerStats = await program.account.referrerStats.fetch(
+            await getReferrerStatsPDA(marketplace.marketplacePDA, marketplaceMint.publicKey, referrer.publicKey)
+        )
+        assert.equal(referrerStats.volume.toNumber(), 2 * price.toNumber())
+    });
+});
@@ -0,0 +1,154 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getRoyaltyVaultPDA, getSellOrderPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('royalty policy', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let marketplaceMint: splToken.Token;
+    let adminTokenAccount: splToken.AccountInfo;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let nftMint: Token;
+    let sellerNftAccount: web3.PublicKey;
+    let buyerNftAccount: web3.PublicKey;
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    // 5% maker fee on a 1000 fill of an nft with 10% royalties
+    let price = new anchor.BN(1000)
+    let makerShare = 50
+
+    const balance = async (account: web3.PublicKey): Promise<number> =>
+        (await marketplaceMint.getAccountInfo(account)).amount.toNumber()
+
+    // royalties accrued for the creator and not claimed yet
+    const accruedRoyalties = async (): Promise<number> => {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplace.marketplacePDA, marketplaceMint.publicKey, creator.publicKey)
+        if (await provider.connection.getAccountInfo(royaltyVaultPDA) === null) {
+            return 0
+        }
+        return (await program.account.royaltyVault.fetch(royaltyVaultPDA)).amount.toNumber()
+    }
+
+    const setRoyaltyPolicy = (royaltyPolicy: any) =>
+        program.methods.updateCollection(null, null, null, null, null, royaltyPolicy).accounts({
+            authority: admin.publicKey,
+            marketplace: marketplace.marketplacePDA,
+            collection: collection.collectionPDA,
+        }).signers([admin]).rpc()
+
+    const buy = async (nonce: number, maxTotalPrice: number, royaltyTip?: number) => collection.buy(
+        nftMint.publicKey,
+        [await getSellOrderPDA(sellerNftAccount, new anchor.BN(nonce))],
+        buyerNftAccount,
+        buyerTokenAccount.address,
+        new anchor.BN(1),
+        new anchor.BN(maxTotalPrice),
+        buyer,
+        null,
+        null,
+        null,
+        royaltyTip,
+    )
+
+    // the seller lists one of its copies of the nft
+    const list = (nonce: number) => collection.sellAsset(
+        nftMint.publicKey,
+        sellerNftAccount,
+        sellerTokenAccount.address,
+        new anchor.BN(nonce),
+        price,
+        new anchor.BN(1),
+        seller,
+    )
+
+    // checks how the fill is split between the buyer, the seller and the creator
+    const assertBuy = async (nonce: number, maxTotalPrice: number, royaltyTip: number, royalties: number, tip: number) => {
+        let buyerBalanceBefore = await balance(buyerTokenAccount.address)
+        let sellerBalanceBefore = await balance(sellerTokenAccount.address)
+        let royaltiesBefore = await accruedRoyalties()
+        await buy(nonce, maxTotalPrice, royaltyTip)
+
+        assert.equal(await balance(buyerTokenAccount.address), buyerBalanceBefore - 1000 - tip)
+        assert.equal(await balance(sellerTokenAccount.address), sellerBalanceBefore + 1000 - royalties - makerShare)
+        assert.equal(await accruedRoyalties(), royaltiesBefore + royalties + tip)
+    }
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        marketplaceMint = await splToken.Token.createMint(provider.connection, admin, admin.publicKey, null, 6, TOKEN_PROGRAM_ID)
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey)
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey)
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey)
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 10000)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+        await provider.send(tx, [mint, creator]);
+        nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+        sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey)
+        buyerNftAccount = await nftMint.createAssociatedTokenAccount(buyer.publicKey)
+
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, marketplaceMint.publicKey, 500, adminTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('reject shares above 100%', async () => {
+        await assert.rejects(setRoyaltyPolicy({ tip: { floor: 10001 } }))
+        await assert.rejects(setRoyaltyPolicy({ cap: { maxFees: 10001 } }))
+    });
+
+    it('let buyers tip royalties above the floor', async () => {
+        // half of the 10% royalties are always paid, the buyer can tip the other half
+        await setRoyaltyPolicy({ tip: { floor: 5000 } })
+
+        // the tip is paid on top of the price
+        await list(0)
+        await assert.rejects(buy(0, 1000, 5000))
+        await assertBuy(0, 1050, 5000, 50, 50)
+
+        await list(1)
+        await assertBuy(1, 1000, 0, 50, 0)
+
+        // tips above the rest of the royalties are capped
+        await list(2)
+        await assertBuy(2, 1050, 10000, 50, 50)
+    });
+
+    it('cap royalties', async () => {
+        await setRoyaltyPolicy({ cap: { maxFees: 300 } })
+
+        await list(3)
+        await assertBuy(3, 1000, 0, 30, 0)
+    });
+});
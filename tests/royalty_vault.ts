@@ -0,0 +1,152 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace as MarketplaceDefinition } from '../target/types/marketplace';
+import { NATIVE_MINT, Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, nextEvent } from "./utils/utils";
+import { Marketplace } from '../js/marketplace';
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getRoyaltyVaultPDA, getSellOrderPDA } from "../js/getPDAs";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<MarketplaceDefinition>;
+
+describe('royalty vault', () => {
+    let admin: web3.Keypair;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let buyer: web3.Keypair;
+    let nftMints: Token[] = [];
+    let sellerNftAccounts: web3.PublicKey[] = [];
+    let buyerNftAccounts: web3.PublicKey[] = [];
+
+    let marketplace: Marketplace;
+    let collection: Collection;
+
+    let price = new anchor.BN(web3.LAMPORTS_PER_SOL / 10)
+
+    // royalties accrued for the creator and not claimed yet
+    const accruedRoyalties = async (): Promise<number> => {
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplace.marketplacePDA, NATIVE_MINT, creator.publicKey)
+        if (await provider.connection.getAccountInfo(royaltyVaultPDA) === null) {
+            return 0
+        }
+        return (await program.account.royaltyVault.fetch(royaltyVaultPDA)).amount.toNumber()
+    }
+
+    const sell = async (i: number) => collection.sellAsset(
+        nftMints[i].publicKey,
+        sellerNftAccounts[i],
+        seller.publicKey,
+        new anchor.BN(0),
+        price,
+        new anchor.BN(1),
+        seller,
+    )
+
+    const buy = async (i: number) => collection.buy(
+        nftMints[i].publicKey,
+        [await getSellOrderPDA(sellerNftAccounts[i], new anchor.BN(0))],
+        buyerNftAccounts[i],
+        buyer.publicKey,
+        new anchor.BN(1),
+        price,
+        buyer,
+    )
+
+    it('Prepare tests variables', async () => {
+        admin = web3.Keypair.generate()
+        creator = web3.Keypair.generate()
+        seller = web3.Keypair.generate()
+        buyer = web3.Keypair.generate()
+        for (let account of [admin, creator, seller, buyer]) {
+            let signature = await provider.connection.requestAirdrop(account.publicKey, web3.LAMPORTS_PER_SOL)
+            await provider.connection.confirmTransaction(signature)
+        }
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        for (let i = 0; i < 2; i++) {
+            const [mint, , tx] = await createMint(creator.publicKey, seller.publicKey, lamports, nft_data(creator.publicKey), nft_json_url);
+            await provider.send(tx, [mint, creator]);
+            let nftMint = new Token(provider.connection, mint.publicKey, TOKEN_PROGRAM_ID, seller)
+            nftMints.push(nftMint)
+            sellerNftAccounts.push(await getAssociatedTokenAddress(seller.publicKey, nftMint.publicKey))
+            buyerNftAccounts.push(await nftMint.createAssociatedTokenAccount(buyer.publicKey))
+        }
+
+        // fees are paid straight to the admin wallet
+        marketplace = new Marketplace(provider)
+        await marketplace.createMarketplace(admin, NATIVE_MINT, 500, admin.publicKey, new anchor.BN(0))
+        await marketplace.createCollection(admin, "AURY", creator.publicKey, "AURY", false)
+
+        let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
+        collection = new Collection(provider, marketplace.marketplacePDA, collectionPDA)
+    });
+
+    it('the buyer funds the royalty vault rent the marketplace cannot pay', async () => {
+        await sell(0)
+
+        let buyerBalanceBefore = await provider.connection.getBalance(buyer.publicKey)
+        let marketplaceBalanceBefore = await provider.connection.getBalance(marketplace.marketplacePDA)
+        await buy(0)
+
+        // the buyer pays the price and the vault rent, the marketplace lamports are left untouched
+        let total = price.toNumber()
+        let royaltyVaultRent = await provider.connection.getMinimumBalanceForRentExemption(144)
+        assert.equal(await provider.connection.getBalance(buyer.publicKey), buyerBalanceBefore - total - royaltyVaultRent)
+        assert.equal(await provider.connection.getBalance(marketplace.marketplacePDA), marketplaceBalanceBefore)
+        assert.equal(await accruedRoyalties(), total / 10)
+
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplace.marketplacePDA, NATIVE_MINT, creator.publicKey)
+        let royaltyVault = await program.account.royaltyVault.fetch(royaltyVaultPDA)
+        assert.equal(royaltyVault.rentPayer.toString(), buyer.publicKey.toString())
+    });
+
+    it('creators claim accrued royalties', async () => {
+        let royalties = await accruedRoyalties()
+        assert.ok(royalties > 0)
+
+        let creatorBalanceBefore = await provider.connection.getBalance(creator.publicKey)
+        let buyerBalanceBefore = await provider.connection.getBalance(buyer.publicKey)
+        await assert.rejects(marketplace.claimRoyalties(seller, NATIVE_MINT, seller.publicKey))
+        let claimedEvent = nextEvent(program, "RoyaltiesClaimed")
+        await marketplace.claimRoyalties(creator, NATIVE_MINT, creator.publicKey)
+
+        let event = await claimedEvent
+        assert.equal(event.creator.toString(), creator.publicKey.toString());
+        assert.equal(event.destination.toString(), creator.publicKey.toString());
+        assert.equal(event.amount.toNumber(), royalties);
+
+        // the vault is closed and its rent goes back to the buyer who funded it
+        let royaltyVaultRent = await provider.connection.getMinimumBalanceForRentExemption(144)
+        let royaltyVaultPDA = await getRoyaltyVaultPDA(marketplace.marketplacePDA, NATIVE_MINT, creator.publicKey)
+        assert.equal(await provider.connection.getAccountInfo(royaltyVaultPDA), null)
+        assert.equal(await accruedRoyalties(), 0)
+        assert.equal(await provider.connection.getBalance(creator.publicKey), creatorBalanceBefore + royalties)
+        assert.equal(await provider.connection.getBalance(buyer.publicKey), buyerBalanceBefore + royaltyVaultRent)
+    });
+
+    it('the marketplace funds the royalty vault rent', async () => {
+        await marketplace.fundRoyaltyVaults(admin, web3.LAMPORTS_PER_SOL / 100)
+        await sell(1)
+
+        let buyerBalanceBefore = await provider.connection.getBalance(buyer.publicKey)
+        let marketplaceBalanceBefore = await provider.connection.getBalance(marketplace.marketplacePDA)
+        await buy(1)
+
+        // the buyer only pays the price, the vault rent comes out of the marketplace lamports
+        let total = price.toNumber()
+        let royaltyVaultRent = await provider.connection.getMinimumBalanceForRentExemption(144)
+        assert.equal(await provider.connection.getBalance(buyer.publicKey), buyerBalanceBefore - total)
+        assert.equal(await provider.connection.getBalance(marketplace.marketplacePDA), marketplaceBalanceBefore - royaltyVaultRent)
+        assert.equal(await accruedRoyalties(), total / 10)
+
+        // claiming gives the rent back to the marketplace
+        marketplaceBalanceBefore = await provider.connection.getBalance(marketplace.marketplacePDA)
+        await marketplace.claimRoyalties(creator, NATIVE_MINT, creator.publicKey)
+        assert.equal(await provider.connection.getBalance(marketplace.marketplacePDA), marketplaceBalanceBefore + royaltyVaultRent)
+    });
+});
@@ -8,7 +8,7 @@ import { nft_data, nft_json_url } from "./data";
 import { createMint } from "./utils/utils";
 import { Marketplace } from '../js/marketplace';
 import { Collection } from "../js/collection";
-import { getCollectionPDA, getEscrowPDA, getNftVaultPDA, getSellOrderPDA } from "../js/getPDAs";
+import { getCollectionPDA, getEscrowPDA, getSellOrderPDA, getSellOrderVaultPDA } from "../js/getPDAs";
 
 let provider = anchor.Provider.env()
 anchor.setProvider(provider);
@@ -79,7 +79,8 @@ describe('multi sell orders test', () => {
         sellerNftAssociatedTokenAccount = (await nftMint.getOrCreateAssociatedAccountInfo(seller.publicKey)).address
 
         marketplace = new Marketplace(provider)
-        await marketplace.createMarketplace(seller, marketplaceMint.publicKey, 5, sellerTokenAccount.address)
+        await marketplace.createMarketplace(seller, marketplaceMint.publicKey, 5, sellerTokenAccount.address, new anchor.BN(0))
+        await marketplace.fundRoyaltyVaults(seller, web3.LAMPORTS_PER_SOL / 100)
         await marketplace.createCollection(seller, "AURY", creator.publicKey, "AURY", false)
 
         let collectionPDA = await getCollectionPDA(marketplace.marketplacePDA, "AURY")
@@ -91,6 +92,7 @@ describe('multi sell orders test', () => {
             nftMint.publicKey,
             sellerNftAssociatedTokenAccount,
             sellerTokenAccount.address,
+            new anchor.BN(0),
             new anchor.BN(2000),
             new anchor.BN(2),
             seller,
@@ -100,6 +102,7 @@ describe('multi sell orders test', () => {
             nftMint.publicKey,
             sellerNftAssociatedTokenAccount,
             sellerTokenAccount.address,
+            new anchor.BN(1),
             new anchor.BN(2200),
             new anchor.BN(2),
             seller,
@@ -108,10 +111,13 @@ describe('multi sell orders test', () => {
         let sellerAfterSell = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
         assert.equal(sellerAfterSell.amount.toNumber(), 1)
 
-        let nftVaultAddr = await getNftVaultPDA(nftMint.publicKey)
+        let nftVaultAddr = await getSellOrderVaultPDA(
+            marketplace.marketplacePDA,
+            await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(1)),
+        )
         let vaultAfterSell = await nftMint.getAccountInfo(nftVaultAddr)
 
-        assert.equal(vaultAfterSell.amount.toNumber(), 4)
+        assert.equal(vaultAfterSell.amount.toNumber(), 2)
 
         let buyer = anchor.web3.Keypair.generate()
         let fromAirdropSignature = await provider.connection.requestAirdrop(
@@ -128,12 +134,13 @@ describe('multi sell orders test', () => {
         await collection.buy(
             nftMint.publicKey,
             [
-                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(2000)),
-                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(2200)),
+                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(0)),
+                await getSellOrderPDA(sellerNftAssociatedTokenAccount, new anchor.BN(1)),
             ],
             buyerNftATA,
             buyerTokenATA,
             new anchor.BN(4),
+            new anchor.BN(8400),
             buyer,
         )
 
@@ -143,7 +150,42 @@ describe('multi sell orders test', () => {
         let buyerTokenAccountAfterSell = await marketplaceMint.getAccountInfo(buyerTokenATA)
         assert.equal(buyerTokenAccountAfterSell.amount.toNumber(), 0)
 
+        await marketplace.claimRoyalties(creator, marketplaceMint.publicKey, creatorTokenAccount.address)
+
         let creatorTokenAccountAfterSell = await marketplaceMint.getAccountInfo(creatorTokenAccount.address)
         assert.equal(creatorTokenAccountAfterSell.amount.toNumber(), 840)
     });
+
+    it('expire sell order', async function () {
+        let nonce = new anchor.BN(2)
+        let price = new anchor.BN(2500)
+        let expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 2)
+        await collection.sellAsset(
+            nftMint.publicKey,
+            sellerNftAssociatedTokenAccount,
+            sellerTokenAccount.address,
+            nonce,
+            price,
+            new anchor.BN(1),
+            seller,
+            expiry,
+        )
+
+        let sellerAfterSell = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
+        assert.equal(sellerAfterSell.amount.toNumber(), 0)
+
+        let sellOrderPDA = await getSellOrderPDA(sellerNftAssociatedTokenAccount, nonce)
+        await assert.rejects(
+            collection.expireSellOrder(nftMint.publicKey, sellerNftAssociatedTokenAccount, sellOrderPDA, seller.publicKey)
+        )
+
+        await new Promise(resolve => setTimeout(resolve, 4000))
+        await collection.expireSellOrder(nftMint.publicKey, sellerNftAssociatedTokenAccount, sellOrderPDA, seller.publicKey)
+
+        let sellerAfterExpire = await nftMint.getAccountInfo(sellerNftAssociatedTokenAccount)
+        assert.equal(sellerAfterExpire.amount.toNumber(), 1)
+
+        let closedSellOrder = await provider.connection.getAccountInfo(sellOrderPDA)
+        assert.equal(closedSellOrder, null)
+    });
 });
@@ -0,0 +1,213 @@
+import * as anchor from '@project-serum/anchor';
+import { Program, web3 } from '@project-serum/anchor';
+import { Marketplace } from '../target/types/marketplace';
+import * as splToken from '@solana/spl-token';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import assert from "assert";
+import { nft_data, nft_json_url } from "./data";
+import { createMint, fundRoyaltyVaults, nextEvent } from "./utils/utils";
+import { Collection } from "../js/collection";
+import { getAssociatedTokenAddress, getCollectionPDA, getEscrowPDA, getMarketplacePDA, getPaymentMintPDA, getRoyaltyEscrowPDA, getRoyaltyVaultPDA, getTraitOfferPDA } from "../js/getPDAs";
+import { getMerkleRoot } from "../js/merkle";
+
+let provider = anchor.Provider.env()
+anchor.setProvider(provider);
+
+const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+
+describe('trait offers', () => {
+    let admin: web3.Keypair;
+    let adminTokenAccount: splToken.AccountInfo;
+    let buyer: web3.Keypair;
+    let buyerTokenAccount: splToken.AccountInfo;
+    let creator: web3.Keypair;
+    let seller: web3.Keypair;
+    let sellerTokenAccount: splToken.AccountInfo;
+    let marketplacePDA: web3.PublicKey;
+    let marketplaceMint: splToken.Token;
+    let escrowPDA: web3.PublicKey;
+    let collectionPDA: web3.PublicKey;
+    let collection: Collection;
+    let nftMint: web3.PublicKey;
+    let outsiderNftMint: web3.PublicKey;
+    let traitMints: web3.PublicKey[];
+    let fee = 200;
+    let collectionName = "AURY"
+
+    it('Prepare tests variables', async () => {
+        admin = anchor.web3.Keypair.generate()
+        creator = anchor.web3.Keypair.generate()
+        seller = anchor.web3.Keypair.generate()
+        buyer = anchor.web3.Keypair.generate()
+        for (let wallet of [admin, creator, seller, buyer]) {
+            let fromAirdropSignature = await provider.connection.requestAirdrop(
+                wallet.publicKey,
+                anchor.web3.LAMPORTS_PER_SOL,
+            );
+            await provider.connection.confirmTransaction(fromAirdropSignature);
+        }
+
+        marketplacePDA = await getMarketplacePDA(admin.publicKey)
+        marketplaceMint = await splToken.Token.createMint(
+            provider.connection,
+            admin,
+            admin.publicKey,
+            null,
+            6,
+            splToken.TOKEN_PROGRAM_ID,
+        );
+        adminTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(admin.publicKey);
+        sellerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+        buyerTokenAccount = await marketplaceMint.getOrCreateAssociatedAccountInfo(buyer.publicKey);
+        await marketplaceMint.mintTo(buyerTokenAccount.address, admin, [], 2040);
+
+        escrowPDA = await getEscrowPDA(marketplacePDA, marketplaceMint.publicKey)
+        collectionPDA = await getCollectionPDA(marketplacePDA, collectionName)
+
+        const lamports = await Token.getMinBalanceRentForExemptMint(provider.connection);
+        let mints = []
+        for (let i = 0; i < 2; i++) {
+            const [mint, , tx] = await createMint(
+                creator.publicKey,
+                seller.publicKey,
+                lamports,
+                nft_data(creator.publicKey),
+                nft_json_url,
+            );
+            await provider.send(tx, [mint, creator]);
+            mints.push(mint.publicKey)
+        }
+        nftMint = mints[0]
+        outsiderNftMint = mints[1]
+        // the trait only holds the first nft and two mints the seller doesn't own
+        traitMints = [anchor.web3.Keypair.generate().publicKey, nftMint, anchor.web3.Keypair.generate().publicKey]
+
+        await program.methods.createMarketplace(marketplaceMint.publicKey, fee, adminTokenAccount.address, admin.publicKey, new anchor.BN(0)).accounts(
+            {
+                payer: admin.publicKey,
+                marketplace: marketplacePDA,
+                mint: marketplaceMint.publicKey,
+                escrow: escrowPDA,
+                royaltyEscrow: await getRoyaltyEscrowPDA(marketplacePDA, marketplaceMint.publicKey),
+                paymentMint: await getPaymentMintPDA(marketplacePDA, marketplaceMint.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc();
+
+        // fills pay the rent of new royalty vaults from the marketplace lamports
+        await fundRoyaltyVaults(provider.connection, admin, marketplacePDA)
+
+        await program.methods.createCollection(collectionName, creator.publicKey, null, false, { full: {} }).accounts(
+            {
+                authority: admin.publicKey,
+                marketplace: marketplacePDA,
+                collection: collectionPDA,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            }).signers([admin]).rpc()
+
+        collection = new Collection(provider, marketplacePDA, collectionPDA)
+    });
+
+    it('create trait offer', async () => {
+        let price = new anchor.BN(1000)
+        let createdEvent = nextEvent(program, "TraitOfferCreated")
+        await collection.createTraitOffer(traitMints, price, new anchor.BN(2), buyerTokenAccount.address, buyer)
+
+        let traitOfferPDA = await getTraitOfferPDA(marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), price)
+        let traitOffer = await program.account.traitOffer.fetch(traitOfferPDA)
+        assert.equal(Buffer.from(traitOffer.merkleRoot).toString('hex'), getMerkleRoot(traitMints).toString('hex'));
+        assert.equal(traitOffer.quantity.toNumber(), 2);
+
+        // the 2% maker fee of each nft is escrowed on top of the price
+        assert.equal(traitOffer.makerFee.toNumber(), 20);
+        let escrowAccount = await marketplaceMint.getAccountInfo(escrowPDA)
+        assert.equal(escrowAccount.amount.toNumber(), 2040);
+
+        let event = await createdEvent
+        assert.equal(event.offer.toString(), traitOfferPDA.toString());
+        assert.equal(Buffer.from(event.merkleRoot).toString('hex'), getMerkleRoot(traitMints).toString('hex'));
+        assert.equal(event.paymentMint.toString(), marketplaceMint.publicKey.toString());
+        assert.equal(event.quantity.toNumber(), 2);
+    });
+
+    it('reject an nft outside of the trait', async () => {
+        let traitOfferPDA = await getTraitOfferPDA(marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), new anchor.BN(1000))
+        let sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, outsiderNftMint)
+
+        // a valid proof of another tree doesn't verify against the offer root
+        await assert.rejects(collection.executeTraitOffer(
+            traitOfferPDA,
+            [traitMints[0], outsiderNftMint],
+            outsiderNftMint,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            seller,
+        ))
+
+        let traitOffer = await program.account.traitOffer.fetch(traitOfferPDA)
+        assert.equal(traitOffer.quantity.toNumber(), 2);
+    });
+
+    it('execute trait offer', async () => {
+        let traitOfferPDA = await getTraitOfferPDA(marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), new anchor.BN(1000))
+        let sellerNftAccount = await getAssociatedTokenAddress(seller.publicKey, nftMint)
+
+        let filledEvent = nextEvent(program, "TraitOfferFilled")
+        await collection.executeTraitOffer(
+            traitOfferPDA,
+            traitMints,
+            nftMint,
+            sellerNftAccount,
+            sellerTokenAccount.address,
+            seller,
+        )
+
+        let buyerNftAccount = await new Token(provider.connection, nftMint, TOKEN_PROGRAM_ID, admin)
+            .getAccountInfo(await getAssociatedTokenAddress(buyer.publicKey, nftMint))
+        assert.equal(buyerNftAccount.amount.toNumber(), 1);
+
+        // 1000 minus the 2% marketplace fee and the 10% royalties
+        let updatedSellerAccount = await marketplaceMint.getAccountInfo(sellerTokenAccount.address)
+        assert.equal(updatedSellerAccount.amount.toNumber(), 900);
+
+        let updatedAdminAccount = await marketplaceMint.getAccountInfo(adminTokenAccount.address)
+        assert.equal(updatedAdminAccount.amount.toNumber(), 20);
+
+        let royaltyVault = await program.account.royaltyVault.fetch(
+            await getRoyaltyVaultPDA(marketplacePDA, marketplaceMint.publicKey, creator.publicKey)
+        )
+        assert.equal(royaltyVault.amount.toNumber(), 100);
+
+        let traitOffer = await program.account.traitOffer.fetch(traitOfferPDA)
+        assert.equal(traitOffer.quantity.toNumber(), 1);
+
+        let event = await filledEvent
+        assert.equal(event.offer.toString(), traitOfferPDA.toString());
+        assert.equal(event.mint.toString(), nftMint.toString());
+        assert.equal(event.seller.toString(), seller.publicKey.toString());
+        assert.equal(event.marketplaceShare.toNumber(), 20);
+        assert.equal(event.creatorShares[0].amount.toNumber(), 100);
+        assert.equal(event.sellerShare.toNumber(), 900);
+        assert.equal(event.remainingQuantity.toNumber(), 1);
+    });
+
+    it('remove trait offer', async () => {
+        let traitOfferPDA = await getTraitOfferPDA(marketplacePDA, buyer.publicKey, getMerkleRoot(traitMints), new anchor.BN(1000))
+        let cancelledEvent = nextEvent(program, "TraitOfferCancelled")
+        await collection.removeTraitOffer(traitOfferPDA, buyerTokenAccount.address, buyer)
+
+        let updatedBuyerAccount = await marketplaceMint.getAccountInfo(buyerTokenAccount.address)
+        assert.equal(updatedBuyerAccount.amount.toNumber(), 1020);
+
+        let closedTraitOffer = await provider.connection.getAccountInfo(traitOfferPDA);
+        assert.equal(closedTraitOffer, null);
+
+        let event = await cancelledEvent
+        assert.equal(event.offer.toString(), traitOfferPDA.toString());
+        assert.equal(event.quantity.toNumber(), 1);
+    });
+})
+    ;
@@ -1,11 +1,12 @@
 import { programs } from '@metaplex/js';
-import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey, sendAndConfirmTransaction, SystemProgram } from '@solana/web3.js';
 import {
     ASSOCIATED_TOKEN_PROGRAM_ID,
     MintLayout,
     TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 import { Token } from '@solana/spl-token';
+import { Program } from '@project-serum/anchor';
 
 const { Metadata, MetadataDataData, CreateMetadata, Creator } =
     programs.metadata;
@@ -93,4 +94,30 @@ export async function createMint(
 
     const tx = Transaction.fromCombined([tx_mint, tx_metadata]);
     return [mint, metadataPDA, tx];
-}
\ No newline at end of file
+}
+
+// fills pay the rent of new royalty vaults from the lamports the marketplace holds above its own rent
+export async function fundRoyaltyVaults(
+    connection: Connection,
+    payer: Keypair,
+    marketplacePDA: PublicKey,
+): Promise<string> {
+    const tx = new Transaction().add(
+        SystemProgram.transfer({
+            fromPubkey: payer.publicKey,
+            toPubkey: marketplacePDA,
+            lamports: LAMPORTS_PER_SOL / 100,
+        })
+    );
+    return sendAndConfirmTransaction(connection, tx, [payer]);
+}
+
+// resolves with the next event of that name emitted by the program, start listening before sending the transaction
+export function nextEvent(program: Program<any>, name: string): Promise<any> {
+    return new Promise((resolve) => {
+        let listener = program.addEventListener(name, (event) => {
+            program.removeEventListener(listener)
+            resolve(event)
+        })
+    })
+}